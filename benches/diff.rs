@@ -43,17 +43,177 @@ fn bench_func(
 }
 
 fn diff_benchmark(c: &mut criterion::Criterion) {
-    let ksub94 = empfindung::cie94::Params::graphic();
+    use empfindung::{cie00, cie76, cie94, cmc};
+
+    let colours = generate_colours(1_000);
+    bench_func(c, &colours, "cie76", cie76::diff_fn());
+    bench_func(c, &colours, "cie94", cie94::diff_fn(cie94::Params::graphic()));
+    bench_func(c, &colours, "cie00", cie00::diff_fn(cie00::Params::default()));
+    bench_func(c, &colours, "cmc", cmc::diff_fn(cmc::LC11));
+}
+
+/// Compares calling CIEDE2000 through the generic `impl ToLab` interface
+/// with the closure returned by `diff_fn` which operates on L\*a\*b\* tuples
+/// directly and doesn’t go through `ToLab` conversion.
+fn dispatch_benchmark(c: &mut criterion::Criterion) {
+    use empfindung::cie00;
+
+    let colours = generate_colours(1_000);
+    bench_func(c, &colours, "cie00/generic", cie00::diff);
+    let direct = cie00::diff_fn(cie00::Params::default());
+    bench_func(c, &colours, "cie00/direct", direct);
+}
+
+/// Compares calling CIEDE2000 in a loop with using `cie00::Reference`.  The
+/// first reference lies on the a\* axis so `Reference` caches its h′ hue
+/// angle; the second one doesn’t.
+fn reference_benchmark(c: &mut criterion::Criterion) {
+    use empfindung::cie00;
+
     let colours = generate_colours(1_000);
-    bench_func(c, &colours, "cie76", empfindung::cie76::diff);
-    bench_func(c, &colours, "cie94", |a, b| {
-        empfindung::cie94::diff(a, b, ksub94)
+    let mut out = vec![0.0; colours.len()];
+
+    for (name, reference) in
+        [("axis", (50.0, 2.5, 0.0)), ("off-axis", (50.0, 2.5, -7.0))]
+    {
+        let id = format!("cie00/one-to-many/{}/naive", name);
+        c.bench_function(&id, |b| {
+            b.iter(|| {
+                for (out, colour) in out.iter_mut().zip(colours.iter()) {
+                    *out = cie00::diff(reference, *colour);
+                }
+                criterion::black_box(&mut out);
+            });
+        });
+        let id = format!("cie00/one-to-many/{}/reference", name);
+        c.bench_function(&id, |b| {
+            b.iter(|| {
+                let reference = cie00::Reference::new(reference);
+                for (out, colour) in out.iter_mut().zip(colours.iter()) {
+                    *out = reference.diff(*colour);
+                }
+                criterion::black_box(&mut out);
+            });
+        });
+    }
+}
+
+/// Compares `cie00::diff_rgb_many`, which converts pixels into L\*a\*b\* in
+/// bulk, with calling `cie00::diff` on each sRGB pixel.
+#[cfg(all(feature = "lab", feature = "rgb"))]
+fn rgb_many_benchmark(c: &mut criterion::Criterion) {
+    use empfindung::cie00;
+
+    let pixels = (0..=255u8)
+        .step_by(5)
+        .flat_map(|r| {
+            (0..=255u8).step_by(17).map(move |g| rgb::RGB8::new(r, g, r ^ g))
+        })
+        .collect::<Vec<_>>();
+    let reference = rgb::RGB8::new(234, 76, 76);
+    let mut out = vec![0.0; pixels.len()];
+
+    c.bench_function("cie00/rgb-many/naive", |b| {
+        b.iter(|| {
+            for (out, pixel) in out.iter_mut().zip(pixels.iter()) {
+                *out = cie00::diff(reference, pixel);
+            }
+            criterion::black_box(&mut out);
+        });
+    });
+    c.bench_function("cie00/rgb-many/batched", |b| {
+        b.iter(|| {
+            cie00::diff_rgb_many(reference, &pixels, &mut out);
+            criterion::black_box(&mut out);
+        });
     });
-    bench_func(c, &colours, "cie00", empfindung::cie00::diff);
-    bench_func(c, &colours, "cmc", |a, b| {
-        empfindung::cmc::diff(a, b, (1.0, 1.0))
+}
+
+#[cfg(not(all(feature = "lab", feature = "rgb")))]
+fn rgb_many_benchmark(_: &mut criterion::Criterion) {}
+
+fn slice_benchmark(c: &mut criterion::Criterion) {
+    use empfindung::cie00;
+
+    let colours = generate_colours(1_001);
+    let (colours_1, colours_2) = (&colours[1..], &colours[..1_000]);
+    let ksub = cie00::Params::default();
+    let mut out = vec![0.0; 1_000];
+
+    c.bench_function("cie00/slice/scalar", |b| {
+        b.iter(|| {
+            let pairs = colours_1.iter().zip(colours_2.iter());
+            for (out, (colour_1, colour_2)) in out.iter_mut().zip(pairs) {
+                *out = cie00::diff_with_params(*colour_1, *colour_2, ksub);
+            }
+            criterion::black_box(&mut out);
+        });
+    });
+    #[cfg(feature = "simd")]
+    c.bench_function("cie00/slice/simd", |b| {
+        b.iter(|| {
+            cie00::diff_slice_simd(colours_1, colours_2, ksub, &mut out);
+            criterion::black_box(&mut out);
+        });
+    });
+}
+
+fn srgb_benchmark(c: &mut criterion::Criterion) {
+    #[cfg(feature = "rgb")]
+    use empfindung::ToLab;
+
+    let pixels = (0..=255u8)
+        .step_by(5)
+        .flat_map(|r| (0..=255u8).step_by(17).map(move |g| [r, g, r ^ g]))
+        .collect::<Vec<_>>();
+    let mut out = vec![(0.0, 0.0, 0.0); pixels.len()];
+
+    #[cfg(all(feature = "lab", feature = "rgb"))]
+    c.bench_function("srgb8/to-lab/exact", |b| {
+        b.iter(|| {
+            for (out, pixel) in out.iter_mut().zip(pixels.iter()) {
+                *out = rgb::RGB8::from(*pixel).to_lab();
+            }
+            criterion::black_box(&mut out);
+        });
+    });
+    // Gamma decoding of each component followed by the matrix multiplication
+    // which is what the lookup table replaces.  Converting the components to
+    // f32 is done upfront so that it isn’t measured.
+    #[cfg(feature = "rgb")]
+    c.bench_function("srgb8/to-lab/decode", |b| {
+        let pixels = pixels
+            .iter()
+            .map(|&[r, g, b]| {
+                let norm = |c: u8| f32::from(c) / 255.0;
+                rgb::RGB::new(norm(r), norm(g), norm(b))
+            })
+            .collect::<Vec<_>>();
+        b.iter(|| {
+            for (out, pixel) in out.iter_mut().zip(pixels.iter()) {
+                *out = pixel.to_lab();
+            }
+            criterion::black_box(&mut out);
+        });
+    });
+    let lut = empfindung::Srgb8Lut::new();
+    c.bench_function("srgb8/to-lab/lut", |b| {
+        b.iter(|| {
+            for (out, pixel) in out.iter_mut().zip(pixels.iter()) {
+                *out = lut.to_lab(*pixel);
+            }
+            criterion::black_box(&mut out);
+        });
     });
 }
 
-criterion_group!(benches, diff_benchmark,);
+criterion_group!(
+    benches,
+    diff_benchmark,
+    dispatch_benchmark,
+    reference_benchmark,
+    rgb_many_benchmark,
+    slice_benchmark,
+    srgb_benchmark
+);
 criterion_main!(benches);
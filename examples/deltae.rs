@@ -19,53 +19,26 @@
 // OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
 // SOFTWARE.
 
-fn parse(arg: std::ffi::OsString) -> Result<u32, String> {
-    let arg = arg.into_string().map_err(|arg| {
-        format!("{}: not valid Unicode", arg.to_string_lossy())
-    })?;
-    if arg.len() != 7 ||
-        arg.as_bytes()[0] != '#' as u8 ||
-        arg.as_bytes()[1] == '+' as u8
-    {
-        Err(format!("{}: expected argument in #RRGGBB format", arg))
-    } else {
-        u32::from_str_radix(&arg[1..], 16)
-            .map_err(|err| format!("{}: {}", arg, err))
-    }
-}
+use core::convert::TryFrom;
 
-fn parse_args() -> Result<(u32, u32), (std::ffi::OsString, String)> {
-    let mut argv0 = "example".into();
-    let mut colours = [0, 0];
-    let mut n = 0;
-    for arg in std::env::args_os() {
-        if n == 0 {
-            argv0 = arg;
-        } else if n < 3 {
-            colours[n - 1] = match parse(arg) {
-                Ok(colour) => colour,
-                Err(msg) => return Err((argv0, msg)),
-            };
-        } else {
-            break;
-        }
-        n += 1;
-    }
-    if n != 3 {
-        Err((argv0, String::from("Expected two arguments")))
-    } else {
-        Ok((colours[0], colours[1]))
-    }
+use empfindung::Hex;
+
+fn parse(arg: std::ffi::OsString) -> Result<Hex, String> {
+    Hex::try_from(arg.as_os_str())
+        .map_err(|err| format!("{}: {}", arg.to_string_lossy(), err))
 }
 
-// Return (f32, f32, f32) rather than lab::Lab so that the example works even if
-// the library is built without lab feature.  Normally, this would just return
-// lab::Lab and the library would be built with lab feature enabled (which is
-// the default).
-fn from_rgb(rgb: u32) -> (f32, f32, f32) {
-    let lab =
-        lab::Lab::from_rgb(&[(rgb >> 16) as u8, (rgb >> 8) as u8, rgb as u8]);
-    (lab.l, lab.a, lab.b)
+fn parse_args() -> Result<(Hex, Hex), (std::ffi::OsString, String)> {
+    let mut args = std::env::args_os();
+    let argv0 = args.next().unwrap_or_else(|| "example".into());
+    let colours = match args.map(parse).collect::<Result<Vec<Hex>, _>>() {
+        Ok(colours) => colours,
+        Err(msg) => return Err((argv0, msg)),
+    };
+    match colours[..] {
+        [a, b] => Ok((a, b)),
+        _ => Err((argv0, String::from("Expected two arguments"))),
+    }
 }
 
 pub fn main() {
@@ -83,22 +56,21 @@ pub fn main() {
         }
     };
 
-    let a = from_rgb(a);
-    let b = from_rgb(b);
-    println!("ΔE_76  = {:>11.7}  (Euclidean distance)", cie76::diff(a, b));
+    let metrics = all_metrics(a, b);
+    println!("ΔE_76  = {:>11.7}  (Euclidean distance)", metrics.cie76);
     println!(
         "ΔE_94g = {:>11.7}  (parameters for graphic arts)",
-        cie94::diff(a, b, cie94::Params::graphic())
+        metrics.cie94_graphic
     );
     println!(
         "ΔE_94t = {:>11.7}  (parameters for textiles)",
-        cie94::diff(a, b, cie94::Params::textiles())
+        metrics.cie94_textiles
     );
-    println!("ΔE_00  = {:>11.7}  (default parameters", cie00::diff(a, b));
+    println!("ΔE_00  = {:>11.7}  (default parameters)", metrics.cie00);
     println!(
         "ΔE_00y = {:>11.7}  (parameters by Yang et al)",
-        cie00::diff_with_params(a, b, cie00::Params::yang2012())
+        metrics.cie00_yang
     );
-    println!("ΔE_1:1 = {:>11.7}  (CMC 1:1)", cmc::diff(a, b, cmc::LC11));
-    println!("ΔE_1:1 = {:>11.7}  (CMC 2:1)", cmc::diff(a, b, cmc::LC21));
+    println!("ΔE_1:1 = {:>11.7}  (CMC 1:1)", metrics.cmc_11);
+    println!("ΔE_2:1 = {:>11.7}  (CMC 2:1)", metrics.cmc_21);
 }
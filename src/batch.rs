@@ -0,0 +1,472 @@
+// Batch colour difference computation.
+// Copyright (c) 2026 Michał Nazarewicz <mina86@mina86.com>
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in
+// all copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+
+//! Colour difference computation over many colours at once.
+//!
+//! As in [`crate::set`] module, functions here are parameterised by the colour
+//! difference function passed as a `diff` argument implementing
+//! [`crate::Metric`] trait.
+
+use alloc::vec::Vec;
+
+/// Calculates colour differences between L\*a\*b\* colours stored in planar
+/// arrays.
+///
+/// Each of the two sets of colours is given as three planes holding L\*, a\*
+/// and b\* coordinates respectively.  For each index `i`, `out[i]` is set to
+/// `diff((l_1[i], a_1[i], b_1[i]), (l_2[i], a_2[i], b_2[i]))` where
+/// `[l_1, a_1, b_1]` are `planes_1` and `[l_2, a_2, b_2]` are `planes_2`.
+///
+/// This layout is common in columnar storage and GPU readback buffers.
+///
+/// # Panics
+///
+/// Panics if length of any of the planes differs from length of `out`.
+///
+/// ## Example
+///
+/// ```
+/// use empfindung::{batch, cie76};
+///
+/// let l = [50.0, 50.0];
+/// let a = [0.0, 3.0];
+/// let b = [0.0, 4.0];
+/// let zero = [0.0, 0.0];
+/// let mut out = [0.0; 2];
+/// batch::diff_planar([&l, &a, &b], [&l, &zero, &zero], cie76::diff_fn(),
+///                    &mut out);
+/// assert_eq!([0.0, 5.0], out);
+/// ```
+pub fn diff_planar(
+    planes_1: [&[f32]; 3],
+    planes_2: [&[f32]; 3],
+    diff: impl crate::Metric,
+    out: &mut [f32],
+) {
+    let len = out.len();
+    for plane in planes_1.iter().chain(planes_2.iter()) {
+        assert_eq!(len, plane.len(), "plane and output lengths differ");
+    }
+    let [l_1, a_1, b_1] = planes_1;
+    let [l_2, a_2, b_2] = planes_2;
+    for (i, out) in out.iter_mut().enumerate() {
+        *out = diff.diff((l_1[i], a_1[i], b_1[i]), (l_2[i], a_2[i], b_2[i]));
+    }
+}
+
+/// Converts colours into L\*a\*b\*.
+///
+/// Conversion into L\*a\*b\* (especially from sRGB) may take significant
+/// portion of time spent calculating colour differences.  When comparing the
+/// same colours using several formulæ, it’s beneficial to convert them once
+/// and pass the resulting slice to functions in this and [`crate::set`]
+/// modules; L\*a\*b\* tuples implement [`crate::ToLab`] trivially.  The
+/// results are bit-for-bit identical to passing the original colours.
+///
+/// ## Example
+///
+/// ```
+/// use empfindung::{batch, cie00, cie76};
+///
+/// let colours = [[50.0, 0.0, 0.0], [50.0, 3.0, 4.0]];
+/// let labs = batch::to_lab_vec(&colours[..]);
+/// assert_eq!(vec![(50.0, 0.0, 0.0), (50.0, 3.0, 4.0)], labs);
+///
+/// let mut out = [0.0; 2];
+/// batch::diff_many((50.0, 0.0, 0.0), &labs, cie76::diff_fn(), &mut out);
+/// assert_eq!([0.0, 5.0], out);
+/// let params = cie00::Params::default();
+/// batch::diff_many((50.0, 0.0, 0.0), &labs, cie00::diff_fn(params), &mut out);
+/// ```
+pub fn to_lab_vec<C: crate::ToLab>(colours: &[C]) -> Vec<(f32, f32, f32)> {
+    colours.iter().map(crate::ToLab::to_lab).collect()
+}
+
+/// Calculates colour differences between a reference colour and each colour
+/// in a slice.
+///
+/// The reference colour is converted to L\*a\*b\* only once.  For each index
+/// `i`, `out[i]` is set to `diff(reference, colours[i])` with both colours
+/// converted to L\*a\*b\*.  The results are bit-for-bit identical to calling
+/// `diff` in a loop.
+///
+/// Modules implementing colour difference formulæ provide `diff_many`
+/// functions wrapping this function with their respective `diff` function.
+///
+/// # Panics
+///
+/// Panics if length of `colours` differs from length of `out`.
+///
+/// ## Example
+///
+/// ```
+/// use empfindung::{batch, cie76};
+///
+/// let colours = [(50.0, 0.0, 0.0), (50.0, 3.0, 4.0)];
+/// let mut out = [0.0; 2];
+/// batch::diff_many((50.0, 0.0, 0.0), &colours, cie76::diff_fn(), &mut out);
+/// assert_eq!([0.0, 5.0], out);
+/// ```
+pub fn diff_many<C: crate::ToLab>(
+    reference: impl crate::ToLab,
+    colours: &[C],
+    diff: impl crate::Metric,
+    out: &mut [f32],
+) {
+    assert_eq!(
+        colours.len(),
+        out.len(),
+        "colours and output lengths differ"
+    );
+    let reference = reference.to_lab();
+    for (out, colour) in out.iter_mut().zip(colours.iter()) {
+        *out = diff.diff(reference, colour.to_lab());
+    }
+}
+
+/// Calculates colour differences between a reference colour and each colour
+/// in a slice in parallel.
+///
+/// This is a parallel version of [`diff_many`] which splits the work among
+/// threads of the global rayon thread pool.  Each element of `out` is
+/// calculated independently so the results are identical to those of
+/// [`diff_many`] regardless of the number of threads.
+///
+/// The function is available with `rayon` feature.
+///
+/// # Panics
+///
+/// Panics if length of `colours` differs from length of `out`.
+///
+/// ## Example
+///
+/// ```
+/// use empfindung::{batch, cie00};
+///
+/// let colours = [(50.0, 0.0, 0.0), (50.0, 3.0, 4.0), (60.0, -3.0, 4.0)];
+/// let diff = cie00::diff_fn(cie00::Params::default());
+/// let mut out = [0.0; 3];
+/// batch::par_diff_many((50.0, 0.0, 0.0), &colours, diff, &mut out);
+/// let mut want = [0.0; 3];
+/// batch::diff_many((50.0, 0.0, 0.0), &colours, diff, &mut want);
+/// assert_eq!(want, out);
+/// ```
+#[cfg(feature = "rayon")]
+pub fn par_diff_many<C: crate::ToLab + Sync>(
+    reference: impl crate::ToLab,
+    colours: &[C],
+    diff: impl crate::Metric + Sync,
+    out: &mut [f32],
+) {
+    use rayon::prelude::*;
+
+    assert_eq!(
+        colours.len(),
+        out.len(),
+        "colours and output lengths differ"
+    );
+    let reference = reference.to_lab();
+    out.par_iter_mut()
+        .zip(colours.par_iter())
+        .for_each(|(out, colour)| {
+            *out = diff.diff(reference, colour.to_lab());
+        });
+}
+
+/// Running statistics of colour differences.
+///
+/// The accumulator collects colour differences one at a time and keeps track
+/// of their count, mean and maximum as well as a histogram used to
+/// approximate the 95th percentile.  It uses constant memory regardless of
+/// number of values pushed which makes it suitable for processing streams of
+/// data such as video frames.
+///
+/// The histogram divides the `0..100` range into buckets 0.5 wide with one
+/// more bucket for all values of 100 and above.  NaN values are ignored.
+///
+/// ## Example
+///
+/// ```
+/// use empfindung::batch::DeltaAccumulator;
+///
+/// let mut acc = DeltaAccumulator::new();
+/// for delta_e in [1.0, 2.0, 3.0, 10.0] {
+///     acc.push(delta_e);
+/// }
+/// assert_eq!(4, acc.count());
+/// assert_eq!(4.0, acc.mean());
+/// assert_eq!(10.0, acc.max());
+/// assert_eq!(10.0, acc.p95());
+/// ```
+#[derive(Clone, PartialEq, Debug)]
+pub struct DeltaAccumulator {
+    count: u64,
+    sum: f64,
+    max: f32,
+    histogram: [u64; Self::BUCKETS],
+}
+
+impl DeltaAccumulator {
+    /// Width of a histogram bucket.
+    const BUCKET_WIDTH: f32 = 0.5;
+    /// Number of histogram buckets; the last one holds values of 100 and
+    /// above.
+    const BUCKETS: usize = 201;
+
+    /// Returns a new accumulator with no values.
+    pub const fn new() -> Self {
+        Self { count: 0, sum: 0.0, max: 0.0, histogram: [0; Self::BUCKETS] }
+    }
+
+    /// Adds a colour difference to the statistics.  NaN values are ignored.
+    pub fn push(&mut self, delta_e: f32) {
+        if delta_e.is_nan() {
+            return;
+        }
+        self.count += 1;
+        self.sum += f64::from(delta_e);
+        if self.count == 1 || delta_e > self.max {
+            self.max = delta_e;
+        }
+        let bucket = (delta_e.max(0.0) / Self::BUCKET_WIDTH) as usize;
+        self.histogram[bucket.min(Self::BUCKETS - 1)] += 1;
+    }
+
+    /// Returns number of colour differences pushed so far.
+    pub fn count(&self) -> u64 { self.count }
+
+    /// Returns mean of the colour differences or zero if there are none.
+    pub fn mean(&self) -> f32 {
+        if self.count == 0 {
+            0.0
+        } else {
+            (self.sum / self.count as f64) as f32
+        }
+    }
+
+    /// Returns the largest colour difference or zero if there are none.
+    pub fn max(&self) -> f32 { self.max }
+
+    /// Returns approximate 95th percentile of the colour differences or zero
+    /// if there are none.
+    ///
+    /// The result is the upper bound of the histogram bucket containing the
+    /// percentile (but no more than [`Self::max`]) so it overestimates the
+    /// exact value by at most the width of a bucket.  If the percentile falls
+    /// in the last bucket (i.e. is 100 or more), the maximum is returned.
+    pub fn p95(&self) -> f32 {
+        // Number of values at or below the percentile, i.e. ⌈0.95·count⌉.
+        let rank = (self.count * 95).div_ceil(100);
+        let mut seen = 0;
+        for (bucket, &count) in self.histogram.iter().enumerate() {
+            seen += count;
+            if seen >= rank && seen != 0 {
+                if bucket == Self::BUCKETS - 1 {
+                    return self.max;
+                }
+                let upper = (bucket + 1) as f32 * Self::BUCKET_WIDTH;
+                return upper.min(self.max);
+            }
+        }
+        0.0
+    }
+}
+
+impl Default for DeltaAccumulator {
+    fn default() -> Self { Self::new() }
+}
+
+impl Extend<f32> for DeltaAccumulator {
+    fn extend<I: IntoIterator<Item = f32>>(&mut self, iter: I) {
+        iter.into_iter().for_each(|delta_e| self.push(delta_e));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    #[test]
+    fn test_to_lab_vec() {
+        let colours = crate::testutil::generate_colours(100)
+            .into_iter()
+            .map(|(l, a, b)| [l, a, b])
+            .collect::<Vec<_>>();
+        let labs = super::to_lab_vec(&colours);
+        for (want, got) in colours.iter().zip(labs.iter()) {
+            assert_eq!(crate::ToLab::to_lab(want), *got);
+        }
+
+        let reference = colours[0];
+        let mut want = [0.0; 100];
+        let mut got = [0.0; 100];
+        super::diff_many(reference, &colours, crate::Cie76, &mut want);
+        super::diff_many(reference, &labs, crate::Cie76, &mut got);
+        assert_eq!(want, got);
+        let cie00 = crate::Cie00::default();
+        super::diff_many(reference, &colours, cie00, &mut want);
+        super::diff_many(reference, &labs, cie00, &mut got);
+        assert_eq!(want, got);
+        assert_eq!(
+            crate::set::nearest(reference, &colours[1..], crate::Cie76),
+            crate::set::nearest(reference, &labs[1..], crate::Cie76)
+        );
+    }
+
+    #[test]
+    #[cfg(all(feature = "lab", feature = "rgb"))]
+    fn test_to_lab_vec_rgb() {
+        let colours = (0..=255)
+            .step_by(5)
+            .map(|c| rgb::RGB8::new(c, 255 - c, c / 2))
+            .collect::<Vec<_>>();
+        let labs = super::to_lab_vec(&colours);
+        let reference = rgb::RGB8::new(234, 76, 76);
+        let mut want = vec![0.0; colours.len()];
+        let mut got = vec![0.0; colours.len()];
+        let cie00 = crate::Cie00::default();
+        super::diff_many(reference, &colours, cie00, &mut want);
+        super::diff_many(reference, &labs, cie00, &mut got);
+        assert_eq!(want, got);
+    }
+
+    #[test]
+    fn test_delta_accumulator() {
+        let mut acc = super::DeltaAccumulator::new();
+        assert_eq!(0, acc.count());
+        assert_eq!((0.0, 0.0, 0.0), (acc.mean(), acc.max(), acc.p95()));
+
+        // 0.0, 0.1, …, 19.9
+        let values = (0..200).map(|i| i as f32 / 10.0).collect::<Vec<_>>();
+        acc.extend(values.iter().copied());
+        acc.push(f32::NAN);
+        assert_eq!(200, acc.count());
+        let sum = values.iter().map(|&v| f64::from(v)).sum::<f64>();
+        assert_eq!((sum / 200.0) as f32, acc.mean());
+        assert_eq!(19.9, acc.max());
+        // Exact 95th percentile is the 190th value, i.e. 18.9.
+        let p95 = acc.p95();
+        assert!((18.9..=18.9 + 0.5).contains(&p95), "{}", p95);
+
+        let mut acc = super::DeltaAccumulator::default();
+        acc.extend([150.0, 200.0, -1.0]);
+        assert_eq!(200.0, acc.max());
+        assert_eq!(200.0, acc.p95());
+
+        let mut acc = super::DeltaAccumulator::default();
+        acc.push(0.2);
+        assert_eq!(0.2, acc.p95());
+    }
+
+    #[test]
+    fn test_diff_planar() {
+        let planes_1 = [
+            [50.0, 50.0, 90.9257],
+            [2.6772, 2.5, -0.5406],
+            [-79.7751, 0.0, -0.9208],
+        ];
+        let planes_2 = [
+            [50.0, 73.0, 88.6381],
+            [0.0, 25.0, -0.8985],
+            [-82.7485, -18.0, -0.7239],
+        ];
+        let mut out = [0.0; 3];
+        super::diff_planar(
+            [&planes_1[0], &planes_1[1], &planes_1[2]],
+            [&planes_2[0], &planes_2[1], &planes_2[2]],
+            crate::cie00::diff_fn(crate::cie00::Params::default()),
+            &mut out,
+        );
+        for (i, got) in out.iter().enumerate() {
+            let colour_1 = (planes_1[0][i], planes_1[1][i], planes_1[2][i]);
+            let colour_2 = (planes_2[0][i], planes_2[1][i], planes_2[2][i]);
+            assert_eq!(crate::cie00::diff(colour_1, colour_2), *got);
+        }
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_diff_planar_length_mismatch() {
+        let plane = [0.0; 2];
+        let mut out = [0.0; 3];
+        super::diff_planar(
+            [&plane, &plane, &plane],
+            [&plane, &plane, &plane],
+            crate::cie76::diff_fn(),
+            &mut out,
+        );
+    }
+
+    #[test]
+    fn test_diff_many() {
+        let reference = (50.0, 2.5, 0.0);
+        let colours = [
+            (73.0, 25.0, -18.0),
+            (61.0, -5.0, 29.0),
+            (56.0, -27.0, -3.0),
+            (50.0, 2.5, 0.0),
+        ];
+        let mut out = [0.0; 4];
+        let diff = crate::cie00::diff_fn(crate::cie00::Params::default());
+        super::diff_many(reference, &colours, diff, &mut out);
+        for (colour, got) in colours.iter().zip(out.iter()) {
+            assert_eq!(crate::cie00::diff(reference, colour), *got);
+        }
+    }
+
+    #[test]
+    #[should_panic(expected = "colours and output lengths differ")]
+    fn test_diff_many_length_mismatch() {
+        let colours = [(50.0, 0.0, 0.0); 2];
+        let mut out = [0.0; 3];
+        let diff = crate::cie76::diff_fn();
+        super::diff_many((50.0, 0.0, 0.0), &colours, diff, &mut out);
+    }
+
+    #[test]
+    #[cfg(feature = "rayon")]
+    fn test_par_diff_many() {
+        let colours = crate::testutil::generate_colours(1000);
+        let reference = colours[0];
+        let diff = crate::cie00::diff_fn(crate::cie00::Params::default());
+        let mut want = vec![0.0; colours.len()];
+        super::diff_many(reference, &colours, diff, &mut want);
+        for threads in [1, 2, 3, 8] {
+            let pool = rayon::ThreadPoolBuilder::new()
+                .num_threads(threads)
+                .build()
+                .unwrap();
+            let mut got = vec![0.0; colours.len()];
+            pool.install(|| {
+                super::par_diff_many(reference, &colours, diff, &mut got)
+            });
+            assert_eq!(want, got, "threads: {}", threads);
+        }
+    }
+
+    #[test]
+    #[cfg(feature = "rayon")]
+    #[should_panic(expected = "colours and output lengths differ")]
+    fn test_par_diff_many_length_mismatch() {
+        let colours = [(50.0, 0.0, 0.0); 2];
+        let mut out = [0.0; 3];
+        let diff = crate::cie76::diff_fn();
+        super::par_diff_many((50.0, 0.0, 0.0), &colours, diff, &mut out);
+    }
+}
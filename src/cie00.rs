@@ -27,6 +27,16 @@
 //! hue have on the computed distance.  The module provides [`diff`] function
 //! which uses default parameters as well as [`diff_with_params`] which accepts
 //! [`Params`] argument to customise the coefficients.
+//!
+//! Note that while the distance is symmetric, it is not a metric in the
+//! mathematical sense since it does not satisfy the triangle inequality.  In
+//! other words, `diff(a, c)` may be greater than `diff(a, b) + diff(b, c)`.
+//! Use [`crate::cie76`] if that property is required.
+
+use alloc::collections::BTreeMap;
+use alloc::string::String;
+
+use crate::float::Float;
 
 /// Returns the CIEDE2000 colour difference between two L\*a\*b\* colours.
 ///
@@ -71,6 +81,55 @@ pub fn diff(color_1: impl crate::ToLab, color_2: impl crate::ToLab) -> f32 {
     diff_with_params(color_1, color_2, Params::default())
 }
 
+/// Returns the CIEDE2000 colour difference between two L\*a\*b\* colours
+/// calculated with double precision.
+///
+/// This is the same as [`diff`] except that the colours are converted with
+/// [`crate::ToLab64`] and the whole computation is done with `f64` numbers.
+/// This may be useful when small errors of single precision calculation
+/// accumulate, e.g. when summing differences over large data sets.
+///
+/// ## Example
+///
+/// ```
+/// use empfindung::cie00;
+///
+/// let colour_1 = (38.972f64, 58.991, 37.138);
+/// let colour_2 = (54.528f64, 42.416, 54.497);
+/// let delta_e = cie00::diff_f64(colour_1, colour_2);
+/// approx::assert_abs_diff_eq!(20.553640, delta_e, epsilon = 0.000001);
+/// ```
+pub fn diff_f64(
+    color_1: impl crate::ToLab64,
+    color_2: impl crate::ToLab64,
+) -> f64 {
+    diff_with_params_f64(color_1, color_2, Params::default())
+}
+
+/// Returns the CIEDE2000 colour difference between two L\*a\*b\* colours
+/// using custom `k` parameters calculated with double precision.
+///
+/// See [`diff_f64`] and [`diff_with_params`].
+///
+/// ## Example
+///
+/// ```
+/// use empfindung::cie00;
+///
+/// let colour_1 = (38.972f64, 58.991, 37.138);
+/// let colour_2 = (54.528f64, 42.416, 54.497);
+/// let delta_e = cie00::diff_with_params_f64(
+///     colour_1, colour_2, cie00::Params::yang2012());
+/// approx::assert_abs_diff_eq!(23.524858, delta_e, epsilon = 0.00001);
+/// ```
+pub fn diff_with_params_f64(
+    color_1: impl crate::ToLab64,
+    color_2: impl crate::ToLab64,
+    ksub: Params,
+) -> f64 {
+    diff_impl(color_1.to_lab64(), color_2.to_lab64(), ksub)
+}
+
 /// Returns the CIEDE2000 colour difference between two sRGB colours.
 ///
 /// ## Example
@@ -84,8 +143,8 @@ pub fn diff(color_1: impl crate::ToLab, color_2: impl crate::ToLab) -> f32 {
 /// let delta_e = cie00::diff_rgb(&colour_1, &colour_2);
 /// approx::assert_abs_diff_eq!(58.90164, delta_e, epsilon = 0.001);
 /// ```
-#[cfg(feature = "lab")]
-#[deprecated(note = "Use cie00::diff() with rgb::RGB8 argument")]
+#[cfg(all(feature = "lab", not(feature = "no-deprecated")))]
+#[deprecated(note = "Use cie00::diff() with [u8; 3] or rgb::RGB8 argument")]
 pub fn diff_rgb(color_1: &[u8; 3], color_2: &[u8; 3]) -> f32 {
     diff(lab::Lab::from_rgb(color_1), lab::Lab::from_rgb(color_2))
 }
@@ -101,7 +160,9 @@ pub fn diff_rgb(color_1: &[u8; 3], color_2: &[u8; 3]) -> f32 {
 /// To construct the object, either create it directly by providing your own
 /// choice of parameters, or use [`Params::default`] or [`Params::yang2012`]
 /// methods.  The former returns object with all parameters equal one while the
-/// latter returns parameters as devised by Yang et al.
+/// latter returns parameters as devised by Yang et al.  [`Params::new`] with
+/// `with_l`, `with_c` and `with_h` methods can be used to customise only some
+/// of the parameters.
 #[derive(Clone, Copy, PartialEq, PartialOrd, Debug)]
 pub struct Params {
     pub l: f32,
@@ -109,6 +170,7 @@ pub struct Params {
     pub h: f32,
 }
 
+#[cfg(not(feature = "no-deprecated"))]
 #[deprecated(note = "Use Params name instead")]
 pub type KSubParams = Params;
 
@@ -147,56 +209,853 @@ pub fn diff_with_params(
     diff_impl(color_1.to_lab(), color_2.to_lab(), ksub)
 }
 
-fn diff_impl(
-    color_1: (f32, f32, f32),
-    color_2: (f32, f32, f32),
+/// Returns the weighted lightness, chroma and hue terms of the CIEDE2000
+/// colour difference between two L\*a\*b\* colours using custom `k`
+/// parameters.
+///
+/// The lightness, chroma and hue terms are ΔL′/(k_L·S_L), ΔC′/(k_C·S_C) and
+/// ΔH′/(k_H·S_H) respectively and `rotation` is the R_T term.
+/// [`Components::delta_e`](crate::Components::delta_e) of the result equals
+/// [`diff_with_params`].
+///
+/// ## Example
+///
+/// ```
+/// use empfindung::cie00;
+///
+/// let colour_1 = (38.972, 58.991, 37.138);
+/// let colour_2 = (54.528, 42.416, 54.497);
+///
+/// let params = cie00::Params::default();
+/// let components = cie00::diff_components(colour_1, colour_2, params);
+/// approx::assert_abs_diff_eq!(15.12, components.lightness, epsilon = 0.01);
+/// approx::assert_abs_diff_eq!(-0.16, components.chroma, epsilon = 0.01);
+/// approx::assert_abs_diff_eq!(13.92, components.hue, epsilon = 0.01);
+/// approx::assert_abs_diff_eq!(
+///     cie00::diff_with_params(colour_1, colour_2, params),
+///     components.delta_e());
+/// ```
+pub fn diff_components(
+    color_1: impl crate::ToLab,
+    color_2: impl crate::ToLab,
+    ksub: Params,
+) -> crate::Components {
+    let (lightness, chroma, hue, rotation) =
+        get_terms(color_1.to_lab(), color_2.to_lab(), ksub);
+    crate::Components {
+        lightness,
+        chroma,
+        hue,
+        rotation,
+    }
+}
+
+/// Intermediate values of the CIEDE2000 formula returned by [`diff_debug`].
+///
+/// The names follow notation used by Sharma et al. in ‘The CIEDE2000
+/// Color-Difference Formula: Implementation Notes, Supplementary Test Data,
+/// and Mathematical Observations’ which tabulates these values for the test
+/// colour pairs.  Angles are in degrees.
+#[derive(Clone, Copy, PartialEq, Debug, Default)]
+pub struct Intermediates {
+    /// Mean C̄′ of the adjusted chroma of both colours.
+    pub c_prime_bar: f32,
+    /// Adjusted hue angle h₁′ of the first colour in `0..360` range.
+    pub h_prime_1: f32,
+    /// Adjusted hue angle h₂′ of the second colour in `0..360` range.
+    pub h_prime_2: f32,
+    /// Mean hue angle H̄′ in `0..360` range.  This is where implementations
+    /// most often diverge when the hues of the two colours straddle 0°.
+    pub upcase_h_prime_bar: f32,
+    /// The T hue weighting term.
+    pub upcase_t: f32,
+    /// The R_T rotation term.
+    pub r_sub_t: f32,
+}
+
+/// Returns the CIEDE2000 colour difference between two L\*a\*b\* colours using
+/// custom `k` parameters together with intermediate values of the formula.
+///
+/// The colour difference is the same as the one returned by
+/// [`diff_with_params`].  The intermediate values are meant for debugging
+/// disagreements with other implementations of the formula.
+///
+/// ## Example
+///
+/// ```
+/// use empfindung::cie00;
+///
+/// let colour_1 = (50.0, 2.5, 0.0);
+/// let colour_2 = (50.0, 0.0, -2.5);
+/// let params = cie00::Params::default();
+/// let (delta_e, intermediates) =
+///     cie00::diff_debug(colour_1, colour_2, params);
+/// assert_eq!(cie00::diff_with_params(colour_1, colour_2, params), delta_e);
+/// approx::assert_abs_diff_eq!(
+///     315.0, intermediates.upcase_h_prime_bar, epsilon = 0.001);
+/// ```
+pub fn diff_debug(
+    color_1: impl crate::ToLab,
+    color_2: impl crate::ToLab,
+    ksub: Params,
+) -> (f32, Intermediates) {
+    let color_1 = ReferenceTerms::new(color_1.to_lab());
+    let ((lightness, chroma, hue, r_sub_t), intermediates) =
+        get_terms_and_intermediates(&color_1, color_2.to_lab(), ksub);
+    let delta_e = crate::Components {
+        lightness,
+        chroma,
+        hue,
+        rotation: r_sub_t,
+    }
+    .delta_e();
+    let deg = |rad: f32| {
+        let deg = rad.to_degrees();
+        if deg >= 360.0 {
+            deg - 360.0
+        } else {
+            deg
+        }
+    };
+    let intermediates = Intermediates {
+        c_prime_bar: intermediates[0],
+        h_prime_1: deg(intermediates[1]),
+        h_prime_2: deg(intermediates[2]),
+        upcase_h_prime_bar: deg(intermediates[3]),
+        upcase_t: intermediates[4],
+        r_sub_t: intermediates[5],
+    };
+    (delta_e, intermediates)
+}
+
+/// Returns the CIEDE2000 colour difference as well as the CIE76 (i.e.
+/// Euclidean) distance between two L\*a\*b\* colours.
+///
+/// This is a diagnostic convenience function.  Comparing the two values may
+/// help understand why ΔE₀₀ and ΔE₇₆ disagree for a pair of colours.  Each
+/// colour is converted to L\*a\*b\* only once.
+///
+/// ## Example
+///
+/// ```
+/// use empfindung::cie00;
+///
+/// let colour_1 = (38.972, 58.991, 37.138);
+/// let colour_2 = (54.528, 42.416, 54.497);
+///
+/// let (delta_e, euclidean) = cie00::diff_and_euclidean(
+///     colour_1, colour_2, cie00::Params::default());
+/// approx::assert_abs_diff_eq!(20.553642, delta_e, epsilon = 0.001);
+/// approx::assert_abs_diff_eq!(28.601656, euclidean, epsilon = 0.001);
+/// ```
+pub fn diff_and_euclidean(
+    color_1: impl crate::ToLab,
+    color_2: impl crate::ToLab,
+    ksub: Params,
+) -> (f32, f32) {
+    let color_1 = color_1.to_lab();
+    let color_2 = color_2.to_lab();
+    (
+        diff_impl(color_1, color_2, ksub),
+        crate::cie76::diff(color_1, color_2),
+    )
+}
+
+/// Returns the CIEDE2000 colour difference between two L\*a\*b\* colours
+/// ignoring hue difference of near-neutral colours.
+///
+/// Hue of colours close to the achromatic axis is unstable: small noise in a\*
+/// and b\* coordinates of a grey sample can change its hue angle arbitrarily
+/// which in turn may lead to a hue difference larger than expected.  This
+/// function guards against that by dropping the hue term (and with it the
+/// rotation term) of the formula if chroma (C\*ab) of *both* colours is below
+/// `neutral_chroma` threshold.  Otherwise, the result is the same as
+/// [`diff_with_params`].
+///
+/// What threshold is appropriate depends on the noise in the measurements.
+/// Values in the order of one or two are a reasonable starting point.
+/// Threshold of zero (or less) disables the relaxation.
+///
+/// ## Example
+///
+/// ```
+/// use empfindung::cie00;
+///
+/// let colour_1 = (50.0, 0.5, 0.0);
+/// let colour_2 = (50.0, -0.5, 0.0);
+///
+/// let ksub = cie00::Params::default();
+/// let delta_e = cie00::diff_with_params(colour_1, colour_2, ksub);
+/// approx::assert_abs_diff_eq!(1.4896485, delta_e, epsilon = 0.001);
+/// let delta_e = cie00::diff_neutral_relaxed(colour_1, colour_2, ksub, 1.0);
+/// approx::assert_abs_diff_eq!(0.0, delta_e, epsilon = 0.001);
+/// ```
+pub fn diff_neutral_relaxed(
+    color_1: impl crate::ToLab,
+    color_2: impl crate::ToLab,
     ksub: Params,
+    neutral_chroma: f32,
 ) -> f32 {
-    let l_bar = (color_1.0 + color_2.0) * 0.5;
-    let delta_l = color_2.0 - color_1.0;
+    let color_1 = color_1.to_lab();
+    let color_2 = color_2.to_lab();
+    let (lightness, chroma, hue, r_sub_t) = get_terms(color_1, color_2, ksub);
+    if color_1.1.hypot(color_1.2) < neutral_chroma &&
+        color_2.1.hypot(color_2.2) < neutral_chroma
+    {
+        (Float::powi(lightness, 2) + Float::powi(chroma, 2)).sqrt()
+    } else {
+        (Float::powi(lightness, 2) +
+            Float::powi(chroma, 2) +
+            Float::powi(hue, 2) +
+            r_sub_t * chroma * hue)
+            .sqrt()
+    }
+}
+
+/// Returns the CIEDE2000 colour difference between two L\*a\*b\* colours
+/// without the hue rotation term.
+///
+/// The R_T rotation term accounts for interaction between chroma and hue
+/// differences in the blue region.  Some simplified implementations of the
+/// formula omit it and this function reproduces their results, e.g. when
+/// comparing against legacy data sets.  Outside of the blue region R_T is
+/// close to zero so the results are close to those of [`diff_with_params`].
+///
+/// ## Example
+///
+/// ```
+/// use empfindung::cie00;
+///
+/// let colour_1 = (50.0, 2.6772, -79.7751);
+/// let colour_2 = (50.0, 0.0, -82.7485);
+///
+/// let ksub = cie00::Params::default();
+/// let delta_e = cie00::diff_with_params(colour_1, colour_2, ksub);
+/// approx::assert_abs_diff_eq!(2.0425, delta_e, epsilon = 0.0001);
+/// let delta_e = cie00::diff_no_rotation(colour_1, colour_2, ksub);
+/// approx::assert_abs_diff_eq!(1.6081, delta_e, epsilon = 0.0001);
+/// ```
+pub fn diff_no_rotation(
+    color_1: impl crate::ToLab,
+    color_2: impl crate::ToLab,
+    ksub: Params,
+) -> f32 {
+    let (lightness, chroma, hue, _) =
+        get_terms(color_1.to_lab(), color_2.to_lab(), ksub);
+    (Float::powi(lightness, 2) + Float::powi(chroma, 2) + Float::powi(hue, 2))
+        .sqrt()
+}
+
+/// Returns the CIEDE2000 colour difference between two L\*a\*b\* colours
+/// with squared lightness, chroma and hue terms scaled by given weights.
+///
+/// **This is not a standard formula.**  It is meant for research, e.g. to
+/// study which of the terms dominates the colour difference.  Unlike `k`
+/// parameters, which divide the terms before they are squared, the weights
+/// `(w_L, w_C, w_H)` multiply the squared terms directly and the result is
+/// `sqrt(w_L·ΔL² + w_C·ΔC² + w_H·ΔH² + R_T·ΔC·ΔH)`.  The rotation term is not
+/// weighted.  Since it may be negative, the expression under the square root
+/// is clamped at zero.
+///
+/// With weights equal `(1, 1, 1)` the result is the same as
+/// [`diff_with_params`].
+///
+/// ## Example
+///
+/// ```
+/// use empfindung::cie00;
+///
+/// let colour_1 = (38.972, 58.991, 37.138);
+/// let colour_2 = (54.528, 42.416, 54.497);
+///
+/// let ksub = cie00::Params::default();
+/// let weights = (1.0, 1.0, 1.0);
+/// let delta_e = cie00::diff_weighted(colour_1, colour_2, ksub, weights);
+/// approx::assert_abs_diff_eq!(20.553642, delta_e, epsilon = 0.001);
+///
+/// // Ignore lightness difference.
+/// let delta_e = cie00::diff_weighted((50.0, 0.0, 0.0), (60.0, 0.0, 0.0), ksub,
+///                                    (0.0, 1.0, 1.0));
+/// assert_eq!(0.0, delta_e);
+/// ```
+pub fn diff_weighted(
+    color_1: impl crate::ToLab,
+    color_2: impl crate::ToLab,
+    ksub: Params,
+    term_weights: (f32, f32, f32),
+) -> f32 {
+    let (lightness, chroma, hue, r_sub_t) =
+        get_terms(color_1.to_lab(), color_2.to_lab(), ksub);
+    let (w_l, w_c, w_h) = term_weights;
+    (w_l * Float::powi(lightness, 2) +
+        w_c * Float::powi(chroma, 2) +
+        w_h * Float::powi(hue, 2) +
+        r_sub_t * chroma * hue)
+        .max(0.0)
+        .sqrt()
+}
 
+/// Returns the CIEDE2000 colour difference between two L\*a\*b\* colours
+/// flushing subnormal a\* and b\* coordinates to zero.
+///
+/// Colours with extremely small a\* and b\* coordinates (e.g. produced by
+/// noisy sensors) may contain subnormal numbers which on some CPUs make
+/// arithmetic, and in particular the `atan2` calls in hue computation, orders
+/// of magnitude slower.  This function replaces such coordinates with zero
+/// before performing the calculation.  Since subnormal numbers are smaller
+/// than 1.2·10⁻³⁸ the impact on the result is negligible.  For all other
+/// inputs the result is the same as [`diff_with_params`].
+///
+/// ## Example
+///
+/// ```
+/// use empfindung::cie00;
+///
+/// let ksub = cie00::Params::default();
+/// let delta_e = cie00::diff_flush_subnormals(
+///     (50.0, 1e-40, -1e-40), (50.0, 0.0, 0.0), ksub);
+/// assert_eq!(0.0, delta_e);
+/// ```
+pub fn diff_flush_subnormals(
+    color_1: impl crate::ToLab,
+    color_2: impl crate::ToLab,
+    ksub: Params,
+) -> f32 {
+    fn flush(lab: (f32, f32, f32)) -> (f32, f32, f32) {
+        let flush = |v: f32| if v.is_subnormal() { 0.0 } else { v };
+        (lab.0, flush(lab.1), flush(lab.2))
+    }
+
+    diff_impl(flush(color_1.to_lab()), flush(color_2.to_lab()), ksub)
+}
+
+/// Returns the hue angle difference Δh′ between two colours in radians.
+///
+/// This is the hue angle difference as defined by the CIEDE2000 formula,
+/// i.e. difference of hue angles calculated after a\* coordinates have been
+/// adjusted by the (1 + G) factor.  The result is `h′₂ − h′₁` normalised to
+/// the `-π..=π` range, i.e. it is positive if hue of `color_2` lies
+/// counter-clockwise from hue of `color_1`.  If either colour is achromatic,
+/// returns zero.
+///
+/// All hue angles are computed in radians internally.  Use
+/// [`hue_difference_deg`] to get the value in degrees.
+///
+/// ## Example
+///
+/// ```
+/// use empfindung::cie00;
+///
+/// let delta_h =
+///     cie00::hue_difference_rad((50.0, 0.0, 10.0), (50.0, -10.0, 0.0));
+/// approx::assert_abs_diff_eq!(std::f32::consts::FRAC_PI_2, delta_h);
+/// ```
+pub fn hue_difference_rad(
+    color_1: impl crate::ToLab,
+    color_2: impl crate::ToLab,
+) -> f32 {
+    let color_1 = color_1.to_lab();
+    let color_2 = color_2.to_lab();
     let c1 = color_1.1.hypot(color_1.2);
     let c2 = color_2.1.hypot(color_2.2);
+    let tmp = get_a_prime_factor(c1, c2);
+    let h_prime_1 = get_h_prime(color_1.2, color_1.1 * tmp);
+    let h_prime_2 = get_h_prime(color_2.2, color_2.1 * tmp);
+    get_delta_h_prime(c1, c2, h_prime_1, h_prime_2)
+}
+
+/// Returns the hue angle difference Δh′ between two colours in degrees.
+///
+/// This is the same as [`hue_difference_rad`] except that the result is
+/// converted to degrees and is thus in the `-180..=180` range.
+///
+/// ## Example
+///
+/// ```
+/// use empfindung::cie00;
+///
+/// let delta_h =
+///     cie00::hue_difference_deg((50.0, 0.0, 10.0), (50.0, 0.0, -10.0));
+/// approx::assert_abs_diff_eq!(180.0, delta_h.abs());
+/// ```
+pub fn hue_difference_deg(
+    color_1: impl crate::ToLab,
+    color_2: impl crate::ToLab,
+) -> f32 {
+    hue_difference_rad(color_1, color_2).to_degrees()
+}
+
+/// Returns whether the CIEDE2000 colour difference between two L\*a\*b\*
+/// colours is at most `threshold`.
+///
+/// This is equivalent to `diff(color_1, color_2) <= threshold`.
+///
+/// ## Example
+///
+/// ```
+/// use empfindung::cie00;
+///
+/// let colour_1 = (38.972, 58.991, 37.138);
+/// let colour_2 = (54.528, 42.416, 54.497);
+/// assert!(cie00::within(colour_1, colour_2, 21.0));
+/// assert!(!cie00::within(colour_1, colour_2, 20.0));
+/// ```
+pub fn within(
+    color_1: impl crate::ToLab,
+    color_2: impl crate::ToLab,
+    threshold: f32,
+) -> bool {
+    diff(color_1, color_2) <= threshold
+}
 
-    let tmp = ((c1 + c2) * 0.5).powi(7);
-    let tmp = 1.5 - (tmp / (tmp + TWENTY_FIVE_TO_SEVENTH)).sqrt() * 0.5;
-    let a_prime_1 = color_1.1 * tmp;
+/// Returns square of the CIEDE2000 colour difference between two L\*a\*b\*
+/// colours using default parameters.
+///
+/// Squaring non-negative differences doesn’t change their order so the
+/// result ranks colours the same way [`diff`] does and can be compared
+/// against a squared threshold.  This makes the function useful when only
+/// the ordering matters, e.g. when looking for the nearest colour.
+///
+/// Because of the rotation term, rounding errors may make the sum of terms
+/// slightly negative for nearly identical colours.  The result is clamped to
+/// zero in such cases so it is always a valid squared distance.
+///
+/// ## Example
+///
+/// ```
+/// use empfindung::cie00;
+///
+/// let colour_1 = (38.972, 58.991, 37.138);
+/// let colour_2 = (54.528, 42.416, 54.497);
+/// let delta_e_squared = cie00::diff_squared(colour_1, colour_2);
+/// approx::assert_abs_diff_eq!(422.45, delta_e_squared, epsilon = 0.01);
+/// ```
+pub fn diff_squared(
+    color_1: impl crate::ToLab,
+    color_2: impl crate::ToLab,
+) -> f32 {
+    let color_1 = ReferenceTerms::new(color_1.to_lab());
+    diff_squared_with_reference_impl(&color_1, color_2.to_lab(), Params::new())
+        .max(0.0)
+}
+
+/// Calculates the CIEDE2000 colour difference between a reference colour and
+/// each colour in a slice using default parameters.
+///
+/// The reference colour is converted to L\*a\*b\* only once and results are
+/// stored in `out`.  They are bit-for-bit identical to calling [`diff`] in
+/// a loop.  See [`crate::batch::diff_many`].
+///
+/// # Panics
+///
+/// Panics if length of `colours` differs from length of `out`.
+///
+/// ## Example
+///
+/// ```
+/// use empfindung::cie00;
+///
+/// let reference = (50.0, 2.5, 0.0);
+/// let colours = [(73.0, 25.0, -18.0), (61.0, -5.0, 29.0)];
+/// let mut out = [0.0; 2];
+/// cie00::diff_many(reference, &colours, &mut out);
+/// assert_eq!(cie00::diff(reference, colours[0]), out[0]);
+/// assert_eq!(cie00::diff(reference, colours[1]), out[1]);
+/// ```
+pub fn diff_many<C: crate::ToLab>(
+    reference: impl crate::ToLab,
+    colours: &[C],
+    out: &mut [f32],
+) {
+    let diff = diff_fn(Params::default());
+    crate::batch::diff_many(reference, colours, diff, out)
+}
+
+/// Calculates the CIEDE2000 colour difference between a reference colour and
+/// each colour in a fixed-size array using default parameters.
+///
+/// This is like [`diff_many`] but returns the results in an array rather than
+/// storing them in a slice which is convenient for small palettes whose size
+/// is known at compile time.  The reference colour is converted to L\*a\*b\*
+/// only once and results are bit-for-bit identical to calling [`diff`] in
+/// a loop.
+///
+/// ## Example
+///
+/// ```
+/// use empfindung::cie00;
+///
+/// let reference = (50.0, 2.5, 0.0);
+/// let palette = [(73.0, 25.0, -18.0), (61.0, -5.0, 29.0)];
+/// let got = cie00::diff_array(reference, &palette);
+/// assert_eq!([cie00::diff(reference, palette[0]),
+///             cie00::diff(reference, palette[1])], got);
+/// ```
+pub fn diff_array<C: crate::ToLab, const N: usize>(
+    reference: impl crate::ToLab,
+    palette: &[C; N],
+) -> [f32; N] {
+    let reference = Reference::new(reference);
+    core::array::from_fn(|i| reference.diff(&palette[i]))
+}
+
+/// A reference colour prepared for calculating CIEDE2000 colour differences
+/// between it and many other colours.
+///
+/// The type converts the reference colour into L\*a\*b\* and calculates its
+/// C\*ab chroma once rather than on each comparison.  If the reference lies
+/// on the a\* or b\* axis (which includes neutral colours), its h′ hue angle
+/// is cached as well and its C′ chroma is calculated without `hypot`.
+///
+/// Other values of the formula depend on both colours.  Most importantly,
+/// the 1 + G factor `1.5 − ½·√(C̄⁷/(C̄⁷ + 25⁷))` by which a\* coordinates are
+/// scaled depends on `C̄`, the mean C\*ab chroma of both colours, and the
+/// reference’s a′, C′ and h′ are calculated from the scaled coordinate.
+/// The saving is therefore most noticeable when converting the reference
+/// colour into L\*a\*b\* is costly (e.g. for `rgb::RGB<u8>` colours).
+///
+/// Results are exactly the same as those returned by [`diff`] and
+/// [`diff_with_params`] with the reference passed as the first argument.
+///
+/// ## Example
+///
+/// ```
+/// use empfindung::cie00;
+///
+/// let reference = cie00::Reference::new((50.0, 2.5, 0.0));
+/// let colours = [(73.0, 25.0, -18.0), (61.0, -5.0, 29.0)];
+/// for colour in colours {
+///     let want = cie00::diff((50.0, 2.5, 0.0), colour);
+///     assert_eq!(want, reference.diff(colour));
+/// }
+/// ```
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub struct Reference(ReferenceTerms<f32>);
+
+impl Reference {
+    /// Prepares given colour for use as a reference colour.
+    pub fn new(colour: impl crate::ToLab) -> Self {
+        Self(ReferenceTerms::new(colour.to_lab()))
+    }
+
+    /// Returns the CIEDE2000 colour difference between the reference and
+    /// given colour.
+    pub fn diff(&self, colour: impl crate::ToLab) -> f32 {
+        self.diff_with_params(colour, Params::default())
+    }
+
+    /// Returns the CIEDE2000 colour difference between the reference and
+    /// given colour using custom `k` parameters.
+    pub fn diff_with_params(
+        &self,
+        colour: impl crate::ToLab,
+        ksub: Params,
+    ) -> f32 {
+        diff_with_reference_impl(&self.0, colour.to_lab(), ksub)
+    }
+}
+
+/// Calculates the CIEDE2000 colour difference between a reference sRGB colour
+/// and each pixel in a slice using default parameters.
+///
+/// The reference colour is converted to L\*a\*b\* only once (see
+/// [`Reference`]) while pixels are converted in bulk with `lab` crate’s
+/// `rgb_bytes_to_labs` function (which uses AVX2 instructions if they are
+/// enabled at compile time) without going through the [`crate::ToLab`] trait.
+/// Results are stored in `out` and match, up to rounding of the vectorised
+/// conversion, those of calling [`diff`] in a loop.
+///
+/// # Panics
+///
+/// Panics if length of `pixels` differs from length of `out`.
+///
+/// ## Example
+///
+/// ```
+/// use empfindung::cie00;
+/// use rgb::RGB8;
+///
+/// let reference = RGB8::new(234, 76, 76);
+/// let pixels = [RGB8::new(76, 187, 234), RGB8::new(234, 76, 76)];
+/// let mut out = [0.0; 2];
+/// cie00::diff_rgb_many(reference, &pixels, &mut out);
+/// approx::assert_abs_diff_eq!(58.90164, out[0], epsilon = 0.001);
+/// assert_eq!(0.0, out[1]);
+/// ```
+#[cfg(all(feature = "lab", feature = "rgb"))]
+pub fn diff_rgb_many(
+    reference: rgb::RGB8,
+    pixels: &[rgb::RGB8],
+    out: &mut [f32],
+) {
+    use rgb::ComponentBytes;
+
+    assert_eq!(pixels.len(), out.len(), "pixels and output lengths differ");
+    let reference = Reference::new(reference);
+    let labs = lab::rgb_bytes_to_labs(pixels.as_bytes());
+    for (out, lab) in out.iter_mut().zip(labs.iter()) {
+        *out = reference.diff(lab);
+    }
+}
+
+/// Returns a function calculating the CIEDE2000 colour difference between two
+/// L\*a\*b\* colours using given `k` parameters.
+///
+/// The returned closure captures the parameters and operates on plain
+/// `(f32, f32, f32)` tuples which makes it convenient to pass to thread pools
+/// and other code expecting a plain function.
+///
+/// ## Example
+///
+/// ```
+/// use empfindung::cie00;
+///
+/// let diff = cie00::diff_fn(cie00::Params::yang2012());
+/// let delta_e = diff((38.972, 58.991, 37.138), (54.528, 42.416, 54.497));
+/// approx::assert_abs_diff_eq!(23.524858, delta_e, epsilon = 0.001);
+/// ```
+pub fn diff_fn(
+    ksub: Params,
+) -> impl Fn((f32, f32, f32), (f32, f32, f32)) -> f32 + Copy {
+    move |color_1, color_2| diff_impl(color_1, color_2, ksub)
+}
+
+/// Calculates the CIEDE2000 colour differences between corresponding
+/// L\*a\*b\* colours in two slices using SIMD instructions.
+///
+/// For each index `i`, `out[i]` is set to the difference between
+/// `colours_1[i]` and `colours_2[i]`.  All three slices must have the same
+/// length.  The colours are processed eight pairs at a time.  Since there are
+/// no vectorised trigonometric functions, they are approximated with
+/// polynomials.  On the test data published by Sharma et al. the results
+/// differ from those of [`diff_with_params`] by less than 0.001.
+///
+/// The function is available with `simd` feature.  By default it relies on
+/// the compiler vectorising loops over eight lanes.  On nightly compilers,
+/// building with `--cfg empfindung_portable_simd` switches to portable SIMD
+/// (`std::simd`) instead.
+///
+/// # Panics
+///
+/// Panics if length of `colours_1` or `colours_2` differs from `out.len()`.
+///
+/// ## Example
+///
+/// ```
+/// use empfindung::cie00;
+///
+/// let colours_1 = [(38.972, 58.991, 37.138), (50.0, 2.5, 0.0)];
+/// let colours_2 = [(54.528, 42.416, 54.497), (73.0, 25.0, -18.0)];
+/// let mut out = [0.0; 2];
+/// cie00::diff_slice_simd(
+///     &colours_1, &colours_2, cie00::Params::default(), &mut out);
+/// approx::assert_abs_diff_eq!(20.553642, out[0], epsilon = 0.001);
+/// approx::assert_abs_diff_eq!(27.1492, out[1], epsilon = 0.001);
+/// ```
+#[cfg(feature = "simd")]
+pub fn diff_slice_simd(
+    colours_1: &[(f32, f32, f32)],
+    colours_2: &[(f32, f32, f32)],
+    ksub: Params,
+    out: &mut [f32],
+) {
+    use crate::simd::{f32x8, LANES};
+
+    fn load(colours: &[(f32, f32, f32)]) -> [f32x8; 3] {
+        // Pad the last chunk with black which is harmless to compute.
+        let get = |i: usize| colours.get(i).copied().unwrap_or_default();
+        [
+            f32x8::from_array(core::array::from_fn(|i| get(i).0)),
+            f32x8::from_array(core::array::from_fn(|i| get(i).1)),
+            f32x8::from_array(core::array::from_fn(|i| get(i).2)),
+        ]
+    }
+
+    assert_eq!(colours_1.len(), out.len(), "slice lengths differ");
+    assert_eq!(colours_2.len(), out.len(), "slice lengths differ");
+    let chunks = colours_1
+        .chunks(LANES)
+        .zip(colours_2.chunks(LANES))
+        .zip(out.chunks_mut(LANES));
+    for ((colours_1, colours_2), out) in chunks {
+        let got = crate::simd::diff(load(colours_1), load(colours_2), ksub);
+        out.copy_from_slice(&got.as_array()[..out.len()]);
+    }
+}
+
+fn diff_impl<F: Float>(
+    color_1: (F, F, F),
+    color_2: (F, F, F),
+    ksub: Params,
+) -> F {
+    diff_with_reference_impl(&ReferenceTerms::new(color_1), color_2, ksub)
+}
+
+/// Values of the CIEDE2000 formula which depend on the first colour only.
+///
+/// Going through the formula (see [`get_terms_and_intermediates`]):
+///
+/// - L̄ and ΔL depend on lightness of both colours.  The S_L weighting
+///   depends on L̄ so it can’t be cached either.
+/// - C\*ab chroma is cached.
+/// - 1 + G depends on the mean chroma C̄ of both colours.  Its C̄⁷ term could
+///   be expanded into a polynomial in the other colour’s chroma with
+///   coefficients taken from this colour but evaluating it costs more than
+///   calculating the mean and raising it to the seventh power.
+/// - a′ = a\*·(1 + G) and thus C′ and h′ depend on both colours.  The
+///   exception is when a\* or b\* is zero.  Scaling a\* by the positive
+///   1 + G factor then doesn’t change h′ which is cached, and C′ is either
+///   |b\*| or |a′| which doesn’t need `hypot`.
+/// - C̄′, H̄′, T, S_C, S_H and R_T depend on C′ and h′ of both colours.
+///
+/// Everything else is calculated from the above.  Results are bit-for-bit
+/// identical whether or not the values were cached.
+#[derive(Clone, Copy, PartialEq, Debug)]
+struct ReferenceTerms<F> {
+    lab: (F, F, F),
+    /// C\*ab chroma.
+    chroma: F,
+    /// h′ hue angle if the colour lies on a\* or b\* axis.
+    h_prime: Option<F>,
+}
+
+impl<F: Float> ReferenceTerms<F> {
+    fn new(lab: (F, F, F)) -> Self {
+        let zero = F::lit(0.0);
+        let h_prime = if lab.1 == zero || lab.2 == zero {
+            Some(get_h_prime(lab.2, lab.1))
+        } else {
+            None
+        };
+        Self {
+            lab,
+            chroma: lab.1.hypot(lab.2),
+            h_prime,
+        }
+    }
+
+    /// Returns C′ chroma and h′ hue angle given a′ coordinate, i.e. a\*
+    /// coordinate scaled by the 1 + G factor.
+    #[inline]
+    fn c_h_prime(&self, a_prime: F) -> (F, F) {
+        let b = self.lab.2;
+        match self.h_prime {
+            Some(h_prime) if b == F::lit(0.0) => (a_prime.abs(), h_prime),
+            Some(h_prime) => (b.abs(), h_prime),
+            None => (a_prime.hypot(b), get_h_prime(b, a_prime)),
+        }
+    }
+}
+
+/// Calculates the CIEDE2000 colour difference given precomputed values of the
+/// first colour.
+fn diff_with_reference_impl<F: Float>(
+    color_1: &ReferenceTerms<F>,
+    color_2: (F, F, F),
+    ksub: Params,
+) -> F {
+    diff_squared_with_reference_impl(color_1, color_2, ksub).sqrt()
+}
+
+/// Calculates square of the CIEDE2000 colour difference given precomputed
+/// values of the first colour.  The result may be slightly negative due to
+/// rounding errors.
+fn diff_squared_with_reference_impl<F: Float>(
+    color_1: &ReferenceTerms<F>,
+    color_2: (F, F, F),
+    ksub: Params,
+) -> F {
+    let (lightness, chroma, hue, r_sub_t) =
+        get_terms_with_reference(color_1, color_2, ksub);
+    lightness.powi(2) + chroma.powi(2) + hue.powi(2) + r_sub_t * chroma * hue
+}
+
+/// Returns the lightness, chroma and hue terms of the CIEDE2000 formula (i.e.
+/// ΔL′/(k_L·S_L), ΔC′/(k_C·S_C) and ΔH′/(k_H·S_H)) as well as the rotation
+/// term R_T.
+fn get_terms<F: Float>(
+    color_1: (F, F, F),
+    color_2: (F, F, F),
+    ksub: Params,
+) -> (F, F, F, F) {
+    get_terms_with_reference(&ReferenceTerms::new(color_1), color_2, ksub)
+}
+
+/// Returns the terms of the CIEDE2000 formula (see [`get_terms`]) given
+/// precomputed values of the first colour.
+fn get_terms_with_reference<F: Float>(
+    color_1: &ReferenceTerms<F>,
+    color_2: (F, F, F),
+    ksub: Params,
+) -> (F, F, F, F) {
+    get_terms_and_intermediates(color_1, color_2, ksub).0
+}
+
+/// Returns the terms of the CIEDE2000 formula (see [`get_terms`]) as well as
+/// the intermediate values C̄′, h₁′, h₂′, H̄′, T and R_T (with angles in
+/// radians).
+#[inline]
+fn get_terms_and_intermediates<F: Float>(
+    reference: &ReferenceTerms<F>,
+    color_2: (F, F, F),
+    ksub: Params,
+) -> ((F, F, F, F), [F; 6]) {
+    let half = F::lit(0.5);
+    let one = F::lit(1.0);
+    let (color_1, c1) = (reference.lab, reference.chroma);
+
+    let l_bar = (color_1.0 + color_2.0) * half;
+    let delta_l = color_2.0 - color_1.0;
+
+    let c2 = color_2.1.hypot(color_2.2);
+
+    let tmp = get_a_prime_factor(c1, c2);
     let a_prime_2 = color_2.1 * tmp;
 
-    let c_prime_1 = a_prime_1.hypot(color_1.2);
+    let (c_prime_1, h_prime_1) = reference.c_h_prime(color_1.1 * tmp);
     let c_prime_2 = a_prime_2.hypot(color_2.2);
-    let c_prime_bar = (c_prime_1 + c_prime_2) * 0.5;
+    let c_prime_bar = (c_prime_1 + c_prime_2) * half;
     let delta_c_prime = c_prime_2 - c_prime_1;
 
-    let tmp = (l_bar - 50.0).powi(2);
-    let s_sub_l = 1.0 + (0.015 * tmp) / (20.0 + tmp).sqrt();
+    let tmp = (l_bar - F::lit(50.0)).powi(2);
+    let s_sub_l = one + (F::lit(0.015) * tmp) / (F::lit(20.0) + tmp).sqrt();
 
-    let s_sub_c = 1.0 + 0.045 * c_prime_bar;
+    let s_sub_c = one + F::lit(0.045) * c_prime_bar;
 
-    let h_prime_1 = get_h_prime(color_1.2, a_prime_1);
     let h_prime_2 = get_h_prime(color_2.2, a_prime_2);
     let delta_h_prime = get_delta_h_prime(c1, c2, h_prime_1, h_prime_2);
 
-    let delta_upcase_h_prime =
-        2.0 * (c_prime_1 * c_prime_2).sqrt() * (0.5 * delta_h_prime).sin();
+    let delta_upcase_h_prime = F::lit(2.0) *
+        (c_prime_1 * c_prime_2).sqrt() *
+        (half * delta_h_prime).sin();
 
-    let upcase_h_prime_bar = if (h_prime_1 - h_prime_2).abs() > PI_32 {
-        (h_prime_1 + h_prime_2) * 0.5 + PI_32
+    let upcase_h_prime_bar = if (h_prime_1 - h_prime_2).abs() > F::PI {
+        (h_prime_1 + h_prime_2) * half + F::PI
     } else {
-        (h_prime_1 + h_prime_2) * 0.5
+        (h_prime_1 + h_prime_2) * half
     };
 
     let upcase_t = get_upcase_t(upcase_h_prime_bar);
 
-    let s_sub_upcase_h = 1.0 + 0.015 * c_prime_bar * upcase_t;
+    let s_sub_upcase_h = one + F::lit(0.015) * c_prime_bar * upcase_t;
 
-    let lightness = delta_l / (ksub.l * s_sub_l);
-    let chroma = delta_c_prime / (ksub.c * s_sub_c);
-    let hue = delta_upcase_h_prime / (ksub.h * s_sub_upcase_h);
+    let k = |k: f32| F::lit(f64::from(k));
+    let lightness = delta_l / (k(ksub.l) * s_sub_l);
+    let chroma = delta_c_prime / (k(ksub.c) * s_sub_c);
+    let hue = delta_upcase_h_prime / (k(ksub.h) * s_sub_upcase_h);
     let r_sub_t = get_r_sub_t(c_prime_bar, upcase_h_prime_bar);
 
-    (lightness.powi(2) + chroma.powi(2) + hue.powi(2) + r_sub_t * chroma * hue)
-        .sqrt()
+    (
+        (lightness, chroma, hue, r_sub_t),
+        [
+            c_prime_bar,
+            h_prime_1,
+            h_prime_2,
+            upcase_h_prime_bar,
+            upcase_t,
+            r_sub_t,
+        ],
+    )
 }
 
 /// Returns the CIEDE2000 colour difference between two sRGB colours using
@@ -214,8 +1073,10 @@ fn diff_impl(
 ///     &colour_1, &colour_2, cie00::Params::yang2012());
 /// approx::assert_abs_diff_eq!(26.88325, delta_e, epsilon = 0.001);
 /// ```
-#[cfg(feature = "lab")]
-#[deprecated(note = "Use cie00::diff_with_params() with rgb::RGB8 argument")]
+#[cfg(all(feature = "lab", not(feature = "no-deprecated")))]
+#[deprecated(
+    note = "Use cie00::diff_with_params() with [u8; 3] or rgb::RGB8 argument"
+)]
 pub fn diff_rgb_with_params(
     color_1: &[u8; 3],
     color_2: &[u8; 3],
@@ -229,11 +1090,12 @@ pub fn diff_rgb_with_params(
 }
 
 
+#[cfg(not(feature = "no-deprecated"))]
 #[deprecated(note = "Use cie00::diff() or cie00::diff_rgb() instead")]
 pub struct DE2000;
 
 #[allow(deprecated)]
-#[cfg(feature = "lab")]
+#[cfg(all(feature = "lab", not(feature = "no-deprecated")))]
 impl DE2000 {
     /// Returns the colour difference between two `Lab` colours.
     ///
@@ -286,16 +1148,39 @@ let colour_2 = (54.528, 42.416, 54.497);
 }
 
 impl Default for Params {
-    fn default() -> Self {
+    fn default() -> Self { Self::new() }
+}
+
+impl Params {
+    /// Returns default parameters with all values equal one.
+    ///
+    /// This is the same as [`Params::default`] but usable in `const`
+    /// contexts.  Together with `with_*` methods it allows customising
+    /// individual parameters:
+    ///
+    /// ```
+    /// use empfindung::cie00;
+    ///
+    /// let params = cie00::Params::new().with_l(2.0);
+    /// assert_eq!(cie00::Params { l: 2.0, c: 1.0, h: 1.0 }, params);
+    /// ```
+    pub const fn new() -> Self {
         Self {
             l: 1.0,
             c: 1.0,
             h: 1.0,
         }
     }
-}
 
-impl Params {
+    /// Returns copy of the parameters with lightness weight set to `l`.
+    pub const fn with_l(self, l: f32) -> Self { Self { l, ..self } }
+
+    /// Returns copy of the parameters with chroma weight set to `c`.
+    pub const fn with_c(self, c: f32) -> Self { Self { c, ..self } }
+
+    /// Returns copy of the parameters with hue weight set to `h`.
+    pub const fn with_h(self, h: f32) -> Self { Self { h, ..self } }
+
     /// Returns parameters as determined in (Yang, 2012).
     ///
     /// See Yang Yang, Jun Ming, Nenghai Yu, ‘Color Image Quality Assessment
@@ -306,70 +1191,277 @@ impl Params {
     /// values.  Colorimetry is hard and it’s up to the user to determine
     /// correct values to use.  This function is here just for reference.  If in
     /// doubt use `Params::default()` which is what [`diff`] function uses.
-    pub fn yang2012() -> Self {
+    pub const fn yang2012() -> Self {
         Self {
             l: 0.65,
             c: 1.0,
             h: 4.0,
         }
     }
+
+    /// Linearly interpolates between parameters `a` and `b`.
+    ///
+    /// Returns `a` when `t` is zero and `b` when `t` is one.  Values of `t`
+    /// outside of the [0, 1] range extrapolate.
+    ///
+    /// ```
+    /// use empfindung::cie00::Params;
+    ///
+    /// let params = Params::lerp(Params::new(), Params::yang2012(), 0.5);
+    /// approx::assert_abs_diff_eq!(0.825, params.l);
+    /// approx::assert_abs_diff_eq!(1.0, params.c);
+    /// approx::assert_abs_diff_eq!(2.5, params.h);
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// In debug builds panics if any of the resulting parameters is zero.
+    pub fn lerp(a: Self, b: Self, t: f32) -> Self {
+        let lerp = |a: f32, b: f32| a * (1.0 - t) + b * t;
+        Self {
+            l: lerp(a.l, b.l),
+            c: lerp(a.c, b.c),
+            h: lerp(a.h, b.h),
+        }
+        .debug_check()
+    }
+
+    /// Checks in debug builds that none of the parameters, all of which are
+    /// used as denominators, is zero.
+    fn debug_check(self) -> Self {
+        debug_assert!(
+            self.l != 0.0 && self.c != 0.0 && self.h != 0.0,
+            "k parameters must not be zero; got {:?}",
+            self
+        );
+        self
+    }
+}
+
+impl core::ops::Add for Params {
+    type Output = Self;
+
+    /// Adds parameters component-wise.
+    ///
+    /// # Panics
+    ///
+    /// In debug builds panics if any of the resulting parameters is zero.
+    fn add(self, rhs: Self) -> Self {
+        Self {
+            l: self.l + rhs.l,
+            c: self.c + rhs.c,
+            h: self.h + rhs.h,
+        }
+        .debug_check()
+    }
+}
+
+impl core::ops::Mul<f32> for Params {
+    type Output = Self;
+
+    /// Multiplies all parameters by a scalar.
+    ///
+    /// # Panics
+    ///
+    /// In debug builds panics if any of the resulting parameters is zero.
+    fn mul(self, rhs: f32) -> Self {
+        Self {
+            l: self.l * rhs,
+            c: self.c * rhs,
+            h: self.h * rhs,
+        }
+        .debug_check()
+    }
+}
+
+/// Presets built into every [`PresetRegistry`] and used by [`diff_named`].
+const BUILTIN_PRESETS: [(&str, Params); 2] =
+    [("default", Params::new()), ("yang2012", Params::yang2012())];
+
+/// Returns the CIEDE2000 colour difference between two L\*a\*b\* colours
+/// using one of the built-in named parameter presets.
+///
+/// Recognised presets are `"default"` (see [`Params::default`]) and
+/// `"yang2012"` (see [`Params::yang2012`]).  Returns `None` if `preset` is not
+/// one of them.  Use [`PresetRegistry`] to define custom presets.
+///
+/// ## Example
+///
+/// ```
+/// use empfindung::cie00;
+///
+/// let colour_1 = (38.972, 58.991, 37.138);
+/// let colour_2 = (54.528, 42.416, 54.497);
+///
+/// let delta_e = cie00::diff_named(colour_1, colour_2, "yang2012");
+/// assert_eq!(
+///     Some(cie00::diff_with_params(
+///         colour_1, colour_2, cie00::Params::yang2012())),
+///     delta_e
+/// );
+/// assert_eq!(None, cie00::diff_named(colour_1, colour_2, "bogus"));
+/// ```
+pub fn diff_named(
+    color_1: impl crate::ToLab,
+    color_2: impl crate::ToLab,
+    preset: &str,
+) -> Option<f32> {
+    let ksub = BUILTIN_PRESETS
+        .iter()
+        .find(|(name, _)| *name == preset)
+        .map(|(_, ksub)| *ksub)?;
+    Some(diff_with_params(color_1, color_2, ksub))
+}
+
+/// Collection of named [`Params`] presets.
+///
+/// Colour engineers often maintain sets of parameters tuned for particular
+/// substrates or customers.  The registry maps names to such parameters which
+/// lets tools select them by name, e.g. with a `--preset` command line flag.
+///
+/// New registry contains built-in `"default"` and `"yang2012"` presets (see
+/// [`Params::default`] and [`Params::yang2012`]).  They can be overridden by
+/// registering presets with the same names.
+///
+/// ## Example
+///
+/// ```
+/// use empfindung::cie00;
+///
+/// let mut registry = cie00::PresetRegistry::new();
+/// registry.register("textile", cie00::Params::new().with_l(2.0));
+///
+/// let colour_1 = (38.972, 58.991, 37.138);
+/// let colour_2 = (54.528, 42.416, 54.497);
+/// let delta_e = registry.diff_named(colour_1, colour_2, "textile");
+/// approx::assert_abs_diff_eq!(15.841, delta_e.unwrap(), epsilon = 0.01);
+/// assert_eq!(None, registry.diff_named(colour_1, colour_2, "paper"));
+/// ```
+#[derive(Clone, PartialEq, Debug)]
+pub struct PresetRegistry {
+    presets: BTreeMap<String, Params>,
+}
+
+impl PresetRegistry {
+    /// Creates a new registry with the built-in presets.
+    pub fn new() -> Self {
+        let presets = BUILTIN_PRESETS
+            .iter()
+            .map(|(name, ksub)| (String::from(*name), *ksub))
+            .collect();
+        Self { presets }
+    }
+
+    /// Registers preset with given name.
+    ///
+    /// If preset with the same name already existed, it’s replaced and the
+    /// old parameters are returned.
+    pub fn register(
+        &mut self,
+        name: impl Into<String>,
+        ksub: Params,
+    ) -> Option<Params> {
+        self.presets.insert(name.into(), ksub)
+    }
+
+    /// Returns preset with given name or `None` if there’s no such preset.
+    pub fn get(&self, name: &str) -> Option<Params> {
+        self.presets.get(name).copied()
+    }
+
+    /// Returns an iterator over names and parameters of all presets in
+    /// alphabetical order.
+    pub fn iter(&self) -> impl Iterator<Item = (&str, Params)> {
+        self.presets
+            .iter()
+            .map(|(name, ksub)| (name.as_str(), *ksub))
+    }
+
+    /// Returns the CIEDE2000 colour difference between two L\*a\*b\* colours
+    /// using named preset.
+    ///
+    /// Returns `None` if there’s no preset with given name.
+    pub fn diff_named(
+        &self,
+        color_1: impl crate::ToLab,
+        color_2: impl crate::ToLab,
+        preset: &str,
+    ) -> Option<f32> {
+        let ksub = self.get(preset)?;
+        Some(diff_with_params(color_1, color_2, ksub))
+    }
 }
 
-fn get_h_prime(x: f32, y: f32) -> f32 {
-    if x == 0.0 && y == 0.0 {
-        return 0.0;
+impl Default for PresetRegistry {
+    fn default() -> Self { Self::new() }
+}
+
+/// Returns the 1 + G factor by which a\* coordinates are scaled in the
+/// CIEDE2000 formula.
+fn get_a_prime_factor<F: Float>(c1: F, c2: F) -> F {
+    let tmp = ((c1 + c2) * F::lit(0.5)).powi(7);
+    F::lit(1.5) -
+        (tmp / (tmp + F::lit(TWENTY_FIVE_TO_SEVENTH))).sqrt() * F::lit(0.5)
+}
+
+fn get_h_prime<F: Float>(x: F, y: F) -> F {
+    let zero = F::lit(0.0);
+    if x == zero && y == zero {
+        return zero;
     }
     let rad = x.atan2(y);
-    if rad < 0.0 {
-        rad + TAU_32
+    if rad < zero {
+        rad + F::TAU
     } else {
         rad
     }
 }
 
-fn get_delta_h_prime(c1: f32, c2: f32, h_prime_1: f32, h_prime_2: f32) -> f32 {
-    if 0.0 == c1 || 0.0 == c2 {
-        return 0.0;
+fn get_delta_h_prime<F: Float>(c1: F, c2: F, h_prime_1: F, h_prime_2: F) -> F {
+    let zero = F::lit(0.0);
+    if zero == c1 || zero == c2 {
+        return zero;
     }
     let diff = h_prime_2 - h_prime_1;
-    if diff.abs() <= PI_32 {
+    if diff.abs() <= F::PI {
         diff
     } else if h_prime_2 <= h_prime_1 {
-        diff + TAU_32
+        diff + F::TAU
     } else {
-        diff - TAU_32
+        diff - F::TAU
     }
 }
 
 #[rustfmt::skip]
-fn get_upcase_t(upcase_h_prime_bar: f32) -> f32 {
-    const THIRTY_DEG_IN_RAD: f32 = (TAU_64 / 12.0) as f32;
-    const SIX_DEG_IN_RAD: f32 = (TAU_64 / 60.0) as f32;
-    const SIXTY_THREE_DEG_IN_RAD: f32 = (TAU_64 * 7.0 / 40.0) as f32;
+fn get_upcase_t<F: Float>(upcase_h_prime_bar: F) -> F {
+    let thirty_deg_in_rad = F::lit(TAU_64 / 12.0);
+    let six_deg_in_rad = F::lit(TAU_64 / 60.0);
+    let sixty_three_deg_in_rad = F::lit(TAU_64 * 7.0 / 40.0);
+    let h = upcase_h_prime_bar;
 
-    1.0 - 0.17 * (      upcase_h_prime_bar - THIRTY_DEG_IN_RAD     ).cos()
-        + 0.24 * (2.0 * upcase_h_prime_bar                         ).cos()
-        + 0.32 * (3.0 * upcase_h_prime_bar + SIX_DEG_IN_RAD        ).cos()
-        - 0.20 * (4.0 * upcase_h_prime_bar - SIXTY_THREE_DEG_IN_RAD).cos()
+    F::lit(1.0)
+        - F::lit(0.17) * (               h - thirty_deg_in_rad     ).fast_cos()
+        + F::lit(0.24) * (F::lit(2.0) * h                          ).fast_cos()
+        + F::lit(0.32) * (F::lit(3.0) * h + six_deg_in_rad        ).fast_cos()
+        - F::lit(0.20) * (F::lit(4.0) * h - sixty_three_deg_in_rad).fast_cos()
 }
 
-fn get_r_sub_t(c_prime_bar: f32, upcase_h_prime_bar: f32) -> f32 {
+fn get_r_sub_t<F: Float>(c_prime_bar: F, upcase_h_prime_bar: F) -> F {
     let c7 = c_prime_bar.powi(7);
-    let h = upcase_h_prime_bar * (14.4 / TAU_64) as f32 - 11.0;
-    -2.0 * (c7 / (c7 + TWENTY_FIVE_TO_SEVENTH)).sqrt() *
-        ((-h.powi(2)).exp() * (TAU_64 / 6.0) as f32).sin()
+    let h = upcase_h_prime_bar * F::lit(14.4 / TAU_64) - F::lit(11.0);
+    F::lit(-2.0) *
+        (c7 / (c7 + F::lit(TWENTY_FIVE_TO_SEVENTH))).sqrt() *
+        ((-h.powi(2)).exp() * F::lit(TAU_64 / 6.0)).fast_sin()
 }
 
-const TWENTY_FIVE_TO_SEVENTH: f32 = 6103515625f32;
-const TAU_32: f32 = std::f32::consts::TAU;
-const PI_32: f32 = std::f32::consts::PI;
-const TAU_64: f64 = std::f64::consts::TAU;
+const TWENTY_FIVE_TO_SEVENTH: f64 = 6103515625.0;
+const TAU_64: f64 = core::f64::consts::TAU;
 
 
 #[cfg(test)]
 mod tests {
     #[test]
-    fn test_zero() { crate::testutil::do_test_zero(|a, b| super::diff(a, b)); }
+    fn test_zero() { crate::testutil::do_test_zero(super::diff); }
 
     #[test]
     fn test_zero_with_params() {
@@ -379,9 +1471,91 @@ mod tests {
         });
     }
 
+    #[test]
+    fn test_params_builder() {
+        use super::Params;
+
+        assert_eq!(Params::default(), Params::new());
+        assert_eq!(Params::yang2012(), Params::new().with_l(0.65).with_h(4.0));
+        assert_eq!(
+            Params {
+                l: 2.0,
+                c: 3.0,
+                h: 4.0
+            },
+            Params::new().with_h(4.0).with_c(3.0).with_l(2.0)
+        );
+    }
+
+    #[test]
+    fn test_params_lerp() {
+        use super::Params;
+
+        let (a, b) = (Params::new(), Params::yang2012());
+        assert_eq!(a, Params::lerp(a, b, 0.0));
+        assert_eq!(b, Params::lerp(a, b, 1.0));
+        assert_eq!(Params::new().with_l(1.65).with_c(2.0).with_h(5.0), a + b);
+        assert_eq!(Params::new().with_l(2.0).with_c(2.0).with_h(2.0), a * 2.0);
+    }
+
+    #[test]
+    fn test_preset_registry() {
+        use super::{Params, PresetRegistry};
+
+        let mut registry = PresetRegistry::new();
+        assert_eq!(Some(Params::default()), registry.get("default"));
+        assert_eq!(Some(Params::yang2012()), registry.get("yang2012"));
+        assert_eq!(None, registry.get("textile"));
+        assert_eq!(None, registry.get("Default"));
+
+        let textile = Params::new().with_l(2.0);
+        assert_eq!(None, registry.register("textile", textile));
+        assert_eq!(Some(textile), registry.get("textile"));
+        assert_eq!(
+            Some(Params::default()),
+            registry.register(String::from("default"), textile)
+        );
+        assert_eq!(Some(textile), registry.get("default"));
+        assert_eq!(
+            vec![
+                ("default", textile),
+                ("textile", textile),
+                ("yang2012", Params::yang2012())
+            ],
+            registry.iter().collect::<Vec<_>>()
+        );
+
+        let (a, b) = ((50.0, 2.6772, -79.7751), (50.0, 0.0, -82.7485));
+        let want = super::diff_with_params(a, b, textile);
+        assert_eq!(Some(want), registry.diff_named(a, b, "textile"));
+        assert_eq!(None, registry.diff_named(a, b, "paper"));
+        assert_eq!(PresetRegistry::new(), PresetRegistry::default());
+    }
+
+    #[test]
+    fn test_diff_named() {
+        use super::Params;
+
+        let (a, b) = ((50.0, 2.6772, -79.7751), (50.0, 0.0, -82.7485));
+        assert_eq!(Some(super::diff(a, b)), super::diff_named(a, b, "default"));
+        assert_eq!(
+            Some(super::diff_with_params(a, b, Params::yang2012())),
+            super::diff_named(a, b, "yang2012")
+        );
+        assert_eq!(None, super::diff_named(a, b, "textile"));
+    }
+
+    #[test]
+    #[cfg(debug_assertions)]
+    #[should_panic]
+    fn test_params_zero() {
+        use super::Params;
+        let _ = Params::new() + Params::new().with_l(-1.0);
+    }
+
     #[test]
     fn test_symmetric() {
-        crate::testutil::do_test_symmetric(|a, b| super::diff(a, b));
+        crate::testutil::do_test_symmetric(super::diff);
     }
 
     #[test]
@@ -392,6 +1566,137 @@ mod tests {
         });
     }
 
+    #[test]
+    fn test_diff_and_euclidean() {
+        let ksub = super::Params::yang2012();
+        for (_, colour_1, colour_2) in TESTS.iter().copied() {
+            let got = super::diff_and_euclidean(colour_1, colour_2, ksub);
+            let want = (
+                super::diff_with_params(colour_1, colour_2, ksub),
+                crate::cie76::diff(colour_1, colour_2),
+            );
+            assert_eq!(want, got);
+        }
+    }
+
+    #[test]
+    fn test_diff_fn() {
+        let ksub = super::Params::yang2012();
+        let diff = super::diff_fn(ksub);
+        for (_, colour_1, colour_2) in TESTS.iter().copied() {
+            let want = super::diff_with_params(colour_1, colour_2, ksub);
+            assert_eq!(want, diff(colour_1, colour_2));
+        }
+    }
+
+    #[test]
+    fn test_diff_neutral_relaxed() {
+        let ksub = super::Params::default();
+        for (_, colour_1, colour_2) in TESTS.iter().copied() {
+            let want = super::diff_with_params(colour_1, colour_2, ksub);
+            let got =
+                super::diff_neutral_relaxed(colour_1, colour_2, ksub, 0.0);
+            assert_eq!(want, got);
+        }
+
+        // Grey colours with opposite hues but different lightness.
+        let colour_1 = (50.0, 0.3, 0.4);
+        let colour_2 = (52.0, -0.3, -0.4);
+        let full = super::diff_with_params(colour_1, colour_2, ksub);
+        let got = super::diff_neutral_relaxed(colour_1, colour_2, ksub, 1.0);
+        let want = super::diff_with_params(colour_1, (52.0, 0.3, 0.4), ksub);
+        assert!(got < full, "{} ≥ {}", got, full);
+        approx::assert_abs_diff_eq!(want, got, epsilon = 0.001);
+        let got = super::diff_neutral_relaxed(colour_1, colour_2, ksub, 0.4);
+        assert_eq!(full, got);
+    }
+
+    /// Tests symmetry for pairs of colours whose hues straddle the 0°/360°
+    /// wraparound or whose hue difference is close to 180°.  Those are the
+    /// cases where `get_delta_h_prime` and `upcase_h_prime_bar` computation
+    /// branch which makes them most likely to break symmetry.
+    #[test]
+    fn test_symmetric_hue_wraparound() {
+        let lab = |l: f32, c: f32, deg: f32| {
+            let rad = deg.to_radians();
+            (l, c * rad.cos(), c * rad.sin())
+        };
+        let ksub = super::Params::yang2012();
+        for h1 in [-10.0, -1.0, -0.001, 0.0, 0.001, 1.0, 10.0, 90.0] {
+            for dh in [
+                -180.5, -180.0, -179.5, -20.0, -2.0, 2.0, 20.0, 179.5, 180.0,
+                180.5,
+            ] {
+                for (c1, c2) in [(0.5, 1.5), (20.0, 25.0), (60.0, 40.0)] {
+                    let a = lab(50.0, c1, h1);
+                    let b = lab(55.0, c2, h1 + dh);
+                    assert_eq!(super::diff(a, b), super::diff(b, a));
+                    assert_eq!(
+                        super::diff_with_params(a, b, ksub),
+                        super::diff_with_params(b, a, ksub)
+                    );
+                }
+            }
+        }
+    }
+
+    /// Tests that CIEDE2000 does not satisfy triangle inequality as documented
+    /// in the module description.
+    #[test]
+    fn test_not_triangle() {
+        let a = (40.14, 86.92, 21.11);
+        let b = (33.23, -9.66, -3.06);
+        let c = (26.32, -96.50, -33.83);
+        let diff = super::diff;
+        assert!(diff(a, c) > diff(a, b) + diff(b, c));
+    }
+
+    #[test]
+    fn test_hue_difference() {
+        use super::{hue_difference_deg, hue_difference_rad};
+
+        let tests = [
+            ((50.0, 10.0, 0.0), (50.0, 0.0, 10.0), 90.0),
+            ((50.0, 10.0, 0.0), (50.0, 0.0, -10.0), -90.0),
+            // a* is scaled by 1 + G which makes the angle smaller than the
+            // -11.42° one would get from plain a* and b*.
+            ((50.0, 10.0, 1.0), (50.0, 10.0, -1.0), -7.734),
+            ((50.0, 10.0, 0.0), (50.0, 0.0, 0.0), 0.0),
+            ((50.0, 0.0, 0.0), (50.0, 0.0, 0.0), 0.0),
+        ];
+        for (colour_1, colour_2, want) in tests {
+            let deg = hue_difference_deg(colour_1, colour_2);
+            approx::assert_abs_diff_eq!(want, deg, epsilon = 0.001);
+            let rad = hue_difference_rad(colour_1, colour_2);
+            let want_rad = want.to_radians();
+            approx::assert_abs_diff_eq!(want_rad, rad, epsilon = 0.001);
+            let rev = hue_difference_deg(colour_2, colour_1);
+            approx::assert_abs_diff_eq!(-want, rev, epsilon = 0.001);
+        }
+    }
+
+    #[test]
+    fn test_diff_flush_subnormals() {
+        use super::{diff_flush_subnormals, diff_with_params, Params};
+
+        let ksub = Params::default();
+        let tiny = f32::MIN_POSITIVE / 4.0;
+        let want = diff_with_params((50.0, 0.0, 0.0), (60.0, 10.0, 10.0), ksub);
+        let got = diff_flush_subnormals(
+            (50.0, tiny, -tiny),
+            (60.0, 10.0, 10.0),
+            ksub,
+        );
+        assert_eq!(want, got);
+
+        for (_, a, b) in TESTS.iter() {
+            assert_eq!(
+                diff_with_params(a, b, ksub),
+                diff_flush_subnormals(a, b, ksub)
+            );
+        }
+    }
+
     // Tests taken from Table 1: "CIEDE2000 total color difference test data" of
     // "The CIEDE2000 Color-Difference Formula: Implementation Notes,
     // Supplementary Test Data, and Mathematical Observations" by Gaurav Sharma,
@@ -399,7 +1704,7 @@ mod tests {
     //
     // http://www.ece.rochester.edu/~gsharma/papers/CIEDE2000CRNAFeb05.pdf
     #[rustfmt::skip]
-    static TESTS: [(f32, (f32, f32, f32), (f32, f32, f32)); 34] = [
+    static TESTS: [crate::testutil::TestCase; 34] = [
         (100.0,   (100.0,     0.0050,  -0.0100), ( 0.0000,   0.0000,   0.0000)),
         ( 2.0425, (50.0000,   2.6772, -79.7751), (50.0000,   0.0000, -82.7485)),
         ( 2.8615, (50.0000,   3.1571, -77.2803), (50.0000,   0.0000, -82.7485)),
@@ -436,8 +1741,263 @@ mod tests {
         ( 0.9082, ( 2.0776,   0.0795,  -1.1350), ( 0.9033,  -0.0636,  -0.5514)),
     ];
 
+    #[test]
+    fn test_within() {
+        crate::testutil::do_test_within(&TESTS, super::diff, super::within);
+    }
+
+    #[test]
+    fn test_diff_array() {
+        let colours = crate::testutil::generate_colours(17);
+        let reference = colours[0];
+        let palette: [(f32, f32, f32); 16] =
+            core::array::from_fn(|i| colours[i + 1]);
+        let mut want = [0.0; 16];
+        super::diff_many(reference, &palette, &mut want);
+        assert_eq!(want, super::diff_array(reference, &palette));
+        let empty: [(f32, f32, f32); 0] = [];
+        assert_eq!([0.0; 0], super::diff_array(reference, &empty));
+    }
+
+    #[test]
+    fn test_diff_no_rotation() {
+        use crate::testutil::EPSILON;
+
+        let ksub = super::Params::default();
+        for (want, colour_1, colour_2) in TESTS.iter() {
+            let got = super::diff_no_rotation(*colour_1, *colour_2, ksub);
+            let components = super::diff_components(*colour_1, *colour_2, ksub);
+            let (l, c, h) =
+                (components.lightness, components.chroma, components.hue);
+            approx::assert_abs_diff_eq!(
+                (l * l + c * c + h * h).sqrt(),
+                got,
+                epsilon = EPSILON
+            );
+            if components.rotation * c * h == 0.0 {
+                approx::assert_abs_diff_eq!(*want, got, epsilon = EPSILON);
+            }
+        }
+
+        // In the blue region, the rotation term is significant.
+        let colour_1 = (50.0, 2.6772, -79.7751);
+        let colour_2 = (50.0, 0.0, -82.7485);
+        let got = super::diff_no_rotation(colour_1, colour_2, ksub);
+        approx::assert_abs_diff_eq!(1.6081, got, epsilon = 0.0001);
+        let got = super::diff_with_params(colour_1, colour_2, ksub);
+        approx::assert_abs_diff_eq!(2.0425, got, epsilon = 0.0001);
+    }
+
+    #[test]
+    fn test_diff_weighted() {
+        use crate::testutil::EPSILON;
+
+        let ksub = super::Params::default();
+        for (want, colour_1, colour_2) in TESTS.iter() {
+            let (colour_1, colour_2) = (*colour_1, *colour_2);
+            let got =
+                super::diff_weighted(colour_1, colour_2, ksub, (1.0, 1.0, 1.0));
+            approx::assert_abs_diff_eq!(*want, got, epsilon = EPSILON);
+
+            let components = super::diff_components(colour_1, colour_2, ksub);
+            let (l, c, h) =
+                (components.lightness, components.chroma, components.hue);
+            let rch = components.rotation * c * h;
+            for &(weights, want) in [
+                ((0.0, 1.0, 1.0), c * c + h * h + rch),
+                ((1.0, 0.0, 1.0), l * l + h * h + rch),
+                ((1.0, 1.0, 0.0), l * l + c * c + rch),
+                ((2.0, 0.5, 1.0), 2.0 * l * l + 0.5 * c * c + h * h + rch),
+            ]
+            .iter()
+            {
+                let got =
+                    super::diff_weighted(colour_1, colour_2, ksub, weights);
+                approx::assert_abs_diff_eq!(
+                    want.max(0.0).sqrt(),
+                    got,
+                    epsilon = EPSILON
+                );
+            }
+        }
+
+        // Lightness difference only.
+        let (colour_1, colour_2) = ((50.0, 0.0, 0.0), (60.0, 0.0, 0.0));
+        let got =
+            super::diff_weighted(colour_1, colour_2, ksub, (0.0, 1.0, 1.0));
+        assert_eq!(0.0, got);
+        let got =
+            super::diff_weighted(colour_1, colour_2, ksub, (4.0, 0.0, 0.0));
+        let want = super::diff_with_params(colour_1, colour_2, ksub) * 2.0;
+        approx::assert_abs_diff_eq!(want, got, epsilon = EPSILON);
+    }
+
+    #[test]
+    fn test_diff_squared() {
+        crate::testutil::do_test_squared(
+            &TESTS,
+            super::diff,
+            super::diff_squared,
+        );
+    }
+
     #[test]
     fn test_difference() {
         crate::testutil::do_test_difference(&TESTS, super::diff);
     }
+
+    #[test]
+    #[cfg(feature = "simd")]
+    fn test_diff_slice_simd() {
+        let colours_1: Vec<_> = TESTS.iter().map(|test| test.1).collect();
+        let colours_2: Vec<_> = TESTS.iter().map(|test| test.2).collect();
+        let mut out = vec![0.0; TESTS.len()];
+        let ksub = super::Params::default();
+        super::diff_slice_simd(&colours_1, &colours_2, ksub, &mut out);
+        for (test, got) in TESTS.iter().zip(out.iter()) {
+            let want = super::diff_with_params(test.1, test.2, ksub);
+            approx::assert_abs_diff_eq!(want, *got, epsilon = 0.001);
+        }
+    }
+
+    #[test]
+    fn test_diff_f64() {
+        crate::testutil::do_test_difference_f64(&TESTS, super::diff_f64);
+    }
+
+    #[test]
+    #[cfg(all(feature = "lab", feature = "rgb"))]
+    fn test_diff_rgb_many() {
+        let pixels: Vec<_> = (0..=255u8)
+            .step_by(15)
+            .flat_map(|r| {
+                (0..=255u8).step_by(51).map(move |g| {
+                    rgb::RGB8::new(r, g, 255 - r / 2 - g / 2)
+                })
+            })
+            .collect();
+        for &reference in pixels.iter().step_by(7) {
+            let mut out = vec![0.0; pixels.len()];
+            super::diff_rgb_many(reference, &pixels, &mut out);
+            for (pixel, got) in pixels.iter().zip(out.iter()) {
+                let want = super::diff(reference, pixel);
+                approx::assert_abs_diff_eq!(want, *got, epsilon = 0.0001);
+            }
+        }
+    }
+
+    #[test]
+    fn test_diff_debug() {
+        let ksub = super::Params::default();
+        for test in TESTS.iter() {
+            let want = super::diff_with_params(test.1, test.2, ksub);
+            assert_eq!(want, super::diff_debug(test.1, test.2, ksub).0);
+        }
+
+        // Values from the supplementary test data by Sharma et al.
+        #[rustfmt::skip]
+        let tests = [
+            // C̄′, h₁′, h₂′, H̄′, T, R_T
+            ((50.0, 2.6772, -79.7751), (50.0, 0.0, -82.7485),
+             (81.2843, 271.9222, 270.0, 270.9611, 0.6907, -1.7042)),
+            ((50.0, 2.49, -0.001), (50.0, -2.49, 0.0009),
+             (3.7346, 359.9847, 179.9862, 269.9854, 0.7212, -0.0022)),
+            ((50.0, 2.49, -0.001), (50.0, -2.49, 0.0011),
+             (3.7346, 359.9847, 179.9831, 89.9839, 0.6175, 0.0)),
+        ];
+        for (colour_1, colour_2, want) in tests.iter() {
+            let (_, got) = super::diff_debug(*colour_1, *colour_2, ksub);
+            let got = (
+                got.c_prime_bar,
+                got.h_prime_1,
+                got.h_prime_2,
+                got.upcase_h_prime_bar,
+                got.upcase_t,
+                got.r_sub_t,
+            );
+            // Trigonometric approximations affect the fourth decimal place.
+            let epsilon =
+                if cfg!(feature = "fast-trig") { 0.001 } else { 0.0001 };
+            let eq = |a: f32, b: f32| (a - b).abs() < epsilon;
+            assert!(
+                eq(want.0, got.0) &&
+                    eq(want.1, got.1) &&
+                    eq(want.2, got.2) &&
+                    eq(want.3, got.3) &&
+                    eq(want.4, got.4) &&
+                    eq(want.5, got.5),
+                "{:?} ≠ {:?}; colours: {:?}, {:?}",
+                want,
+                got,
+                colour_1,
+                colour_2
+            );
+        }
+    }
+
+    #[test]
+    fn test_diff_components() {
+        for ksub in [super::Params::default(), super::Params::yang2012()] {
+            for test in TESTS.iter() {
+                let want = super::diff_with_params(test.1, test.2, ksub);
+                let components = super::diff_components(test.1, test.2, ksub);
+                assert_eq!(want, components.delta_e());
+            }
+        }
+    }
+
+    #[test]
+    fn test_reference() {
+        for ksub in [super::Params::default(), super::Params::yang2012()] {
+            for test in TESTS.iter() {
+                let reference = super::Reference::new(test.1);
+                let want = super::diff_with_params(test.1, test.2, ksub);
+                assert_eq!(want, reference.diff_with_params(test.2, ksub));
+                let want = super::diff_with_params(test.2, test.1, ksub);
+                let reference = super::Reference::new(test.2);
+                assert_eq!(want, reference.diff_with_params(test.1, ksub));
+            }
+        }
+        for test in TESTS.iter() {
+            let want = super::diff(test.1, test.2);
+            assert_eq!(want, super::Reference::new(test.1).diff(test.2));
+        }
+    }
+
+    #[test]
+    fn test_reference_on_axis() {
+        // Cached h′ of colours on a* or b* axis must give the same results as
+        // calculating it from the scaled a′ coordinate.
+        let references = [
+            (50.0, 0.0, 0.0),
+            (50.0, -0.0, 0.0),
+            (50.0, 0.0, -0.0),
+            (50.0, 2.5, 0.0),
+            (50.0, -2.5, 0.0),
+            (50.0, 40.0, -0.0),
+            (50.0, 0.0, 2.5),
+            (50.0, -0.0, -40.0),
+            (50.0, 1e-40, 0.0),
+            (50.0, 0.0, 1000.0),
+        ];
+        let ksub = super::Params::default();
+        for reference in references {
+            let cached = super::ReferenceTerms::new(reference);
+            assert!(cached.h_prime.is_some(), "{:?}", reference);
+            let uncached = super::ReferenceTerms {
+                h_prime: None,
+                ..cached
+            };
+            let terms = |reference, colour| {
+                super::get_terms_and_intermediates(reference, colour, ksub)
+            };
+            for test in TESTS.iter() {
+                for colour in [test.1, test.2] {
+                    let want = terms(&uncached, colour);
+                    let got = terms(&cached, colour);
+                    assert_eq!(want, got, "{:?} {:?}", reference, colour);
+                }
+            }
+        }
+    }
 }
@@ -23,7 +23,10 @@
 //!
 //! The CIE76 (ΔE₇₆) is a simple metric based on the L\*a\*b\* colour space.  In
 //! fact, it’s nothing more than Euclidean distance between the two colours in
-//! that colour space.
+//! that colour space.  As such, it is a metric in the mathematical sense: it
+//! is symmetric and satisfies the triangle inequality.
+
+use crate::float::Float;
 
 /// Returns the CIE76 colour difference between two L\*a\*b\* colours.
 ///
@@ -62,12 +65,283 @@ approx::assert_abs_diff_eq!(104.05857, delta_e, epsilon = 0.001);
 )]
 /// ```
 pub fn diff(colour_1: impl crate::ToLab, colour_2: impl crate::ToLab) -> f32 {
-    let colour_1 = colour_1.to_lab();
-    let colour_2 = colour_2.to_lab();
+    diff_squared_impl(colour_1.to_lab(), colour_2.to_lab()).sqrt()
+}
+
+/// Returns the CIE76 colour difference between two L\*a\*b\* colours
+/// calculating the final square root with given function.
+///
+/// This is the same as [`diff`] except that `sqrt` is used in place of
+/// `f32::sqrt`.  With an approximate function such as [`crate::fast_sqrt`]
+/// the result is slightly less accurate but on some targets cheaper to
+/// compute.  Since CIE76 is little more than the square root, the choice of
+/// the function dominates the cost of the calculation.
+///
+/// ## Example
+///
+/// ```
+/// use empfindung::cie76;
+///
+/// let colour_1 = (38.972, 58.991, 37.138);
+/// let colour_2 = (54.528, 42.416, 54.497);
+/// let delta_e =
+///     cie76::diff_with_sqrt(colour_1, colour_2, empfindung::fast_sqrt);
+/// approx::assert_abs_diff_eq!(28.601656, delta_e, epsilon = 0.05);
+/// ```
+pub fn diff_with_sqrt(
+    colour_1: impl crate::ToLab,
+    colour_2: impl crate::ToLab,
+    sqrt: impl Fn(f32) -> f32,
+) -> f32 {
+    sqrt(diff_squared_impl(colour_1.to_lab(), colour_2.to_lab()))
+}
+
+/// Returns the CIE76 colour difference between two L\*a\*b\* colours
+/// calculated with double precision.
+///
+/// This is the same as [`diff`] except that the colours are converted with
+/// [`crate::ToLab64`] and the computation is done with `f64` numbers.
+///
+/// ## Example
+///
+/// ```
+/// use empfindung::cie76;
+///
+/// let colour_1 = (38.972f64, 58.991, 37.138);
+/// let colour_2 = (54.528f64, 42.416, 54.497);
+/// let delta_e = cie76::diff_f64(colour_1, colour_2);
+/// approx::assert_abs_diff_eq!(28.601655, delta_e, epsilon = 0.000001);
+/// ```
+pub fn diff_f64(
+    colour_1: impl crate::ToLab64,
+    colour_2: impl crate::ToLab64,
+) -> f64 {
+    diff_squared_impl(colour_1.to_lab64(), colour_2.to_lab64()).sqrt()
+}
+
+fn diff_squared_impl<F: Float>(
+    colour_1: (F, F, F),
+    colour_2: (F, F, F),
+) -> F {
     let dl = colour_1.0 - colour_2.0;
     let da = colour_1.1 - colour_2.1;
     let db = colour_1.2 - colour_2.2;
-    (dl * dl + da * da + db * db).sqrt()
+    dl * dl + da * da + db * db
+}
+
+/// Returns whether the CIE76 colour difference between two L\*a\*b\* colours
+/// is at most `threshold`.
+///
+/// This is equivalent to `diff(colour_1, colour_2) <= threshold` except that
+/// the square of the distance is compared against `threshold²` which avoids
+/// calculating the square root.  Since square root is monotonic, comparing the
+/// squares gives the same answer as comparing the differences themselves and
+/// in general orders colours the same way.  This makes the function measurably
+/// faster than [`diff`] in hot loops.  Because of rounding, the result may
+/// differ from comparing [`diff`] result when the threshold is within
+/// a rounding error of the actual distance.  Negative threshold is never
+/// satisfied.
+///
+/// ## Example
+///
+/// ```
+/// use empfindung::cie76;
+///
+/// let colour_1 = (38.972, 58.991, 37.138);
+/// let colour_2 = (54.528, 42.416, 54.497);
+/// assert!(cie76::within(colour_1, colour_2, 30.0));
+/// assert!(!cie76::within(colour_1, colour_2, 28.0));
+/// ```
+pub fn within(
+    colour_1: impl crate::ToLab,
+    colour_2: impl crate::ToLab,
+    threshold: f32,
+) -> bool {
+    threshold >= 0.0 &&
+        diff_squared_impl(colour_1.to_lab(), colour_2.to_lab()) <=
+            threshold * threshold
+}
+
+/// Returns square of the CIE76 colour difference between two L\*a\*b\*
+/// colours.
+///
+/// The result orders colours the same way as [`diff`] does (see [`within`])
+/// which makes the function useful when only the ordering matters, e.g. when
+/// looking for the nearest colour, as it skips calculating the final square
+/// root.
+///
+/// ## Example
+///
+/// ```
+/// use empfindung::cie76;
+///
+/// let colour_1 = (38.972, 58.991, 37.138);
+/// let colour_2 = (54.528, 42.416, 54.497);
+/// let delta_e_squared = cie76::diff_squared(colour_1, colour_2);
+/// approx::assert_abs_diff_eq!(818.05, delta_e_squared, epsilon = 0.01);
+/// ```
+pub fn diff_squared(
+    colour_1: impl crate::ToLab,
+    colour_2: impl crate::ToLab,
+) -> f32 {
+    diff_squared_impl(colour_1.to_lab(), colour_2.to_lab())
+}
+
+/// Calculates the CIE76 colour difference between a reference colour and
+/// each colour in a slice.
+///
+/// The reference colour is converted to L\*a\*b\* only once and results are
+/// stored in `out`.  They are bit-for-bit identical to calling [`diff`] in
+/// a loop.  See [`crate::batch::diff_many`].
+///
+/// # Panics
+///
+/// Panics if length of `colours` differs from length of `out`.
+///
+/// ## Example
+///
+/// ```
+/// use empfindung::cie76;
+///
+/// let reference = (50.0, 2.5, 0.0);
+/// let colours = [(73.0, 25.0, -18.0), (61.0, -5.0, 29.0)];
+/// let mut out = [0.0; 2];
+/// cie76::diff_many(reference, &colours, &mut out);
+/// assert_eq!(cie76::diff(reference, colours[0]), out[0]);
+/// assert_eq!(cie76::diff(reference, colours[1]), out[1]);
+/// ```
+pub fn diff_many<C: crate::ToLab>(
+    reference: impl crate::ToLab,
+    colours: &[C],
+    out: &mut [f32],
+) {
+    crate::batch::diff_many(reference, colours, diff_fn(), out)
+}
+
+/// Returns a function calculating the CIE76 colour difference between two
+/// L\*a\*b\* colours.
+///
+/// The returned function operates on plain `(f32, f32, f32)` tuples.  It is
+/// provided for symmetry with `diff_fn` functions in other modules.
+///
+/// ## Example
+///
+/// ```
+/// use empfindung::cie76;
+///
+/// let diff = cie76::diff_fn();
+/// let delta_e = diff((38.972, 58.991, 37.138), (54.528, 42.416, 54.497));
+/// approx::assert_abs_diff_eq!(28.601656, delta_e, epsilon = 0.001);
+/// ```
+pub fn diff_fn() -> impl Fn((f32, f32, f32), (f32, f32, f32)) -> f32 + Copy {
+    diff
+}
+
+/// Returns the CIE76 colour difference between two L\*a\*b\* colours given in
+/// fixed-point representation.
+///
+/// The coordinates of the colours as well as the result are signed Q-format
+/// numbers with `frac_bits` fractional bits, i.e. value `x` represents `x /
+/// 2^frac_bits`.  The calculation is done using integer arithmetic only (with
+/// the square root computed using Newton’s method) which makes the function
+/// suitable for targets without floating point support.
+///
+/// Since Euclidean distance scales linearly, the format does not affect the
+/// computation; `frac_bits` documents the format of the arguments and the
+/// result and must be less than 32.  The result is rounded to the nearest
+/// representable value so its error is at most 2^-(frac_bits + 1).
+///
+/// All intermediate values are computed with enough bits so that no overflow
+/// can happen for any inputs.  However, the result saturates at `i32::MAX` if
+/// the distance is 2^(31 - frac_bits) or more.  The largest distance between
+/// colours with L\* in `0..=100` range and a\* and b\* in `-128..=128` range
+/// is under 376 so with `frac_bits` of 22 or less no saturation can happen for
+/// such colours.
+///
+/// ## Example
+///
+/// ```
+/// use empfindung::cie76;
+///
+/// const ONE: i32 = 1 << 16;
+/// let colour_1 = [50 * ONE, 3 * ONE, 0];
+/// let colour_2 = [50 * ONE, 0, -4 * ONE];
+/// assert_eq!(5 * ONE, cie76::diff_fixed(colour_1, colour_2, 16));
+///
+/// // 1.5 in Q8 and 2.0 in Q8.
+/// let delta_e = cie76::diff_fixed([0, 384, 0], [0, 0, 512], 8);
+/// assert_eq!(640, delta_e);  // 2.5 in Q8
+/// ```
+pub fn diff_fixed(
+    colour_1: [i32; 3],
+    colour_2: [i32; 3],
+    frac_bits: u32,
+) -> i32 {
+    debug_assert!(frac_bits < 32);
+    let sum = colour_1
+        .iter()
+        .zip(colour_2.iter())
+        .map(|(&lhs, &rhs)| {
+            let d = (i64::from(lhs) - i64::from(rhs)).unsigned_abs();
+            u128::from(d * d)
+        })
+        .sum::<u128>();
+    let root = isqrt(sum);
+    // Round to nearest: (root + ½)² = root² + root + ¼.
+    let root = root + u128::from(sum - root * root > root);
+    root.min(i32::MAX as u128) as i32
+}
+
+/// Returns floor of the square root of a number using Newton’s method.
+fn isqrt(n: u128) -> u128 {
+    if n < 2 {
+        return n;
+    }
+    // Start with a power of two which is not smaller than the result.
+    let bits = 128 - n.leading_zeros();
+    let mut x = 1u128 << bits.div_ceil(2);
+    loop {
+        let y = (x + n / x) / 2;
+        if y >= x {
+            return x;
+        }
+        x = y;
+    }
+}
+
+/// Returns the “redmean” weighted Euclidean distance between two sRGB
+/// colours.
+///
+/// This is not a CIE colour difference formula and the result is not on the
+/// ΔE scale.  It’s a fast approximation of perceptual distance computed
+/// directly on gamma-encoded sRGB components as `sqrt((2 + r̄/256)·ΔR² +
+/// 4·ΔG² + (2 + (255 − r̄)/256)·ΔB²)` where r̄ is the mean of the red
+/// components.  The function avoids conversion to L\*a\*b\* which makes it
+/// suitable for pre-filtering candidates (e.g. in a large palette) before
+/// calculating more accurate but expensive colour difference.
+///
+/// ## Example
+///
+/// ```
+/// use empfindung::cie76;
+/// use rgb::RGB8;
+///
+/// let black = RGB8::new(0, 0, 0);
+/// let white = RGB8::new(255, 255, 255);
+/// let distance = cie76::redmean_rgb(black, white);
+/// approx::assert_abs_diff_eq!(764.834, distance, epsilon = 0.001);
+/// ```
+#[cfg(feature = "rgb")]
+pub fn redmean_rgb(colour_1: rgb::RGB<u8>, colour_2: rgb::RGB<u8>) -> f32 {
+    let r_mean = (f32::from(colour_1.r) + f32::from(colour_2.r)) * 0.5;
+    let delta = |lhs: u8, rhs: u8| f32::from(lhs) - f32::from(rhs);
+    let delta_r = delta(colour_1.r, colour_2.r);
+    let delta_g = delta(colour_1.g, colour_2.g);
+    let delta_b = delta(colour_1.b, colour_2.b);
+    ((2.0 + r_mean / 256.0) * delta_r * delta_r +
+        4.0 * delta_g * delta_g +
+        (2.0 + (255.0 - r_mean) / 256.0) * delta_b * delta_b)
+        .sqrt()
 }
 
 /// Returns the CIE76 colour difference between two sRGB colours.
@@ -83,8 +357,8 @@ pub fn diff(colour_1: impl crate::ToLab, colour_2: impl crate::ToLab) -> f32 {
 /// let delta_e = cie76::diff_rgb(&colour_1, &colour_2);
 /// approx::assert_abs_diff_eq!(104.05857, delta_e, epsilon = 0.001);
 /// ```
-#[cfg(feature = "lab")]
-#[deprecated(note = "Use cie76::diff() with rgb::RGB8 argument")]
+#[cfg(all(feature = "lab", not(feature = "no-deprecated")))]
+#[deprecated(note = "Use cie76::diff() with [u8; 3] or rgb::RGB8 argument")]
 pub fn diff_rgb(colour_1: &[u8; 3], colour_2: &[u8; 3]) -> f32 {
     diff(lab::Lab::from_rgb(colour_1), lab::Lab::from_rgb(colour_2))
 }
@@ -93,7 +367,7 @@ pub fn diff_rgb(colour_1: &[u8; 3], colour_2: &[u8; 3]) -> f32 {
 #[cfg(test)]
 mod tests {
     #[rustfmt::skip]
-    static TESTS: [(f32, (f32, f32, f32), (f32, f32, f32)); 6] = [
+    static TESTS: [crate::testutil::TestCase; 6] = [
         ( 5.0, (0.0, 0.0, 0.0), ( 3.0,  4.0,   0.0)),
         ( 5.0, (0.0, 0.0, 0.0), ( 3.0, -4.0,   0.0)),
         ( 5.0, (0.0, 0.0, 0.0), (-3.0,  4.0,   0.0)),
@@ -102,16 +376,112 @@ mod tests {
         (97.0, (0.0, 0.0, 0.0), ( 0.0, 65.0, -72.0)),
     ];
 
+    #[test]
+    fn test_within() {
+        crate::testutil::do_test_within(&TESTS, super::diff, super::within);
+    }
+
     #[test]
     fn test_difference() {
         crate::testutil::do_test_difference(&TESTS, super::diff);
     }
 
     #[test]
-    fn test_zero() { crate::testutil::do_test_zero(|a, b| super::diff(a, b)) }
+    fn test_diff_squared() {
+        crate::testutil::do_test_squared(
+            &TESTS,
+            super::diff,
+            super::diff_squared,
+        );
+    }
+
+    #[test]
+    fn test_diff_fn() {
+        crate::testutil::do_test_difference(&TESTS, super::diff_fn());
+    }
+
+    #[test]
+    fn test_zero() { crate::testutil::do_test_zero(super::diff) }
+
+    #[test]
+    #[cfg(feature = "rgb")]
+    fn test_redmean_rgb() {
+        use rgb::RGB8;
+
+        for &(want, a, b) in &[
+            (0.0, (10, 20, 30), (10, 20, 30)),
+            (764.834, (0, 0, 0), (255, 255, 255)),
+            (417.1408, (234, 76, 76), (76, 187, 234)),
+            (569.9746, (255, 0, 0), (0, 0, 255)),
+            (15.5247, (100, 100, 100), (110, 100, 100)),
+        ] {
+            let a = RGB8::new(a.0, a.1, a.2);
+            let b = RGB8::new(b.0, b.1, b.2);
+            let got = super::redmean_rgb(a, b);
+            approx::assert_abs_diff_eq!(want, got, epsilon = 0.001);
+            assert_eq!(got, super::redmean_rgb(b, a));
+        }
+
+        // Distance grows as any single component moves away.
+        let base = RGB8::new(128, 128, 128);
+        for channel in 0..3 {
+            let mut prev = 0.0;
+            for value in 129..=255 {
+                let mut colour = base;
+                match channel {
+                    0 => colour.r = value,
+                    1 => colour.g = value,
+                    _ => colour.b = value,
+                }
+                let got = super::redmean_rgb(base, colour);
+                assert!(prev < got, "{} {} {}", channel, prev, got);
+                prev = got;
+            }
+        }
+    }
+
+    #[test]
+    fn test_isqrt() {
+        for n in 0..10_000u128 {
+            let root = super::isqrt(n);
+            assert!(root * root <= n && (root + 1) * (root + 1) > n, "{}", n);
+        }
+        assert_eq!(u64::MAX as u128, super::isqrt(u128::MAX));
+    }
+
+    #[test]
+    fn test_diff_fixed() {
+        for &(want, a, b) in TESTS.iter() {
+            for frac_bits in [0, 8, 16, 22] {
+                let scale = (1 << frac_bits) as f32;
+                let fixed = |c: (f32, f32, f32)| {
+                    let (l, a, b) = (c.0 * scale, c.1 * scale, c.2 * scale);
+                    [l as i32, a as i32, b as i32]
+                };
+                let got = super::diff_fixed(fixed(a), fixed(b), frac_bits);
+                assert_eq!((want * scale) as i32, got);
+            }
+        }
+
+        // Rounding to nearest.
+        assert_eq!(1, super::diff_fixed([0, 0, 0], [1, 1, 0], 0));
+        assert_eq!(2, super::diff_fixed([0, 0, 0], [1, 1, 1], 0));
+
+        // Saturation.
+        let (min, max) = ([i32::MIN; 3], [i32::MAX; 3]);
+        assert_eq!(i32::MAX, super::diff_fixed(min, max, 0));
+    }
 
     #[test]
     fn test_symmetric() {
-        crate::testutil::do_test_symmetric(|a, b| super::diff(a, b))
+        crate::testutil::do_test_symmetric(super::diff)
+    }
+
+    #[test]
+    fn test_triangle() { crate::testutil::do_test_triangle(super::diff) }
+
+    #[test]
+    fn test_diff_f64() {
+        crate::testutil::do_test_difference_f64(&TESTS, super::diff_f64);
     }
 }
@@ -28,9 +28,12 @@
 //! customise the coefficients.
 //!
 //! Note that the distance is not symmetrical, i.e. in general case `diff(a, b,
-//! ksub) != diff(b, a, ksub)`.  Prefer [`crate::cie00`] module if you needa
-//! proper metric or [`crate::cie76`] module if additional performance cost is
-//! not acceptable.
+//! ksub) != diff(b, a, ksub)`.  [`crate::cie00`] module is symmetric but it
+//! doesn’t satisfy the triangle inequality either.  If you need a proper
+//! metric, use [`crate::cie76`] module or, for better perceptual uniformity,
+//! [`crate::din99`] or [`crate::hyab`] modules.
+
+use crate::float::Float;
 
 /// `k` parameters adjusting what effect lightness, hue and chroma difference
 /// will have on the calculated distance.
@@ -53,6 +56,7 @@ pub struct Params {
     pub h: f32,
 }
 
+#[cfg(not(feature = "no-deprecated"))]
 #[deprecated(note = "Use Params name instead")]
 pub type KSubParams = Params;
 
@@ -62,22 +66,94 @@ impl Default for Params {
 }
 
 impl Params {
+    /// Parameters weighted for graphic arts.
+    pub const GRAPHIC: Self = Self {
+        l: 1.0,
+        c: 0.045,
+        h: 0.015,
+    };
+
+    /// Parameters weighted for textiles.
+    pub const TEXTILES: Self = Self {
+        l: 2.0,
+        c: 0.048,
+        h: 0.014,
+    };
+
     /// Returns parameters weighted for graphic arts.
-    pub fn graphic() -> Self {
+    pub const fn graphic() -> Self { Self::GRAPHIC }
+
+    /// Returns parameters weighted for textiles.
+    pub const fn textiles() -> Self { Self::TEXTILES }
+
+    /// Linearly interpolates between parameters `a` and `b`.
+    ///
+    /// Returns `a` when `t` is zero and `b` when `t` is one.  Values of `t`
+    /// outside of the [0, 1] range extrapolate.
+    ///
+    /// ```
+    /// use empfindung::cie94::Params;
+    ///
+    /// let params = Params::lerp(Params::GRAPHIC, Params::TEXTILES, 0.5);
+    /// approx::assert_abs_diff_eq!(1.5, params.l);
+    /// approx::assert_abs_diff_eq!(0.0465, params.c);
+    /// approx::assert_abs_diff_eq!(0.0145, params.h);
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// In debug builds panics if resulting k_L parameter is zero.
+    pub fn lerp(a: Self, b: Self, t: f32) -> Self {
+        let lerp = |a: f32, b: f32| a * (1.0 - t) + b * t;
         Self {
-            l: 1.0,
-            c: 0.045,
-            h: 0.015,
+            l: lerp(a.l, b.l),
+            c: lerp(a.c, b.c),
+            h: lerp(a.h, b.h),
         }
+        .debug_check()
     }
 
-    /// Returns parameters weighted for textiles.
-    pub fn textiles() -> Self {
+    /// Checks in debug builds that k_L parameter, which is used as
+    /// a denominator, is non-zero.
+    fn debug_check(self) -> Self {
+        debug_assert!(self.l != 0.0, "k_L parameter must not be zero");
+        self
+    }
+}
+
+impl core::ops::Add for Params {
+    type Output = Self;
+
+    /// Adds parameters component-wise.
+    ///
+    /// # Panics
+    ///
+    /// In debug builds panics if resulting k_L parameter is zero.
+    fn add(self, rhs: Self) -> Self {
         Self {
-            l: 2.0,
-            c: 0.048,
-            h: 0.014,
+            l: self.l + rhs.l,
+            c: self.c + rhs.c,
+            h: self.h + rhs.h,
         }
+        .debug_check()
+    }
+}
+
+impl core::ops::Mul<f32> for Params {
+    type Output = Self;
+
+    /// Multiplies all parameters by a scalar.
+    ///
+    /// # Panics
+    ///
+    /// In debug builds panics if resulting k_L parameter is zero.
+    fn mul(self, rhs: f32) -> Self {
+        Self {
+            l: self.l * rhs,
+            c: self.c * rhs,
+            h: self.h * rhs,
+        }
+        .debug_check()
     }
 }
 
@@ -131,24 +207,301 @@ pub fn diff(
     diff_impl(reference.to_lab(), colour.to_lab(), ksub)
 }
 
-fn diff_impl(
-    reference: (f32, f32, f32),
-    colour: (f32, f32, f32),
+/// Returns whether the CIE94 colour difference between two L\*a\*b\* colours
+/// using specified `k` parameters is at most `threshold`.
+///
+/// This is equivalent to `diff(reference, colour, ksub) <= threshold`.
+///
+/// ## Example
+///
+/// ```
+/// use empfindung::cie94;
+///
+/// let reference = (38.972, 58.991, 37.138);
+/// let colour = (54.528, 42.416, 54.497);
+/// let ksub = cie94::Params::graphic();
+/// assert!(cie94::within(reference, colour, ksub, 20.0));
+/// assert!(!cie94::within(reference, colour, ksub, 19.0));
+/// ```
+pub fn within(
+    reference: impl crate::ToLab,
+    colour: impl crate::ToLab,
+    ksub: Params,
+    threshold: f32,
+) -> bool {
+    diff(reference, colour, ksub) <= threshold
+}
+
+/// Returns square of the CIE94 colour difference between two L\*a\*b\*
+/// colours using specified `k` parameters.
+///
+/// Squared differences keep the ordering of the differences returned by
+/// [`diff`] so the result can be compared against a squared threshold
+/// instead.  This makes the function useful when only the ordering matters,
+/// e.g. when looking for the nearest colour.
+///
+/// ## Example
+///
+/// ```
+/// use empfindung::cie94;
+///
+/// let reference = (38.972, 58.991, 37.138);
+/// let colour = (54.528, 42.416, 54.497);
+/// let params = cie94::Params::graphic();
+/// let delta_e_squared = cie94::diff_squared(reference, colour, params);
+/// approx::assert_abs_diff_eq!(379.58, delta_e_squared, epsilon = 0.01);
+/// ```
+pub fn diff_squared(
+    reference: impl crate::ToLab,
+    colour: impl crate::ToLab,
     ksub: Params,
 ) -> f32 {
+    diff_squared_impl(reference.to_lab(), colour.to_lab(), ksub)
+}
+
+/// Calculates the CIE94 colour difference between a reference colour and
+/// each colour in a slice using specified `k` parameters.
+///
+/// The reference colour is converted to L\*a\*b\* only once and results are
+/// stored in `out`.  They are bit-for-bit identical to calling [`diff`] in
+/// a loop.  See [`crate::batch::diff_many`].
+///
+/// # Panics
+///
+/// Panics if length of `colours` differs from length of `out`.
+///
+/// ## Example
+///
+/// ```
+/// use empfindung::cie94;
+///
+/// let reference = (50.0, 2.5, 0.0);
+/// let colours = [(73.0, 25.0, -18.0), (61.0, -5.0, 29.0)];
+/// let params = cie94::Params::graphic();
+/// let mut out = [0.0; 2];
+/// cie94::diff_many(reference, &colours, params, &mut out);
+/// assert_eq!(cie94::diff(reference, colours[0], params), out[0]);
+/// assert_eq!(cie94::diff(reference, colours[1], params), out[1]);
+/// ```
+pub fn diff_many<C: crate::ToLab>(
+    reference: impl crate::ToLab,
+    colours: &[C],
+    ksub: Params,
+    out: &mut [f32],
+) {
+    crate::batch::diff_many(reference, colours, diff_fn(ksub), out)
+}
+
+/// Returns a function calculating the CIE94 colour difference between two
+/// L\*a\*b\* colours using given `k` parameters.
+///
+/// The returned closure captures the parameters and operates on plain
+/// `(f32, f32, f32)` tuples.  As with [`diff`], the first argument is the
+/// reference colour.
+///
+/// ## Example
+///
+/// ```
+/// use empfindung::cie94;
+///
+/// let diff = cie94::diff_fn(cie94::Params::graphic());
+/// let delta_e = diff((38.972, 58.991, 37.138), (54.528, 42.416, 54.497));
+/// approx::assert_abs_diff_eq!(19.482761, delta_e, epsilon = 0.001);
+/// ```
+pub fn diff_fn(
+    ksub: Params,
+) -> impl Fn((f32, f32, f32), (f32, f32, f32)) -> f32 + Copy {
+    move |reference, colour| diff_impl(reference, colour, ksub)
+}
+
+/// Returns the CIE94 colour difference between two L\*a\*b\* colours using
+/// specified `k` parameters and calculating the final square root with given
+/// function.
+///
+/// This is the same as [`diff`] except that `sqrt` is used in place of
+/// `f32::sqrt` for the final square root of the sum of squared components.
+/// With an approximate function such as [`crate::fast_sqrt`] the result is
+/// slightly less accurate but on some targets cheaper to compute.
+///
+/// ## Example
+///
+/// ```
+/// use empfindung::cie94;
+///
+/// let reference = (38.972, 58.991, 37.138);
+/// let colour = (54.528, 42.416, 54.497);
+/// let delta_e = cie94::diff_with_sqrt(
+///     reference, colour, cie94::Params::graphic(), empfindung::fast_sqrt);
+/// approx::assert_abs_diff_eq!(19.482761, delta_e, epsilon = 0.05);
+/// ```
+pub fn diff_with_sqrt(
+    reference: impl crate::ToLab,
+    colour: impl crate::ToLab,
+    ksub: Params,
+    sqrt: impl Fn(f32) -> f32,
+) -> f32 {
+    sqrt(diff_squared_impl(reference.to_lab(), colour.to_lab(), ksub))
+}
+
+/// Returns the weighted lightness, chroma and hue terms of the CIE94 colour
+/// difference between two L\*a\*b\* colours using specified `k` parameters.
+///
+/// The lightness, chroma and hue terms are ΔL\*/(k_L·S_L), ΔC\*/S_C and
+/// ΔH\*/S_H respectively.  Since the formula has no rotation term,
+/// `rotation` is always zero.  ΔH\* is non-negative so the hue term is
+/// never negative.  [`Components::delta_e`](crate::Components::delta_e) of
+/// the result equals [`diff`].
+///
+/// ## Example
+///
+/// ```
+/// use empfindung::cie94;
+///
+/// let reference = (38.972, 58.991, 37.138);
+/// let colour = (54.528, 42.416, 54.497);
+///
+/// let ksub = cie94::Params::graphic();
+/// let components = cie94::diff_components(reference, colour, ksub);
+/// approx::assert_abs_diff_eq!(-15.56, components.lightness, epsilon = 0.01);
+/// approx::assert_abs_diff_eq!(
+///     cie94::diff(reference, colour, ksub), components.delta_e());
+/// ```
+pub fn diff_components(
+    reference: impl crate::ToLab,
+    colour: impl crate::ToLab,
+    ksub: Params,
+) -> crate::Components {
+    let (lightness, chroma, hue) =
+        get_terms(reference.to_lab(), colour.to_lab(), ksub, |c_1, _| c_1);
+    crate::Components {
+        lightness,
+        chroma,
+        hue,
+        rotation: 0.0,
+    }
+}
+
+/// Returns the CIE94 colour difference between two L\*a\*b\* colours using
+/// specified `k` parameters calculated with double precision.
+///
+/// This is the same as [`diff`] except that the colours are converted with
+/// [`crate::ToLab64`] and the computation is done with `f64` numbers.
+///
+/// ## Example
+///
+/// ```
+/// use empfindung::cie94;
+///
+/// let reference = (38.972f64, 58.991, 37.138);
+/// let colour = (54.528f64, 42.416, 54.497);
+/// let delta_e = cie94::diff_f64(reference, colour, cie94::Params::graphic());
+/// approx::assert_abs_diff_eq!(19.482761, delta_e, epsilon = 0.00001);
+/// ```
+pub fn diff_f64(
+    reference: impl crate::ToLab64,
+    colour: impl crate::ToLab64,
+    ksub: Params,
+) -> f64 {
+    diff_impl(reference.to_lab64(), colour.to_lab64(), ksub)
+}
+
+fn diff_impl<F: Float>(
+    reference: (F, F, F),
+    colour: (F, F, F),
+    ksub: Params,
+) -> F {
+    diff_squared_impl(reference, colour, ksub).sqrt()
+}
+
+fn diff_squared_impl<F: Float>(
+    reference: (F, F, F),
+    colour: (F, F, F),
+    ksub: Params,
+) -> F {
+    diff_squared_with_chroma(reference, colour, ksub, |c_1, _| c_1)
+}
+
+/// Calculates square of the CIE94 colour difference with chroma used in the
+/// weighting functions calculated by `weight` from chroma of both colours.
+fn diff_squared_with_chroma<F: Float>(
+    reference: (F, F, F),
+    colour: (F, F, F),
+    ksub: Params,
+    weight: impl Fn(F, F) -> F,
+) -> F {
+    let (l, c, h) = get_terms(reference, colour, ksub, weight);
+    l * l + c * c + h * h
+}
+
+/// Returns the lightness, chroma and hue terms of the CIE94 formula (i.e.
+/// ΔL\*/(k_L·S_L), ΔC\*/S_C and ΔH\*/S_H) with chroma used in the weighting
+/// functions calculated by `weight` from chroma of both colours.
+fn get_terms<F: Float>(
+    reference: (F, F, F),
+    colour: (F, F, F),
+    ksub: Params,
+    weight: impl Fn(F, F) -> F,
+) -> (F, F, F) {
     let delta_l = reference.0 - colour.0;
     let delta_a = reference.1 - colour.1;
     let delta_b = reference.2 - colour.2;
     let c_1 = reference.1.hypot(reference.2);
     let c_2 = colour.1.hypot(colour.2);
     let delta_c = c_1 - c_2;
-    let delta_h = (delta_a.powi(2) + delta_b.powi(2) - delta_c.powi(2)).sqrt();
+    // Rounding errors may make the radicand slightly negative.
+    let delta_h = (delta_a.powi(2) + delta_b.powi(2) - delta_c.powi(2))
+        .max(F::lit(0.0))
+        .sqrt();
 
-    let l = delta_l / ksub.l;
-    let c = delta_c / (1.0 + ksub.c * c_1);
-    let h = delta_h / (1.0 + ksub.h * c_1);
+    let c_w = weight(c_1, c_2);
 
-    (l * l + c * c + h * h).sqrt()
+    let k = |k: f32| F::lit(f64::from(k));
+    let one = F::lit(1.0);
+    let l = delta_l / k(ksub.l);
+    let c = delta_c / (one + k(ksub.c) * c_w);
+    let h = delta_h / (one + k(ksub.h) * c_w);
+
+    (l, c, h)
+}
+
+/// Returns a symmetric variant of the CIE94 colour difference between two
+/// L\*a\*b\* colours using specified `k` parameters.
+///
+/// [`diff`] uses chroma of the reference colour in the chroma and hue
+/// weighting functions which makes the result depend on the order of the
+/// arguments.  This function uses geometric mean of the chroma of both
+/// colours instead, i.e. `sqrt(C₁·C₂)`, so the result is symmetric by
+/// construction (bit for bit, not merely up to rounding errors).  This is
+/// useful when there is no natural distinction between reference and sample,
+/// for example when clustering colours.
+///
+/// Note that the result is in general different from both `diff(a, b)` and
+/// `diff(b, a)` though for small differences it lies close to them.
+///
+/// ## Example
+///
+/// ```
+/// use empfindung::cie94;
+///
+/// let colour_1 = (38.972, 58.991, 37.138);
+/// let colour_2 = (54.528, 42.416, 54.497);
+/// let params = cie94::Params::graphic();
+/// let delta_e = cie94::diff_symmetric(colour_1, colour_2, params);
+/// assert_eq!(delta_e, cie94::diff_symmetric(colour_2, colour_1, params));
+/// approx::assert_abs_diff_eq!(19.4997, delta_e, epsilon = 0.001);
+/// ```
+pub fn diff_symmetric(
+    colour_1: impl crate::ToLab,
+    colour_2: impl crate::ToLab,
+    ksub: Params,
+) -> f32 {
+    diff_squared_with_chroma(
+        colour_1.to_lab(),
+        colour_2.to_lab(),
+        ksub,
+        |c_1, c_2| (c_1 * c_2).sqrt(),
+    )
+    .sqrt()
 }
 
 /// Returns the CIE94 colour difference between two sRGB colours using custom
@@ -169,8 +522,8 @@ fn diff_impl(
 ///     &reference, &colour, cie94::Params::graphic());
 /// approx::assert_abs_diff_eq!(50.87644, delta_e, epsilon = 0.001);
 /// ```
-#[cfg(feature = "lab")]
-#[deprecated(note = "Use cie94::diff() with rgb::RGB8 argument")]
+#[cfg(all(feature = "lab", not(feature = "no-deprecated")))]
+#[deprecated(note = "Use cie94::diff() with [u8; 3] or rgb::RGB8 argument")]
 pub fn diff_rgb(reference: &[u8; 3], colour: &[u8; 3], ksub: Params) -> f32 {
     diff(
         lab::Lab::from_rgb(reference),
@@ -194,8 +547,51 @@ mod tests {
         crate::testutil::do_test_zero(|a, b| super::diff(a, b, ksub))
     }
 
+    #[test]
+    fn test_const_presets() {
+        const PRESETS: [(&str, super::Params); 2] = [
+            ("graphic", super::Params::graphic()),
+            ("textiles", super::Params::TEXTILES),
+        ];
+        assert_eq!(super::Params::GRAPHIC, PRESETS[0].1);
+        assert_eq!(super::Params::textiles(), PRESETS[1].1);
+    }
+
+    #[test]
+    fn test_params_lerp() {
+        use super::Params;
+
+        let (graphic, textiles) = (Params::GRAPHIC, Params::TEXTILES);
+        assert_eq!(graphic, Params::lerp(graphic, textiles, 0.0));
+        assert_eq!(textiles, Params::lerp(graphic, textiles, 1.0));
+        assert_eq!(
+            Params {
+                l: 3.0,
+                c: 0.093,
+                h: 0.029
+            },
+            graphic + textiles
+        );
+        assert_eq!(
+            Params {
+                l: 2.0,
+                c: 0.09,
+                h: 0.03
+            },
+            graphic * 2.0
+        );
+    }
+
+    #[test]
+    #[cfg(debug_assertions)]
+    #[should_panic]
+    fn test_params_zero_l() {
+        use super::Params;
+        let _ = Params::GRAPHIC + Params::GRAPHIC * -1.0;
+    }
+
     #[rustfmt::skip]
-    static TESTS: [(f32, (f32, f32, f32), (f32, f32, f32)); 34] = [
+    static TESTS: [crate::testutil::TestCase; 34] = [
         (100.0,   (100.0,     0.0050,  -0.0100), ( 0.0000,   0.0000,   0.0000)),
         ( 1.3950, (50.0000,   2.6772, -79.7751), (50.0000,   0.0000, -82.7485)),
         ( 1.9341, (50.0000,   3.1571, -77.2803), (50.0000,   0.0000, -82.7485)),
@@ -232,9 +628,80 @@ mod tests {
         ( 1.3065, ( 2.0776,   0.0795,  -1.1350), ( 0.9033,  -0.0636,  -0.5514)),
     ];
 
+    #[test]
+    fn test_within() {
+        let ksub = super::Params::textiles();
+        crate::testutil::do_test_within(
+            &TESTS,
+            |a, b| super::diff(a, b, ksub),
+            |a, b, threshold| super::within(a, b, ksub, threshold),
+        );
+    }
+
+    #[test]
+    fn test_diff_squared() {
+        let ksub = super::Params::textiles();
+        crate::testutil::do_test_squared(
+            &TESTS,
+            |a, b| super::diff(a, b, ksub),
+            |a, b| super::diff_squared(a, b, ksub),
+        );
+    }
+
     #[test]
     fn test_difference() {
         let diff = |a, b| super::diff(a, b, super::Params::default());
         crate::testutil::do_test_difference(&TESTS, diff);
     }
+
+    #[test]
+    fn test_diff_components() {
+        for ksub in [super::Params::graphic(), super::Params::textiles()] {
+            for test in TESTS.iter() {
+                let want = super::diff(test.1, test.2, ksub);
+                let components = super::diff_components(test.1, test.2, ksub);
+                assert_eq!(0.0, components.rotation);
+                assert_eq!(want, components.delta_e());
+            }
+        }
+    }
+
+    #[test]
+    fn test_diff_fn() {
+        let diff = super::diff_fn(super::Params::default());
+        crate::testutil::do_test_difference(&TESTS, diff);
+    }
+
+    #[test]
+    fn test_diff_symmetric() {
+        for ksub in [super::Params::graphic(), super::Params::textiles()] {
+            let diff = |a, b| super::diff_symmetric(a, b, ksub);
+            crate::testutil::do_test_zero(diff);
+            crate::testutil::do_test_symmetric(diff);
+        }
+
+        // For colours of equal chroma, the result matches the regular one.
+        let (a, b) = ((50.0, 30.0, 40.0), (60.0, 40.0, 30.0));
+        let ksub = super::Params::graphic();
+        let want = super::diff(a, b, ksub);
+        let got = super::diff_symmetric(a, b, ksub);
+        approx::assert_abs_diff_eq!(want, got, epsilon = 0.0001);
+    }
+
+    #[test]
+    fn test_diff_f64() {
+        let diff = |a, b| super::diff_f64(a, b, super::Params::default());
+        crate::testutil::do_test_difference_f64(&TESTS, diff);
+    }
+
+    #[test]
+    fn test_delta_h_rounding() {
+        // Colours of the same hue for which rounding errors make
+        // Δa² + Δb² − ΔC² slightly negative.
+        let reference = (50.0, 1.0, 0.5);
+        let colour = (50.0, 1.00001, 0.500005);
+        let delta_e = super::diff(reference, colour, super::Params::graphic());
+        assert!(delta_e.is_finite());
+        approx::assert_abs_diff_eq!(0.0, delta_e, epsilon = 0.0001);
+    }
 }
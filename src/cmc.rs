@@ -25,8 +25,21 @@
 //! `c`.  Commonly used pair of weights are 1:1 and 2:1.
 //!
 //! Note that the distance is not symmetrical, i.e. in general case `diff(a, b,
-//! ksub) != diff(b, a, ksub)`.  Prefer [`crate::cie00`] module if you needa
-//! proper metric.
+//! ksub) != diff(b, a, ksub)`.  Use [`crate::cie00`] module if you need
+//! a symmetric formula.  For a proper metric which also satisfies the
+//! triangle inequality, use [`crate::cie76`], [`crate::din99`] or
+//! [`crate::hyab`] modules.
+//!
+//! The lightness weighting function S_L of the formula is defined piecewise:
+//! it is constant for reference colours with L\* below 16 and grows with
+//! lightness above that.  The constant used by this implementation matches the
+//! value of the other piece at L\* = 16 so S_L is continuous but its slope
+//! changes abruptly at the threshold.  As a result, for dark reference colours
+//! lightness differences are weighted the same regardless of the exact
+//! lightness while just above L\* = 16 the weighting starts to change.  Use
+//! [`diff_smooth`] if the kink is undesirable.
+
+use crate::float::Float;
 
 /// Returns the CMC l:c colour difference between two L\*a\*b\* colours using
 /// specified `l` and `c` parameters.
@@ -76,14 +89,393 @@ pub fn diff(
     colour: impl crate::ToLab,
     lc: (f32, f32),
 ) -> f32 {
-    diff_impl(reference.to_lab(), colour.to_lab(), lc)
+    diff_with_params(reference, colour, Params::from(lc))
+}
+
+/// Returns the CMC l:c colour difference between two L\*a\*b\* colours using
+/// specified parameters.
+///
+/// This is the same as [`diff`] except that the parameters are given as
+/// a [`Params`] object.
+///
+/// ## Example
+///
+/// ```
+/// use empfindung::cmc;
+///
+/// let colour_1 = (38.972, 58.991, 37.138);
+/// let colour_2 = (54.528, 42.416, 54.497);
+///
+/// let params = cmc::Params::acceptability();
+/// let delta_e = cmc::diff_with_params(colour_1, colour_2, params);
+/// approx::assert_abs_diff_eq!(17.743946, delta_e, epsilon = 0.001);
+/// ```
+pub fn diff_with_params(
+    reference: impl crate::ToLab,
+    colour: impl crate::ToLab,
+    params: Params,
+) -> f32 {
+    diff_impl(reference.to_lab(), colour.to_lab(), params.into())
+}
+
+/// Returns whether the CMC l:c colour difference between two L\*a\*b\*
+/// colours using specified `l` and `c` parameters is at most `threshold`.
+///
+/// This is equivalent to `diff(reference, colour, lc) <= threshold`.
+///
+/// ## Example
+///
+/// ```
+/// use empfindung::cmc;
+///
+/// let reference = (38.972, 58.991, 37.138);
+/// let colour = (54.528, 42.416, 54.497);
+/// assert!(cmc::within(reference, colour, cmc::LC21, 18.0));
+/// assert!(!cmc::within(reference, colour, cmc::LC21, 17.0));
+/// ```
+pub fn within(
+    reference: impl crate::ToLab,
+    colour: impl crate::ToLab,
+    lc: (f32, f32),
+    threshold: f32,
+) -> bool {
+    diff(reference, colour, lc) <= threshold
+}
+
+/// Returns square of the CMC l:c colour difference between two L\*a\*b\*
+/// colours using specified `l` and `c` parameters.
+///
+/// Since the difference is never negative, squaring it keeps the ordering
+/// of colours the same as with [`diff`] and the result can be compared
+/// against a squared threshold.  This makes the function useful when only
+/// the ordering matters, e.g. when looking for the nearest colour.
+///
+/// ## Example
+///
+/// ```
+/// use empfindung::cmc;
+///
+/// let reference = (38.972, 58.991, 37.138);
+/// let colour = (54.528, 42.416, 54.497);
+/// let delta_e_squared = cmc::diff_squared(reference, colour, cmc::LC21);
+/// approx::assert_abs_diff_eq!(314.85, delta_e_squared, epsilon = 0.01);
+/// ```
+pub fn diff_squared(
+    reference: impl crate::ToLab,
+    colour: impl crate::ToLab,
+    lc: (f32, f32),
+) -> f32 {
+    let reference = reference.to_lab();
+    let s_l = get_s_l(reference.0);
+    diff_squared_impl(reference, colour.to_lab(), lc, s_l)
+}
+
+/// Calculates the CMC l:c colour difference between a reference colour and
+/// each colour in a slice using specified `l` and `c` parameters.
+///
+/// The reference colour is converted to L\*a\*b\* only once and results are
+/// stored in `out`.  They are bit-for-bit identical to calling [`diff`] in
+/// a loop.  See [`crate::batch::diff_many`].
+///
+/// # Panics
+///
+/// Panics if length of `colours` differs from length of `out`.
+///
+/// ## Example
+///
+/// ```
+/// use empfindung::cmc;
+///
+/// let reference = (50.0, 2.5, 0.0);
+/// let colours = [(73.0, 25.0, -18.0), (61.0, -5.0, 29.0)];
+/// let params = cmc::LC21;
+/// let mut out = [0.0; 2];
+/// cmc::diff_many(reference, &colours, params, &mut out);
+/// assert_eq!(cmc::diff(reference, colours[0], params), out[0]);
+/// assert_eq!(cmc::diff(reference, colours[1], params), out[1]);
+/// ```
+pub fn diff_many<C: crate::ToLab>(
+    reference: impl crate::ToLab,
+    colours: &[C],
+    lc: (f32, f32),
+    out: &mut [f32],
+) {
+    crate::batch::diff_many(reference, colours, diff_fn(lc), out)
+}
+
+/// Returns a function calculating the CMC l:c colour difference between two
+/// L\*a\*b\* colours using given `l` and `c` parameters.
+///
+/// The returned closure captures the parameters and operates on plain
+/// `(f32, f32, f32)` tuples.  As with [`diff`], the first argument is the
+/// reference colour.
+///
+/// ## Example
+///
+/// ```
+/// use empfindung::cmc;
+///
+/// let diff = cmc::diff_fn(cmc::LC21);
+/// let delta_e = diff((38.972, 58.991, 37.138), (54.528, 42.416, 54.497));
+/// approx::assert_abs_diff_eq!(17.743946, delta_e, epsilon = 0.001);
+/// ```
+pub fn diff_fn(
+    lc: (f32, f32),
+) -> impl Fn((f32, f32, f32), (f32, f32, f32)) -> f32 + Copy {
+    move |reference, colour| diff_impl(reference, colour, lc)
+}
+
+/// Returns the CMC l:c colour difference between two L\*a\*b\* colours using
+/// specified `l` and `c` parameters and calculating the final square root
+/// with given function.
+///
+/// This is the same as [`diff`] except that `sqrt` is used in place of
+/// `f32::sqrt` for the final square root of the sum of squared components.
+/// With an approximate function such as [`crate::fast_sqrt`] the result is
+/// slightly less accurate but on some targets cheaper to compute.
+///
+/// ## Example
+///
+/// ```
+/// use empfindung::cmc;
+///
+/// let reference = (38.972, 58.991, 37.138);
+/// let colour = (54.528, 42.416, 54.497);
+/// let delta_e = cmc::diff_with_sqrt(
+///     reference, colour, cmc::LC21, empfindung::fast_sqrt);
+/// approx::assert_abs_diff_eq!(17.743946, delta_e, epsilon = 0.05);
+/// ```
+pub fn diff_with_sqrt(
+    reference: impl crate::ToLab,
+    colour: impl crate::ToLab,
+    lc: (f32, f32),
+    sqrt: impl Fn(f32) -> f32,
+) -> f32 {
+    let reference = reference.to_lab();
+    let s_l = get_s_l(reference.0);
+    sqrt(diff_squared_impl(reference, colour.to_lab(), lc, s_l))
 }
 
-fn diff_impl(
-    reference: (f32, f32, f32),
-    colour: (f32, f32, f32),
+/// Returns the CMC l:c colour difference between two L\*a\*b\* colours using
+/// specified `l` and `c` parameters with lightness weighting smoothed around
+/// L\* = 16.
+///
+/// This is a variant of [`diff`] which avoids the abrupt change in behaviour
+/// of the lightness weighting function S_L at L\* = 16 (see [module
+/// documentation](self)).  Within 2 units of the threshold (i.e. for
+/// reference colours with L\* between 14 and 18) the function is replaced by
+/// a smooth curve joining the two pieces.  Outside of that band the result is
+/// the same as that of [`diff`].
+///
+/// Note that this is not part of the CMC l:c standard so results may differ
+/// slightly from those of other implementations.
+///
+/// ## Example
+///
+/// ```
+/// use empfindung::cmc;
+///
+/// let colour = (10.0, 5.0, 5.0);
+/// let dark = (16.0, 5.0, 5.0);
+/// let standard = cmc::diff(dark, colour, cmc::LC11);
+/// approx::assert_abs_diff_eq!(11.736, standard, epsilon = 0.001);
+/// let smooth = cmc::diff_smooth(dark, colour, cmc::LC11);
+/// approx::assert_abs_diff_eq!(11.457, smooth, epsilon = 0.001);
+///
+/// let light = (50.0, 5.0, 5.0);
+/// assert_eq!(cmc::diff(light, colour, cmc::LC11),
+///            cmc::diff_smooth(light, colour, cmc::LC11));
+/// ```
+pub fn diff_smooth(
+    reference: impl crate::ToLab,
+    colour: impl crate::ToLab,
     lc: (f32, f32),
 ) -> f32 {
+    let reference = reference.to_lab();
+    let s_l = get_s_l_smooth(reference.0);
+    diff_squared_impl(reference, colour.to_lab(), lc, s_l).sqrt()
+}
+
+/// Returns a symmetric variant of the CMC l:c colour difference between two
+/// L\*a\*b\* colours using specified `l` and `c` parameters.
+///
+/// [`diff`] uses lightness, chroma and hue of the reference colour in the
+/// weighting functions which makes the result depend on the order of the
+/// arguments.  This function instead uses geometric mean of chroma of both
+/// colours (like [`crate::cie94::diff_symmetric`]) and arithmetic means of
+/// the S_L and T weighting functions calculated for both colours so the
+/// result is symmetric by construction (bit for bit, not merely up to
+/// rounding errors).
+///
+/// Note that the result is in general different from both `diff(a, b, lc)`
+/// and `diff(b, a, lc)` though for small differences it lies close to them.
+///
+/// ## Example
+///
+/// ```
+/// use empfindung::cmc;
+///
+/// let colour_1 = (38.972, 58.991, 37.138);
+/// let colour_2 = (54.528, 42.416, 54.497);
+/// let delta_e = cmc::diff_symmetric(colour_1, colour_2, cmc::LC21);
+/// assert_eq!(delta_e, cmc::diff_symmetric(colour_2, colour_1, cmc::LC21));
+/// approx::assert_abs_diff_eq!(19.6032, delta_e, epsilon = 0.001);
+/// ```
+pub fn diff_symmetric(
+    colour_1: impl crate::ToLab,
+    colour_2: impl crate::ToLab,
+    lc: (f32, f32),
+) -> f32 {
+    let (colour_1, colour_2) = (colour_1.to_lab(), colour_2.to_lab());
+    let s_l = (get_s_l(colour_1.0) + get_s_l(colour_2.0)) * 0.5;
+    let t =
+        (get_t(colour_1.1, colour_1.2) + get_t(colour_2.1, colour_2.2)) * 0.5;
+    let (l, c, h) = get_terms_with(colour_1, colour_2, lc, |c_1, c_2| {
+        (s_l, (c_1 * c_2).sqrt(), t)
+    });
+    (l * l + c * c + h * h).sqrt()
+}
+
+/// Returns the weighted lightness, chroma and hue terms of the CMC l:c colour
+/// difference between two L\*a\*b\* colours using specified `l` and `c`
+/// parameters.
+///
+/// The lightness, chroma and hue terms are ΔL\*/(l·S_L), ΔC\*/(c·S_C) and
+/// ΔH\*/S_H respectively.  Since the formula has no rotation term,
+/// `rotation` is always zero.  ΔH\* is non-negative so the hue term is
+/// never negative.  [`Components::delta_e`](crate::Components::delta_e) of
+/// the result equals [`diff`].
+///
+/// ## Example
+///
+/// ```
+/// use empfindung::cmc;
+///
+/// let reference = (38.972, 58.991, 37.138);
+/// let colour = (54.528, 42.416, 54.497);
+///
+/// let components = cmc::diff_components(reference, colour, cmc::LC21);
+/// approx::assert_abs_diff_eq!(-8.22, components.lightness, epsilon = 0.01);
+/// approx::assert_abs_diff_eq!(
+///     cmc::diff(reference, colour, cmc::LC21), components.delta_e());
+/// ```
+pub fn diff_components(
+    reference: impl crate::ToLab,
+    colour: impl crate::ToLab,
+    lc: (f32, f32),
+) -> crate::Components {
+    let reference = reference.to_lab();
+    let s_l = get_s_l(reference.0);
+    let (lightness, chroma, hue) =
+        get_terms(reference, colour.to_lab(), lc, s_l);
+    crate::Components {
+        lightness,
+        chroma,
+        hue,
+        rotation: 0.0,
+    }
+}
+
+/// Returns the CMC l:c colour difference between two L\*a\*b\* colours using
+/// specified `l` and `c` parameters calculated with double precision.
+///
+/// This is the same as [`diff`] except that the colours are converted with
+/// [`crate::ToLab64`] and the computation is done with `f64` numbers.
+///
+/// ## Example
+///
+/// ```
+/// use empfindung::cmc;
+///
+/// let reference = (38.972f64, 58.991, 37.138);
+/// let colour = (54.528f64, 42.416, 54.497);
+/// let delta_e = cmc::diff_f64(reference, colour, cmc::LC21);
+/// approx::assert_abs_diff_eq!(17.743946, delta_e, epsilon = 0.00001);
+/// ```
+pub fn diff_f64(
+    reference: impl crate::ToLab64,
+    colour: impl crate::ToLab64,
+    lc: (f32, f32),
+) -> f64 {
+    diff_impl(reference.to_lab64(), colour.to_lab64(), lc)
+}
+
+fn diff_impl<F: Float>(
+    reference: (F, F, F),
+    colour: (F, F, F),
+    lc: (f32, f32),
+) -> F {
+    let s_l = get_s_l(reference.0);
+    diff_squared_impl(reference, colour, lc, s_l).sqrt()
+}
+
+/// Value of S_L for L\* below 16.  This equals `get_s_l(16.0)` making the
+/// function continuous.
+const S_L_DARK: f64 = 1639.0 / 3206.0;
+
+/// Returns S_L lightness weighting for reference colour with given lightness.
+fn get_s_l<F: Float>(l: F) -> F {
+    if l < F::lit(16.0) {
+        F::lit(S_L_DARK)
+    } else {
+        (F::lit(0.040975) * l) / (F::lit(1.0) + F::lit(0.01765) * l)
+    }
+}
+
+/// Returns S_L lightness weighting with the change at L\* = 16 smoothed.
+///
+/// Within the `14..18` band, replaces the function with a cubic Hermite
+/// spline matching values and slopes of the constant piece at the start and
+/// of the rational piece at the end of the band.  The result is continuously
+/// differentiable and non-decreasing.
+fn get_s_l_smooth(l: f32) -> f32 {
+    const BAND: f32 = 2.0;
+    if l <= 16.0 - BAND || l >= 16.0 + BAND {
+        return get_s_l(l);
+    }
+    let end = 16.0 + BAND;
+    let tmp = 1.0 + 0.01765 * end;
+    let end_value = (0.040975 * end) / tmp;
+    let end_slope = 0.040975 / (tmp * tmp) * (2.0 * BAND);
+    let t = (l - (16.0 - BAND)) / (2.0 * BAND);
+    let (t2, t3) = (t * t, t * t * t);
+    (2.0 * t3 - 3.0 * t2 + 1.0) * S_L_DARK as f32 +
+        (3.0 * t2 - 2.0 * t3) * end_value +
+        (t3 - t2) * end_slope
+}
+
+fn diff_squared_impl<F: Float>(
+    reference: (F, F, F),
+    colour: (F, F, F),
+    lc: (f32, f32),
+    s_l: F,
+) -> F {
+    let (l, c, h) = get_terms(reference, colour, lc, s_l);
+    l * l + c * c + h * h
+}
+
+/// Returns the lightness, chroma and hue terms of the CMC l:c formula (i.e.
+/// ΔL\*/(l·S_L), ΔC\*/(c·S_C) and ΔH\*/S_H) given S_L lightness weighting.
+fn get_terms<F: Float>(
+    reference: (F, F, F),
+    colour: (F, F, F),
+    lc: (f32, f32),
+    s_l: F,
+) -> (F, F, F) {
+    get_terms_with(reference, colour, lc, |c_1, _| {
+        (s_l, c_1, get_t(reference.1, reference.2))
+    })
+}
+
+/// Returns the lightness, chroma and hue terms of the CMC l:c formula with S_L
+/// lightness weighting, chroma used in S_C and F and the T hue weighting
+/// calculated by `weights` from chroma of both colours.
+fn get_terms_with<F: Float>(
+    reference: (F, F, F),
+    colour: (F, F, F),
+    lc: (f32, f32),
+    weights: impl Fn(F, F) -> (F, F, F),
+) -> (F, F, F) {
     let delta_l = reference.0 - colour.0;
     let delta_a = reference.1 - colour.1;
     let delta_b = reference.2 - colour.2;
@@ -91,25 +483,25 @@ fn diff_impl(
     let c_1 = reference.1.hypot(reference.2);
     let c_2 = colour.1.hypot(colour.2);
     let delta_c = c_1 - c_2;
-    let delta_h = (delta_a.powi(2) + delta_b.powi(2) - delta_c.powi(2)).sqrt();
+    // Rounding errors may make the radicand slightly negative.
+    let delta_h = (delta_a.powi(2) + delta_b.powi(2) - delta_c.powi(2))
+        .max(F::lit(0.0))
+        .sqrt();
 
-    let s_l = if reference.0 < 16.0 {
-        const S: f64 = 1639.0f64 / 3206.0f64;
-        S as f32
-    } else {
-        (0.040975 * reference.0) / (1.0 + 0.01765 * reference.0)
-    };
-    let s_c = ((0.0638 * c_1) / (1.0 + (0.0131 * c_1))) + 0.638;
+    let (s_l, c_w, t) = weights(c_1, c_2);
+    let one = F::lit(1.0);
+    let s_c =
+        ((F::lit(0.0638) * c_w) / (one + (F::lit(0.0131) * c_w))) +
+        F::lit(0.638);
 
-    let tmp = c_1.powi(4);
-    let f = (tmp / (tmp + 1900.0)).sqrt();
-    let t = get_t(reference.1, reference.2);
-    let s_h = s_c * (f * t + 1.0 - f);
+    let tmp = c_w.powi(4);
+    let f = (tmp / (tmp + F::lit(1900.0))).sqrt();
+    let s_h = s_c * (f * t + one - f);
 
-    let l = delta_l / (lc.0 * s_l);
-    let c = delta_c / (lc.1 * s_c);
+    let l = delta_l / (F::lit(f64::from(lc.0)) * s_l);
+    let c = delta_c / (F::lit(f64::from(lc.1)) * s_c);
     let h = delta_h / s_h;
-    (l * l + c * c + h * h).sqrt()
+    (l, c, h)
 }
 
 /// Returns the CMC l:c colour difference between two sRGB colours using
@@ -129,8 +521,8 @@ fn diff_impl(
 /// let delta_e = cmc::diff_rgb(&colour_1, &colour_2, (2.0, 1.0));
 /// approx::assert_abs_diff_eq!(63.303917, delta_e, epsilon = 0.001);
 /// ```
-#[cfg(feature = "lab")]
-#[deprecated(note = "Use cmc::diff() with rgb::RGB8 argument")]
+#[cfg(all(feature = "lab", not(feature = "no-deprecated")))]
+#[deprecated(note = "Use cmc::diff() with [u8; 3] or rgb::RGB8 argument")]
 pub fn diff_rgb(reference: &[u8; 3], colour: &[u8; 3], lc: (f32, f32)) -> f32 {
     diff(
         lab::Lab::from_rgb(reference),
@@ -144,23 +536,59 @@ pub const LC11: (f32, f32) = (1.0, 1.0);
 /// ΔE CMC 2:1 parameters.
 pub const LC21: (f32, f32) = (2.0, 1.0);
 
+/// `l` and `c` parameters adjusting what effect lightness and chroma
+/// difference will have on the calculated distance.
+///
+/// This is a named alternative to the `(l, c)` tuples accepted by [`diff`]
+/// and other functions in this module.  The type converts from and into such
+/// tuples.  Commonly used parameters are available through
+/// [`Params::acceptability`] and [`Params::perceptibility`] constructors.
+#[derive(Clone, Copy, PartialEq, PartialOrd, Debug)]
+pub struct Params {
+    /// The l parameter, i.e. lightness weight.  Lightness difference is
+    /// divided by it so larger values make lightness differences matter
+    /// less.  Usually 2 for acceptability and 1 for perceptibility.
+    pub l: f32,
+    /// The c parameter, i.e. chroma weight.  Chroma difference is divided by
+    /// it.  Usually 1 for both acceptability and perceptibility.
+    pub c: f32,
+}
 
-fn get_t(a: f32, b: f32) -> f32 {
-    use std::f64::consts::{PI, TAU};
+impl Params {
+    /// Returns 2:1 parameters commonly used to judge acceptability of
+    /// a colour match.  Equivalent to [`LC21`].
+    pub const fn acceptability() -> Self { Self { l: 2.0, c: 1.0 } }
+
+    /// Returns 1:1 parameters commonly used to judge perceptibility of
+    /// a colour difference.  Equivalent to [`LC11`].
+    pub const fn perceptibility() -> Self { Self { l: 1.0, c: 1.0 } }
+}
+
+impl From<(f32, f32)> for Params {
+    fn from((l, c): (f32, f32)) -> Self { Self { l, c } }
+}
+
+impl From<Params> for (f32, f32) {
+    fn from(params: Params) -> Self { (params.l, params.c) }
+}
+
+
+fn get_t<F: Float>(a: F, b: F) -> F {
+    use core::f64::consts::{PI, TAU};
 
     // (164 - 360) / 360 = -196 / 360 = -49 / 90
-    const START: f32 = (-PI * 49.0 / 45.0) as f32;
+    let start = F::lit(-PI * 49.0 / 45.0);
     // (345 - 360) / 360 = -15 / 360 = -1 / 24
-    const END: f32 = (-TAU / 24.0) as f32;
+    let end = F::lit(-TAU / 24.0);
 
     let h = b.atan2(a);
-    let ft = |m: f32, d: f32| (m * (h + d).cos()).abs();
-    if (START..=END).contains(&h) {
+    let ft = |m: f64, d: f64| (F::lit(m) * (h + F::lit(d)).cos()).abs();
+    if start <= h && h <= end {
         // 168 / 360 = 7 / 15
-        0.56 + ft(0.2, (TAU * 7.0 / 15.0) as f32)
+        F::lit(0.56) + ft(0.2, TAU * 7.0 / 15.0)
     } else {
         // 35 / 128 = 7 / 36
-        0.36 + ft(0.4, (PI * 7.0 / 36.0) as f32)
+        F::lit(0.36) + ft(0.4, PI * 7.0 / 36.0)
     }
 }
 
@@ -180,7 +608,7 @@ mod tests {
     fn test_zero_12() { do_test_zero(1.0, 2.0); }
 
     #[rustfmt::skip]
-    static TESTS: [(f32, (f32, f32, f32), (f32, f32, f32)); 34] = [
+    static TESTS: [crate::testutil::TestCase; 34] = [
         (67.4802, (100.0,     0.0050,  -0.0100), ( 0.0000,   0.0000,   0.0000)),
         ( 1.7387, (50.0000,   2.6772, -79.7751), (50.0000,   0.0000, -82.7485)),
         ( 2.4966, (50.0000,   3.1571, -77.2803), (50.0000,   0.0000, -82.7485)),
@@ -217,9 +645,112 @@ mod tests {
         ( 2.4484, ( 2.0776,   0.0795,  -1.1350), ( 0.9033,  -0.0636,  -0.5514)),
     ];
 
+    #[test]
+    fn test_within() {
+        crate::testutil::do_test_within(
+            &TESTS,
+            |a, b| super::diff(a, b, super::LC21),
+            |a, b, threshold| super::within(a, b, super::LC21, threshold),
+        );
+    }
+
+    #[test]
+    fn test_diff_squared() {
+        crate::testutil::do_test_squared(
+            &TESTS,
+            |a, b| super::diff(a, b, super::LC21),
+            |a, b| super::diff_squared(a, b, super::LC21),
+        );
+    }
+
     #[test]
     fn test_difference() {
         let diff = |a, b| super::diff(a, b, (1.0, 1.0));
         crate::testutil::do_test_difference(&TESTS, diff);
     }
+
+    #[test]
+    fn test_diff_smooth() {
+        // All reference colours in the test data are outside of the band
+        // where smoothing happens.
+        for (_, reference, colour) in TESTS.iter() {
+            assert_eq!(
+                super::diff(reference, colour, super::LC21),
+                super::diff_smooth(reference, colour, super::LC21)
+            );
+        }
+    }
+
+    #[test]
+    fn test_s_l_smooth() {
+        use super::{get_s_l, get_s_l_smooth};
+
+        let mut prev = get_s_l_smooth(10.0);
+        let mut prev_slope = 0.0;
+        for i in 1..=1000 {
+            let l = 10.0 + i as f32 * 0.01;
+            let s_l = get_s_l_smooth(l);
+            if !(14.0..=18.0).contains(&l) {
+                assert_eq!(get_s_l(l), s_l, "l: {}", l);
+            }
+            // Non-decreasing with slope changing gradually.
+            let slope = (s_l - prev) / 0.01;
+            assert!(slope >= 0.0, "l: {}, slope: {}", l, slope);
+            assert!((slope - prev_slope).abs() < 0.001, "l: {}", l);
+            prev = s_l;
+            prev_slope = slope;
+        }
+    }
+
+    #[test]
+    fn test_params() {
+        use super::Params;
+
+        assert_eq!(super::LC21, Params::acceptability().into());
+        assert_eq!(super::LC11, Params::perceptibility().into());
+        assert_eq!(Params::acceptability(), Params::from(super::LC21));
+        for test in TESTS.iter() {
+            for lc in [super::LC11, super::LC21, (1.5, 0.5)] {
+                assert_eq!(
+                    super::diff(test.1, test.2, lc),
+                    super::diff_with_params(test.1, test.2, lc.into())
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_diff_components() {
+        for lc in [super::LC11, super::LC21] {
+            for test in TESTS.iter() {
+                let want = super::diff(test.1, test.2, lc);
+                let components = super::diff_components(test.1, test.2, lc);
+                assert_eq!(0.0, components.rotation);
+                assert_eq!(want, components.delta_e());
+            }
+        }
+    }
+
+    #[test]
+    fn test_diff_fn() {
+        let diff = super::diff_fn(super::LC11);
+        crate::testutil::do_test_difference(&TESTS, diff);
+    }
+
+    #[test]
+    fn test_diff_f64() {
+        let diff = |a, b| super::diff_f64(a, b, super::LC11);
+        crate::testutil::do_test_difference_f64(&TESTS, diff);
+    }
+
+    #[test]
+    fn test_delta_h_rounding() {
+        // Colours of the same hue for which rounding errors make
+        // Δa² + Δb² − ΔC² slightly negative.
+        let reference = (50.0, 1.0, 0.5);
+        let colour = (50.0, 1.00001, 0.500005);
+        let delta_e = super::diff(reference, colour, super::LC11);
+        assert!(delta_e.is_finite());
+        approx::assert_abs_diff_eq!(0.0, delta_e, epsilon = 0.0001);
+    }
 }
@@ -0,0 +1,94 @@
+// Sort key for colour differences.
+// Copyright (c) 2026 Michał Nazarewicz <mina86@mina86.com>
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in
+// all copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+
+/// Colour difference usable as a sort key.
+///
+/// `f32` does not implement [`Ord`] which means a colour difference cannot be
+/// directly used with methods such as [`slice::sort_by_key`].  This wrapper
+/// implements total ordering (see [`f32::total_cmp`]) so that it can.
+/// Differences are ordered numerically with NaN (which is what a `diff`
+/// function returns if any of the inputs is NaN) ordered after all other
+/// values regardless of its sign.  Equality is defined consistently with the
+/// ordering so in particular all NaN keys are equal to each other.
+///
+/// ## Example
+///
+/// ```
+/// use empfindung::{cie00, DiffKey};
+///
+/// let reference = (50.0, 0.0, 0.0);
+/// let mut colours = [(80.0, 0.0, 0.0), (50.0, 5.0, 5.0), (52.0, 0.0, 0.0)];
+/// colours.sort_by_key(|c| DiffKey(cie00::diff(reference, c)));
+/// assert_eq!([(52.0, 0.0, 0.0), (50.0, 5.0, 5.0), (80.0, 0.0, 0.0)],
+///            colours);
+/// ```
+#[derive(Clone, Copy, Debug, Default)]
+pub struct DiffKey(pub f32);
+
+impl From<f32> for DiffKey {
+    fn from(delta_e: f32) -> Self { Self(delta_e) }
+}
+
+impl PartialEq for DiffKey {
+    fn eq(&self, other: &Self) -> bool { self.cmp(other).is_eq() }
+}
+
+impl Eq for DiffKey {}
+
+impl PartialOrd for DiffKey {
+    fn partial_cmp(&self, other: &Self) -> Option<core::cmp::Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for DiffKey {
+    fn cmp(&self, other: &Self) -> core::cmp::Ordering {
+        // total_cmp orders NaNs with sign bit set before all other values.
+        // Depending on the platform, NaN produced by an invalid operation may
+        // have the sign bit set so compare NaN-ness explicitly.
+        match (self.0.is_nan(), other.0.is_nan()) {
+            (false, false) => self.0.total_cmp(&other.0),
+            (lhs, rhs) => lhs.cmp(&rhs),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_diff_key() {
+        let mut keys =
+            [f32::NAN, 2.0, 0.0, f32::INFINITY, 1.0].map(DiffKey::from);
+        keys.sort();
+        let got = keys.map(|key| key.0);
+        assert_eq!([0.0, 1.0, 2.0, f32::INFINITY], got[..4]);
+        assert!(got[4].is_nan());
+        assert_eq!(DiffKey(f32::NAN), DiffKey(f32::NAN));
+        assert_eq!(DiffKey(f32::NAN), DiffKey(-f32::NAN));
+
+        let mut keys = [1.0, -f32::NAN, 0.5, f32::NAN, 3.0, -f32::NAN, 2.0];
+        keys.sort_by_key(|delta_e| DiffKey(*delta_e));
+        assert_eq!([0.5, 1.0, 2.0, 3.0], keys[..4]);
+        assert!(keys[4..].iter().all(|delta_e| delta_e.is_nan()));
+    }
+}
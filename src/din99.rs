@@ -0,0 +1,340 @@
+// DIN99 colour difference implementation.
+// Copyright (c) 2026 Michał Nazarewicz <mina86@mina86.com>
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in
+// all copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+
+//! Implementation of the DIN99 colour distance algorithms.
+//!
+//! DIN99 (DIN 6176) defines a colour space derived from L\*a\*b\* by
+//! logarithmic compression of lightness and chroma and rotation and scaling of
+//! the a\*b\* plane.  The colour difference is the Euclidean distance in that
+//! space and as such it is a metric in the mathematical sense.
+//!
+//! The module implements both the original 1999 formula (see [`diff_din99`])
+//! and the optimised DIN99o variant (see [`diff`] and [`diff_with_params`])
+//! which uses different rotation angle and compression constants and gives
+//! results closer to CIEDE2000.
+
+use crate::float::{Float, FloatExt};
+
+/// Parametric factors adjusting what effect lightness and chroma/hue
+/// difference have on the calculated distance.
+///
+/// To construct the object, either create it directly by providing your own
+/// choice of parameters, or use [`Params::reference`] or [`Params::textiles`]
+/// methods.  The default values, i.e. what [`Params::default`] returns, are
+/// the reference conditions defined by the standard.
+#[derive(Clone, Copy, PartialEq, PartialOrd, Debug)]
+pub struct Params {
+    /// The k_E parameter dividing lightness as well as chroma.
+    pub k_e: f32,
+    /// The k_CH parameter dividing chroma.
+    pub k_ch: f32,
+}
+
+impl Default for Params {
+    /// Returns parameters for the reference conditions.
+    fn default() -> Self { Self::reference() }
+}
+
+impl Params {
+    /// Returns parameters for the reference conditions, i.e. with both
+    /// factors equal one.
+    pub const fn reference() -> Self { Self { k_e: 1.0, k_ch: 1.0 } }
+
+    /// Returns parameters weighted for textiles.
+    pub const fn textiles() -> Self { Self { k_e: 2.0, k_ch: 0.5 } }
+}
+
+/// Constants defining a variant of the DIN99 transformation.
+struct Constants {
+    /// Scale and coefficient of the logarithmic lightness compression.
+    l_scale: f32,
+    l_coeff: f32,
+    /// Rotation of the a\*b\* plane in radians and scale of the f axis.
+    angle: f32,
+    f_scale: f32,
+    /// Coefficient and divisor of the logarithmic chroma compression.
+    c_coeff: f32,
+    c_div: f32,
+    /// Angle in radians added to the hue after chroma compression.
+    hue_offset: f32,
+}
+
+/// Constants of the original DIN99 formula.
+const DIN99: Constants = Constants {
+    l_scale: 105.51,
+    l_coeff: 0.0158,
+    angle: 16.0 * core::f32::consts::PI / 180.0,
+    f_scale: 0.7,
+    c_coeff: 0.045,
+    c_div: 0.045,
+    hue_offset: 0.0,
+};
+
+/// Constants of the DIN99o formula.
+const DIN99O: Constants = Constants {
+    l_scale: 303.67,
+    l_coeff: 0.0039,
+    angle: 26.0 * core::f32::consts::PI / 180.0,
+    f_scale: 0.83,
+    c_coeff: 0.075,
+    c_div: 0.0435,
+    hue_offset: 26.0 * core::f32::consts::PI / 180.0,
+};
+
+/// Returns the DIN99o colour difference between two L\*a\*b\* colours using
+/// the reference parameters.
+///
+/// ## Example
+///
+/// ```
+/// use empfindung::din99;
+///
+/// let colour_1 = (38.972, 58.991, 37.138);
+/// let colour_2 = (54.528, 42.416, 54.497);
+/// let delta_e = din99::diff(colour_1, colour_2);
+/// approx::assert_abs_diff_eq!(19.8681, delta_e, epsilon = 0.001);
+/// ```
+pub fn diff(colour_1: impl crate::ToLab, colour_2: impl crate::ToLab) -> f32 {
+    diff_with_params(colour_1, colour_2, Params::default())
+}
+
+/// Returns the DIN99o colour difference between two L\*a\*b\* colours using
+/// specified parametric factors.
+///
+/// ## Example
+///
+/// ```
+/// use empfindung::din99;
+///
+/// let colour_1 = (38.972, 58.991, 37.138);
+/// let colour_2 = (54.528, 42.416, 54.497);
+/// let delta_e =
+///     din99::diff_with_params(colour_1, colour_2, din99::Params::textiles());
+/// approx::assert_abs_diff_eq!(14.5794, delta_e, epsilon = 0.001);
+/// ```
+pub fn diff_with_params(
+    colour_1: impl crate::ToLab,
+    colour_2: impl crate::ToLab,
+    params: Params,
+) -> f32 {
+    diff_impl(colour_1.to_lab(), colour_2.to_lab(), &DIN99O, params)
+}
+
+/// Returns a function calculating the DIN99o colour difference between two
+/// L\*a\*b\* colours using given parametric factors.
+///
+/// The returned closure captures the parameters and operates on plain
+/// `(f32, f32, f32)` tuples.
+///
+/// ## Example
+///
+/// ```
+/// use empfindung::din99;
+///
+/// let diff = din99::diff_fn(din99::Params::default());
+/// let delta_e = diff((38.972, 58.991, 37.138), (54.528, 42.416, 54.497));
+/// approx::assert_abs_diff_eq!(19.8681, delta_e, epsilon = 0.001);
+/// ```
+pub fn diff_fn(
+    params: Params,
+) -> impl Fn((f32, f32, f32), (f32, f32, f32)) -> f32 + Copy {
+    move |colour_1, colour_2| diff_impl(colour_1, colour_2, &DIN99O, params)
+}
+
+/// Returns the original (1999) DIN99 colour difference between two L\*a\*b\*
+/// colours using specified parametric factors.
+///
+/// ## Example
+///
+/// ```
+/// use empfindung::din99;
+///
+/// let colour_1 = (38.972, 58.991, 37.138);
+/// let colour_2 = (54.528, 42.416, 54.497);
+/// let delta_e =
+///     din99::diff_din99(colour_1, colour_2, din99::Params::default());
+/// approx::assert_abs_diff_eq!(17.1284, delta_e, epsilon = 0.001);
+/// ```
+pub fn diff_din99(
+    colour_1: impl crate::ToLab,
+    colour_2: impl crate::ToLab,
+    params: Params,
+) -> f32 {
+    diff_impl(colour_1.to_lab(), colour_2.to_lab(), &DIN99, params)
+}
+
+fn diff_impl(
+    colour_1: (f32, f32, f32),
+    colour_2: (f32, f32, f32),
+    constants: &Constants,
+    params: Params,
+) -> f32 {
+    let colour_1 = transform(colour_1, constants, params);
+    let colour_2 = transform(colour_2, constants, params);
+    let dl = colour_1.0 - colour_2.0;
+    let da = colour_1.1 - colour_2.1;
+    let db = colour_1.2 - colour_2.2;
+    Float::sqrt(dl * dl + da * da + db * db)
+}
+
+/// Converts L\*a\*b\* colour into DIN99 space defined by given constants.
+fn transform(
+    (l, a, b): (f32, f32, f32),
+    constants: &Constants,
+    params: Params,
+) -> (f32, f32, f32) {
+    let l =
+        constants.l_scale * FloatExt::ln_1p(constants.l_coeff * l) / params.k_e;
+    let (sin, cos) = FloatExt::sin_cos(constants.angle);
+    let e = a * cos + b * sin;
+    let f = constants.f_scale * (b * cos - a * sin);
+    let g = Float::hypot(e, f);
+    let c = FloatExt::ln_1p(constants.c_coeff * g) /
+        (constants.c_div * params.k_ch * params.k_e);
+    let (sin, cos) =
+        FloatExt::sin_cos(Float::atan2(f, e) + constants.hue_offset);
+    (l, c * cos, c * sin)
+}
+
+#[cfg(test)]
+mod tests {
+    // Regression values for the three variants calculated in double
+    // precision.  The columns are: DIN99o with reference parameters, DIN99o
+    // with textiles parameters and original DIN99 with reference parameters.
+    type Lab = (f32, f32, f32);
+
+    #[rustfmt::skip]
+    static TESTS: [((f32, f32, f32), Lab, Lab); 11] = [
+        (( 99.9997, 49.9998, 100.0013), (100.0000,   0.0050,  -0.0100), (  0.0000,   0.0000,   0.0000)),
+        ((  1.7678,  1.7678,   1.4721), ( 50.0000,   2.6772, -79.7751), ( 50.0000,   0.0000, -82.7485)),
+        ((  2.9960,  2.9960,   1.5379), ( 50.0000,   0.0000,   0.0000), ( 50.0000,  -1.0000,   2.0000)),
+        ((  7.6516,  7.6516,   4.6323), ( 50.0000,   2.4900,  -0.0010), ( 50.0000,  -2.4900,   0.0009)),
+        (( 31.3217, 24.8743,  24.6178), ( 50.0000,   2.5000,   0.0000), ( 73.0000,  25.0000, -18.0000)),
+        (( 26.8952, 25.2452,  17.8424), ( 50.0000,   2.5000,   0.0000), ( 61.0000,  -5.0000,  29.0000)),
+        ((  1.7105,  1.7020,   1.1772), ( 60.2574, -34.0099,  36.2677), ( 60.4626, -34.1751,  39.4387)),
+        ((  3.3012,  3.2883,   2.6214), ( 22.7233,  20.0904, -46.6940), ( 23.0331,  14.9730, -42.5619)),
+        ((  2.0944,  1.1690,   1.6137), ( 90.9257,  -0.5406,  -0.9208), ( 88.6381,  -0.8985,  -0.7239)),
+        ((  1.6102,  1.0765,   1.9561), (  2.0776,   0.0795,  -1.1350), (  0.9033,  -0.0636,  -0.5514)),
+        (( 19.8681, 14.5794,  17.1284), ( 38.9720,  58.9910,  37.1380), ( 54.5280,  42.4160,  54.4970)),
+    ];
+
+    fn tests(
+        pick: impl Fn(&(f32, f32, f32)) -> f32,
+    ) -> Vec<crate::testutil::TestCase> {
+        TESTS
+            .iter()
+            .map(|(want, a, b)| (pick(want), *a, *b))
+            .collect()
+    }
+
+    #[test]
+    fn test_transform_din99() {
+        // Example from documentation of colour.Lab_to_DIN99 function in the
+        // colour-science Python library.  The library uses 105.509 as the
+        // lightness scale (rather than 105.51) which makes its L99 smaller by
+        // about 0.0005.
+        let got = super::transform(
+            (41.52787529, 52.63858304, 26.92317922),
+            &super::DIN99,
+            super::Params::default(),
+        );
+        approx::assert_abs_diff_eq!(53.22821988, got.0, epsilon = 0.001);
+        approx::assert_abs_diff_eq!(28.41634656, got.1, epsilon = 0.0001);
+        approx::assert_abs_diff_eq!(3.89839552, got.2, epsilon = 0.0001);
+    }
+
+    #[test]
+    fn test_transform_din99o() {
+        // Example from documentation of colour.Lab_to_DIN99 function in the
+        // colour-science Python library called with method="DIN99b".  DIN99b
+        // differs from DIN99o only by dividing compressed chroma by 1/23
+        // rather than 0.0435 so the lightness is checked with DIN99o constants
+        // directly while the a and b coordinates are checked with the divisor
+        // swapped.
+        const LAB: (f32, f32, f32) = (41.52787529, 52.63858304, 26.92317922);
+        let params = super::Params::default();
+
+        let got = super::transform(LAB, &super::DIN99O, params);
+        approx::assert_abs_diff_eq!(45.58303137, got.0, epsilon = 0.0001);
+
+        let din99b = super::Constants {
+            c_div: 1.0 / 23.0,
+            ..super::DIN99O
+        };
+        let got = super::transform(LAB, &din99b, params);
+        approx::assert_abs_diff_eq!(45.58303137, got.0, epsilon = 0.0001);
+        approx::assert_abs_diff_eq!(34.71824493, got.1, epsilon = 0.0001);
+        approx::assert_abs_diff_eq!(17.61622149, got.2, epsilon = 0.0001);
+    }
+
+    #[test]
+    fn test_difference_din99_published() {
+        // Example from documentation of colour.difference.delta_E_DIN99
+        // function in the colour-science Python library.
+        let got = super::diff_din99(
+            (60.2574, -34.0099, 36.2677),
+            (60.4626, -34.1751, 39.4387),
+            super::Params::default(),
+        );
+        approx::assert_abs_diff_eq!(1.1772166, got, epsilon = 0.0001);
+    }
+
+    #[test]
+    fn test_difference() {
+        crate::testutil::do_test_difference(&tests(|v| v.0), super::diff);
+    }
+
+    #[test]
+    fn test_difference_textiles() {
+        let params = super::Params::textiles();
+        crate::testutil::do_test_difference(&tests(|v| v.1), |a, b| {
+            super::diff_with_params(a, b, params)
+        });
+    }
+
+    #[test]
+    fn test_difference_din99() {
+        let params = super::Params::default();
+        crate::testutil::do_test_difference(&tests(|v| v.2), |a, b| {
+            super::diff_din99(a, b, params)
+        });
+    }
+
+    #[test]
+    fn test_diff_fn() {
+        let diff = super::diff_fn(super::Params::default());
+        crate::testutil::do_test_difference(&tests(|v| v.0), diff);
+    }
+
+    #[test]
+    fn test_zero() { crate::testutil::do_test_zero(super::diff) }
+
+    #[test]
+    fn test_symmetric() {
+        crate::testutil::do_test_symmetric(super::diff);
+        crate::testutil::do_test_symmetric(|a, b| {
+            super::diff_din99(a, b, super::Params::textiles())
+        });
+    }
+
+    #[test]
+    fn test_triangle() { crate::testutil::do_test_triangle(super::diff) }
+}
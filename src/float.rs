@@ -0,0 +1,282 @@
+// Floating point abstraction.
+// Copyright (c) 2026 Michał Nazarewicz <mina86@mina86.com>
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in
+// all copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+
+//! Abstraction over `f32` and `f64` used by colour difference formulæ.
+//!
+//! The formulæ are implemented once, generic over [`Float`], and instantiated
+//! for single precision (the regular `diff` functions) and double precision
+//! (the `diff_f64` functions).
+
+use core::ops::{Add, Div, Mul, Neg, Sub};
+
+/// Floating point type the colour difference formulæ operate on.
+pub(crate) trait Float:
+    Copy +
+    PartialOrd +
+    Add<Output = Self> +
+    Sub<Output = Self> +
+    Mul<Output = Self> +
+    Div<Output = Self> +
+    Neg<Output = Self>
+{
+    const PI: Self;
+    const TAU: Self;
+
+    /// Converts a constant into the type.
+    fn lit(value: f64) -> Self;
+
+    fn abs(self) -> Self;
+    fn max(self, other: Self) -> Self;
+    fn sqrt(self) -> Self;
+    fn hypot(self, other: Self) -> Self;
+    fn powi(self, n: i32) -> Self;
+    fn exp(self) -> Self;
+    fn atan2(self, other: Self) -> Self;
+    fn cos(self) -> Self;
+    fn sin(self) -> Self;
+
+    /// Cosine used in the hue-dependent terms of CIEDE2000.  With `fast-trig`
+    /// feature enabled it’s approximated using a lookup table for `f32`.
+    fn fast_cos(self) -> Self;
+    /// Sine used in the hue-dependent terms of CIEDE2000.  See
+    /// [`Float::fast_cos`].
+    fn fast_sin(self) -> Self;
+}
+
+macro_rules! impl_float {
+    ($t:ident, $m:ident, $cos:path, $sin:path) => {
+        impl Float for $t {
+            const PI: Self = core::$t::consts::PI;
+            const TAU: Self = core::$t::consts::TAU;
+
+            #[inline]
+            fn lit(value: f64) -> Self { value as $t }
+
+            #[inline]
+            fn abs(self) -> Self { $t::abs(self) }
+            #[inline]
+            fn max(self, other: Self) -> Self { $t::max(self, other) }
+            #[inline]
+            fn sqrt(self) -> Self { $m::sqrt(self) }
+            #[inline]
+            fn hypot(self, other: Self) -> Self { $m::hypot(self, other) }
+            #[inline]
+            fn powi(self, n: i32) -> Self { powi(self, n) }
+            #[inline]
+            fn exp(self) -> Self { $m::exp(self) }
+            #[inline]
+            fn atan2(self, other: Self) -> Self { $m::atan2(self, other) }
+            #[inline]
+            fn cos(self) -> Self { $m::cos(self) }
+            #[inline]
+            fn sin(self) -> Self { $m::sin(self) }
+
+            #[inline]
+            fn fast_cos(self) -> Self { $cos(self) }
+            #[inline]
+            fn fast_sin(self) -> Self { $sin(self) }
+        }
+    };
+}
+
+/// Raises `x` to integer power `n` by repeated multiplication.
+///
+/// Used in place of `f32::powi` and `libm::powf` so that results are the same
+/// regardless of whether `std` feature is enabled.  The formulæ only use
+/// small exponents.
+#[inline]
+fn powi<F: Float>(x: F, n: i32) -> F {
+    let mut result = x;
+    for _ in 1..n.unsigned_abs() {
+        result = result * x;
+    }
+    match n {
+        0 => F::lit(1.0),
+        n if n < 0 => F::lit(1.0) / result,
+        _ => result,
+    }
+}
+
+#[cfg(all(feature = "std", feature = "fast-trig"))]
+impl_float!(f32, f32, crate::trig::cos, crate::trig::sin);
+#[cfg(all(feature = "std", not(feature = "fast-trig")))]
+impl_float!(f32, f32, f32::cos, f32::sin);
+#[cfg(feature = "std")]
+impl_float!(f64, f64, f64::cos, f64::sin);
+
+#[cfg(all(not(feature = "std"), feature = "fast-trig"))]
+impl_float!(f32, libm_f32, crate::trig::cos, crate::trig::sin);
+#[cfg(all(not(feature = "std"), not(feature = "fast-trig")))]
+impl_float!(f32, libm_f32, libm_f32::cos, libm_f32::sin);
+#[cfg(not(feature = "std"))]
+impl_float!(f64, libm_f64, libm_f64::cos, libm_f64::sin);
+
+/// Floating point operations used outside of the generic colour difference
+/// formulæ.
+///
+/// Without `std` feature the methods are provided by `libm`.  Note that `std`
+/// may still be linked in (e.g. by tests or by the `lab` crate) in which case
+/// inherent methods of `f32` take precedence over methods of this trait and
+/// of [`Float`] making imports of the traits unused.  Code operating on `f32`
+/// should therefore call the methods using the qualified syntax, e.g.
+/// `FloatExt::cbrt(x)` or `Float::sqrt(x)`.
+#[allow(dead_code)] // Which methods are used depends on enabled features.
+pub(crate) trait FloatExt: Float {
+    fn powf(self, n: Self) -> Self;
+    fn cbrt(self) -> Self;
+    fn ln_1p(self) -> Self;
+    fn sin_cos(self) -> (Self, Self);
+    fn round(self) -> Self;
+    fn floor(self) -> Self;
+}
+
+#[cfg(feature = "std")]
+impl FloatExt for f32 {
+    #[inline]
+    fn powf(self, n: Self) -> Self { f32::powf(self, n) }
+    #[inline]
+    fn cbrt(self) -> Self { f32::cbrt(self) }
+    #[inline]
+    fn ln_1p(self) -> Self { f32::ln_1p(self) }
+    #[inline]
+    fn sin_cos(self) -> (Self, Self) { f32::sin_cos(self) }
+    #[inline]
+    fn round(self) -> Self { f32::round(self) }
+    #[inline]
+    fn floor(self) -> Self { f32::floor(self) }
+}
+
+#[cfg(not(feature = "std"))]
+impl FloatExt for f32 {
+    #[inline]
+    fn powf(self, n: Self) -> Self { libm::powf(self, n) }
+    #[inline]
+    fn cbrt(self) -> Self { libm::cbrtf(self) }
+    #[inline]
+    fn ln_1p(self) -> Self { libm::log1pf(self) }
+    #[inline]
+    fn sin_cos(self) -> (Self, Self) { libm::sincosf(self) }
+    #[inline]
+    fn round(self) -> Self { libm::roundf(self) }
+    #[inline]
+    fn floor(self) -> Self { libm::floorf(self) }
+}
+
+/// `libm` functions named after the corresponding `f32` methods.
+#[cfg(not(feature = "std"))]
+mod libm_f32 {
+    pub use libm::atan2f as atan2;
+    pub use libm::cosf as cos;
+    pub use libm::expf as exp;
+    pub use libm::hypotf as hypot;
+    pub use libm::sinf as sin;
+    pub use libm::sqrtf as sqrt;
+}
+
+/// `libm` functions named after the corresponding `f64` methods.
+#[cfg(not(feature = "std"))]
+mod libm_f64 {
+    pub use libm::atan2;
+    pub use libm::cos;
+    pub use libm::exp;
+    pub use libm::hypot;
+    pub use libm::sin;
+    pub use libm::sqrt;
+}
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use super::Float;
+
+    /// Returns pairs of arguments for testing hypot implementations.  These
+    /// include ordinary L\*a\*b\* coordinates as well as values whose squares
+    /// overflow or underflow `f32`.
+    pub(crate) fn hypot_test_cases() -> Vec<(f32, f32)> {
+        use rand::Rng;
+        use rand::SeedableRng;
+
+        let mut rng = rand_xoshiro::Xoshiro256Plus::seed_from_u64(0);
+        let mut any_finite = || loop {
+            let x = f32::from_bits(rng.gen());
+            if x.is_finite() {
+                break x;
+            }
+        };
+        let mut cases = vec![
+            (0.0, 0.0),
+            (-0.0, 3.0),
+            (3.0, -4.0),
+            (f32::MAX, f32::MAX),
+            (f32::MAX, 1.0),
+            (1e30, 1e30),
+            (1e-40, 1e-40),
+            (f32::from_bits(1), 0.0),
+            (f32::from_bits(1), f32::from_bits(1)),
+            (1e-30, 1e30),
+            (f32::INFINITY, 1.0),
+            (1.0, f32::NEG_INFINITY),
+        ];
+        cases.extend((0..10_000).map(|_| (any_finite(), any_finite())));
+        let mut rng = rand_xoshiro::Xoshiro256Plus::seed_from_u64(1);
+        cases.extend((0..10_000).map(|_| {
+            (rng.gen_range(-200.0..=200.0), rng.gen_range(-200.0..=200.0))
+        }));
+        cases
+    }
+
+    /// Checks `got` result of hypot calculated for given arguments against
+    /// double precision calculation.
+    pub(crate) fn check_hypot(x: f32, y: f32, got: f32) {
+        let want = f64::from(x).hypot(f64::from(y));
+        if want > f64::from(f32::MAX) {
+            assert_eq!(f32::INFINITY, got, "hypot({:e}, {:e})", x, y);
+            return;
+        }
+        let err = (f64::from(got) - want).abs();
+        let max_err = want * 2.5e-7 + f64::from(f32::from_bits(1));
+        assert!(
+            err <= max_err,
+            "hypot({:e}, {:e}): {:e} ≠ {:e}",
+            x,
+            y,
+            want,
+            got
+        );
+    }
+
+    #[test]
+    fn test_powi() {
+        assert_eq!(1.0, Float::powi(3.0f32, 0));
+        assert_eq!(3.0, Float::powi(3.0f32, 1));
+        assert_eq!(9.0, Float::powi(3.0f32, 2));
+        assert_eq!(2187.0, Float::powi(3.0f32, 7));
+        assert_eq!(0.25, Float::powi(2.0f32, -2));
+        assert_eq!(6103515625.0, Float::powi(25.0f64, 7));
+    }
+
+    #[test]
+    fn test_hypot() {
+        for (x, y) in hypot_test_cases() {
+            check_hypot(x, y, Float::hypot(x, y));
+            check_hypot(y, x, Float::hypot(y, x));
+        }
+    }
+}
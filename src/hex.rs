@@ -0,0 +1,250 @@
+// Hexadecimal colour notation parsing.
+// Copyright (c) 2026 Michał Nazarewicz <mina86@mina86.com>
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in
+// all copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+
+use crate::ToLab;
+
+/// An sRGB colour parsed from hexadecimal notation.
+///
+/// The type implements [`FromStr`](core::str::FromStr) trait which accepts
+/// strings in `#RGB`, `#RRGGBB` and `#RRGGBBAA` formats.  Digits are case
+/// insensitive.  In the short form, each digit is repeated so `#fa0` is the
+/// same as `#ffaa00`.  If alpha is not given, the colour is opaque.
+///
+/// When converting to L\*a\*b\* the components are treated as gamma-encoded
+/// sRGB values.  Colours which aren’t opaque are composited over white
+/// background the same way `rgb::RGBA<u8>` colours are.
+///
+/// ## Example
+///
+/// ```
+/// use empfindung::{cie00, Hex};
+///
+/// let colour_1: Hex = "#eA4c4C".parse().unwrap();
+/// let colour_2: Hex = "#4cbbea".parse().unwrap();
+/// assert_eq!(Hex { r: 234, g: 76, b: 76, a: 255 }, colour_1);
+/// let delta_e = cie00::diff(colour_1, colour_2);
+/// approx::assert_abs_diff_eq!(58.90164, delta_e, epsilon = 0.001);
+///
+/// assert!("ea4c4c".parse::<Hex>().is_err());
+/// ```
+///
+/// With `std` feature enabled, the colour can also be parsed from an `OsStr`,
+/// e.g. a command line argument, using [`TryFrom`](core::convert::TryFrom)
+/// trait:
+///
+/// ```
+/// # #[cfg(feature = "std")] {
+/// use core::convert::TryFrom;
+/// use empfindung::{Hex, ParseHexError};
+///
+/// let arg = std::ffi::OsString::from("#ea4c4c");
+/// assert_eq!(Ok(Hex { r: 234, g: 76, b: 76, a: 255 }),
+///            Hex::try_from(arg.as_os_str()));
+/// let arg = std::ffi::OsString::from("ea4c4c");
+/// assert_eq!(Err(ParseHexError::MissingHash),
+///            Hex::try_from(arg.as_os_str()));
+/// # }
+/// ```
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct Hex {
+    /// Red component of the sRGB colour.
+    pub r: u8,
+    /// Green component of the sRGB colour.
+    pub g: u8,
+    /// Blue component of the sRGB colour.
+    pub b: u8,
+    /// Opacity of the colour; 255 means fully opaque.
+    pub a: u8,
+}
+
+/// Error returned when parsing a [`Hex`] colour fails.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum ParseHexError {
+    /// The string doesn’t start with a `#` character.
+    MissingHash,
+    /// Number of characters after `#` is other than three, six or eight.
+    InvalidLength(usize),
+    /// The string contains a character which isn’t a hexadecimal digit.
+    InvalidDigit(char),
+    /// The `OsStr` being parsed isn’t valid Unicode.
+    NotUnicode,
+}
+
+impl core::fmt::Display for ParseHexError {
+    fn fmt(&self, fmtr: &mut core::fmt::Formatter) -> core::fmt::Result {
+        match *self {
+            Self::MissingHash => fmtr.write_str("expected ‘#’ at the start"),
+            Self::InvalidLength(len) => write!(
+                fmtr,
+                "expected 3, 6 or 8 hexadecimal digits but got {}",
+                len
+            ),
+            Self::InvalidDigit(chr) => {
+                write!(fmtr, "invalid hexadecimal digit ‘{}’", chr)
+            }
+            Self::NotUnicode => fmtr.write_str("not valid Unicode"),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for ParseHexError {}
+
+impl core::str::FromStr for Hex {
+    type Err = ParseHexError;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        let digits =
+            value.strip_prefix('#').ok_or(ParseHexError::MissingHash)?;
+        let len = digits.chars().count();
+        if !matches!(len, 3 | 6 | 8) {
+            return Err(ParseHexError::InvalidLength(len));
+        }
+        let mut nibbles = [15; 8];
+        for (nibble, chr) in nibbles.iter_mut().zip(digits.chars()) {
+            let digit =
+                chr.to_digit(16).ok_or(ParseHexError::InvalidDigit(chr));
+            *nibble = digit? as u8;
+        }
+        let [r, g, b, a] = if len == 3 {
+            [nibbles[0] * 17, nibbles[1] * 17, nibbles[2] * 17, 255]
+        } else {
+            let byte = |idx: usize| nibbles[idx] << 4 | nibbles[idx + 1];
+            [byte(0), byte(2), byte(4), byte(6)]
+        };
+        Ok(Self { r, g, b, a })
+    }
+}
+
+#[cfg(feature = "std")]
+impl core::convert::TryFrom<&std::ffi::OsStr> for Hex {
+    type Error = ParseHexError;
+
+    fn try_from(value: &std::ffi::OsStr) -> Result<Self, Self::Error> {
+        value.to_str().ok_or(ParseHexError::NotUnicode)?.parse()
+    }
+}
+
+impl ToLab for Hex {
+    /// Assumes an sRGB colour, composites it over white background and
+    /// converts the result into L\*a\*\b\*.
+    fn to_lab(&self) -> (f32, f32, f32) {
+        use crate::srgb::blend;
+        let rgb = (
+            blend(self.r, 255, self.a),
+            blend(self.g, 255, self.a),
+            blend(self.b, 255, self.a),
+        );
+        let xyz = crate::srgb::mul(&crate::srgb::TO_XYZ, rgb);
+        crate::xyz::to_lab(xyz, crate::xyz::D65)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse() {
+        let hex = |r, g, b, a| Ok(Hex { r, g, b, a });
+        assert_eq!(hex(234, 76, 76, 255), "#ea4c4c".parse());
+        assert_eq!(hex(234, 76, 76, 255), "#EA4C4C".parse());
+        assert_eq!(hex(234, 76, 76, 255), "#eA4c4C".parse());
+        assert_eq!(hex(255, 170, 0, 255), "#fA0".parse());
+        assert_eq!(hex(234, 76, 76, 128), "#ea4c4c80".parse());
+        assert_eq!(hex(0, 0, 0, 0), "#00000000".parse());
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        use ParseHexError::*;
+
+        let parse = |value: &str| value.parse::<Hex>();
+        assert_eq!(Err(MissingHash), parse("ea4c4c"));
+        assert_eq!(Err(MissingHash), parse(""));
+        assert_eq!(Err(InvalidLength(0)), parse("#"));
+        assert_eq!(Err(InvalidLength(2)), parse("#ea"));
+        assert_eq!(Err(InvalidLength(4)), parse("#ea4c"));
+        assert_eq!(Err(InvalidLength(5)), parse("#ea4c4"));
+        assert_eq!(Err(InvalidLength(7)), parse("#ea4c4c8"));
+        assert_eq!(Err(InvalidLength(9)), parse("#ea4c4c800"));
+        assert_eq!(Err(InvalidDigit('g')), parse("#eg4c4c"));
+        assert_eq!(Err(InvalidDigit('#')), parse("#e#4c4c"));
+        assert_eq!(Err(InvalidDigit('+')), parse("#+a4c4c"));
+        assert_eq!(Err(InvalidDigit('ż')), parse("#żół"));
+        assert_eq!(Err(InvalidLength(4)), parse("#żółw"));
+    }
+
+    #[test]
+    #[cfg(feature = "std")]
+    fn test_try_from_os_str() {
+        use core::convert::TryFrom;
+        use std::ffi::OsStr;
+
+        let parse = |value: &OsStr| Hex::try_from(value);
+        assert_eq!("#ea4c4c".parse(), parse(OsStr::new("#ea4c4c")));
+        assert_eq!(
+            Err(ParseHexError::MissingHash),
+            parse(OsStr::new("ea4c4c"))
+        );
+        assert_eq!(
+            Err(ParseHexError::InvalidLength(4)),
+            parse(OsStr::new("#ea4c"))
+        );
+        assert_eq!(
+            Err(ParseHexError::InvalidDigit('g')),
+            parse(OsStr::new("#eg4c4c"))
+        );
+
+        #[cfg(unix)]
+        {
+            use std::os::unix::ffi::OsStrExt;
+            let value = OsStr::from_bytes(b"#ea\xff4c4c");
+            assert_eq!(Err(ParseHexError::NotUnicode), parse(value));
+        }
+    }
+
+    #[test]
+    fn test_error_display() {
+        use ParseHexError::*;
+
+        let fmt = |err: ParseHexError| err.to_string();
+        assert_eq!("expected ‘#’ at the start", fmt(MissingHash));
+        assert_eq!(
+            "expected 3, 6 or 8 hexadecimal digits but got 4",
+            fmt(InvalidLength(4))
+        );
+        assert_eq!("invalid hexadecimal digit ‘g’", fmt(InvalidDigit('g')));
+        assert_eq!("not valid Unicode", fmt(NotUnicode));
+    }
+
+    #[test]
+    #[cfg(all(feature = "lab", feature = "rgb"))]
+    fn test_to_lab() {
+        let hex = "#ea4c4c".parse::<Hex>().unwrap();
+        let want = rgb::RGB::<u8>::new(234, 76, 76).to_lab();
+        let diff = crate::cie76::diff(want, hex);
+        assert!(diff < crate::testutil::EPSILON, "{:?} {:?}", want, hex);
+        let hex = "#ea4c4c80".parse::<Hex>().unwrap();
+        let want = rgb::RGBA::<u8>::new(234, 76, 76, 128).to_lab();
+        assert_eq!(want, hex.to_lab());
+    }
+}
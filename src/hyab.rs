@@ -0,0 +1,96 @@
+// HyAB colour difference implementation.
+// Copyright (c) 2026 Michał Nazarewicz <mina86@mina86.com>
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in
+// all copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+
+//! Implementation of the HyAB colour distance algorithm.
+//!
+//! HyAB (Abasi, Amani Tehran and Fairchild, 2020) is a hybrid distance in the
+//! L\*a\*b\* colour space which combines the absolute (city block) difference
+//! in lightness with the Euclidean distance in the a\*b\* plane.  It was found
+//! to perform better than CIEDE2000 for large colour differences.  Being a sum
+//! of two metrics, it is a metric in the mathematical sense.
+//!
+//! Note that while the distance is invariant under rotations of the a\*b\*
+//! plane (i.e. hue rotations), unlike CIE76 it isn’t invariant under general
+//! rotations of the L\*a\*b\* space.  A colour difference split between
+//! lightness and chroma results in larger distance than the same Euclidean
+//! difference in lightness or chroma alone.
+
+use crate::float::Float;
+
+/// Returns the HyAB colour difference between two L\*a\*b\* colours.
+///
+/// ## Example
+///
+/// ```
+/// use empfindung::hyab;
+///
+/// let colour_1 = (50.0, 0.0, 0.0);
+/// let colour_2 = (53.0, 4.0, 0.0);
+/// assert_eq!(7.0, hyab::diff(colour_1, colour_2));
+///
+/// // CIE76 calculates Euclidean distance in all three dimensions.
+/// assert_eq!(5.0, empfindung::cie76::diff(colour_1, colour_2));
+/// ```
+pub fn diff(colour_1: impl crate::ToLab, colour_2: impl crate::ToLab) -> f32 {
+    let (colour_1, colour_2) = (colour_1.to_lab(), colour_2.to_lab());
+    let dl = colour_1.0 - colour_2.0;
+    let da = colour_1.1 - colour_2.1;
+    let db = colour_1.2 - colour_2.2;
+    dl.abs() + Float::hypot(da, db)
+}
+
+#[cfg(test)]
+mod tests {
+    #[rustfmt::skip]
+    static TESTS: [crate::testutil::TestCase; 6] = [
+        ( 5.0, (50.0, 0.0, 0.0), (50.0,  3.0,  4.0)),
+        ( 5.0, (50.0, 3.0, 0.0), (50.0,  0.0,  4.0)),
+        ( 7.0, (50.0, 0.0, 0.0), (53.0,  4.0,  0.0)),
+        ( 7.0, (50.0, 0.0, 0.0), (47.0,  0.0, -4.0)),
+        (10.0, (40.0, 0.0, 0.0), (50.0,  0.0,  0.0)),
+        (13.0, (10.0, 2.0, 2.0), ( 2.0, -1.0, -2.0)),
+    ];
+
+    #[test]
+    fn test_difference() {
+        crate::testutil::do_test_difference(&TESTS, super::diff);
+    }
+
+    #[test]
+    fn test_not_rotation_invariant() {
+        // Rotating the difference vector within a*b* plane doesn’t affect
+        // the distance but rotating it towards L* axis does.
+        let diff = |d: (f32, f32, f32)| super::diff((50.0, 0.0, 0.0), d);
+        assert_eq!(5.0, diff((50.0, 5.0, 0.0)));
+        assert_eq!(5.0, diff((50.0, 3.0, 4.0)));
+        assert_eq!(5.0, diff((55.0, 0.0, 0.0)));
+        assert_eq!(7.0, diff((54.0, 3.0, 0.0)));
+    }
+
+    #[test]
+    fn test_zero() { crate::testutil::do_test_zero(super::diff) }
+
+    #[test]
+    fn test_symmetric() { crate::testutil::do_test_symmetric(super::diff) }
+
+    #[test]
+    fn test_triangle() { crate::testutil::do_test_triangle(super::diff) }
+}
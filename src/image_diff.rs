@@ -0,0 +1,392 @@
+// Colour difference visualisation of images.
+// Copyright (c) 2026 Michał Nazarewicz <mina86@mina86.com>
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in
+// all copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+
+//! Colour difference computation and visualisation for images.
+//!
+//! The module is available with the `image` feature and operates on
+//! [`image::RgbImage`] buffers whose pixels are assumed to be in sRGB colour
+//! space.
+
+/// Colourmap used to visualise magnitude of colour differences.
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Default)]
+pub enum Colourmap {
+    /// Linear ramp from black to white.
+    Greyscale,
+    /// Perceptually uniform colourmap going from dark purple through blue and
+    /// green to yellow.  It’s the default colourmap of matplotlib and remains
+    /// legible when printed in greyscale and for people with colour vision
+    /// deficiencies.
+    #[default]
+    Viridis,
+}
+
+impl Colourmap {
+    /// Maps value in the `0..=1` range to a colour.
+    ///
+    /// Values outside of the range are clamped (with NaN treated as zero).
+    /// Colours between the stops of the colourmap are linearly interpolated
+    /// in sRGB.
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// use empfindung::image_diff::Colourmap;
+    ///
+    /// assert_eq!([0, 0, 0], Colourmap::Greyscale.map(-1.0));
+    /// assert_eq!([128, 128, 128], Colourmap::Greyscale.map(0.5));
+    /// assert_eq!([0x44, 0x01, 0x54], Colourmap::Viridis.map(0.0));
+    /// assert_eq!([0xfd, 0xe7, 0x25], Colourmap::Viridis.map(1.0));
+    /// ```
+    pub fn map(self, value: f32) -> [u8; 3] {
+        let stops: &[[u8; 3]] = match self {
+            Self::Greyscale => &[[0, 0, 0], [255, 255, 255]],
+            Self::Viridis => &VIRIDIS,
+        };
+        let value = if value > 0.0 { value.min(1.0) } else { 0.0 };
+        let pos = value * (stops.len() - 1) as f32;
+        let idx = (pos as usize).min(stops.len() - 2);
+        let t = pos - idx as f32;
+        let (lo, hi) = (stops[idx], stops[idx + 1]);
+        let lerp = |i: usize| {
+            (lo[i] as f32 + (hi[i] as f32 - lo[i] as f32) * t).round() as u8
+        };
+        [lerp(0), lerp(1), lerp(2)]
+    }
+}
+
+/// Evenly spaced stops of the viridis colourmap.
+#[rustfmt::skip]
+const VIRIDIS: [[u8; 3]; 11] = [
+    [0x44, 0x01, 0x54], [0x48, 0x24, 0x75], [0x41, 0x44, 0x87],
+    [0x35, 0x5f, 0x8d], [0x2a, 0x78, 0x8e], [0x21, 0x91, 0x8c],
+    [0x22, 0xa8, 0x84], [0x44, 0xbf, 0x70], [0x7a, 0xd1, 0x51],
+    [0xbd, 0xdf, 0x26], [0xfd, 0xe7, 0x25],
+];
+
+/// Returns a heatmap visualising per-pixel colour difference between two
+/// images.
+///
+/// For each pixel, calculates CIEDE2000 colour difference between
+/// corresponding pixels of the two images and maps it through the
+/// `colourmap`.  Difference of zero is mapped to the first colour of the
+/// colourmap while differences of `max_delta_e` or more are mapped to the
+/// last one.
+///
+/// Returns an error if the images have different dimensions.
+///
+/// ## Example
+///
+/// ```
+/// use empfindung::image_diff::{diff_heatmap, Colourmap};
+///
+/// let a = image::RgbImage::from_pixel(2, 1, image::Rgb([255, 0, 0]));
+/// let mut b = a.clone();
+/// b.put_pixel(1, 0, image::Rgb([0, 0, 255]));
+///
+/// let heatmap = diff_heatmap(&a, &b, 10.0, Colourmap::Viridis).unwrap();
+/// assert_eq!(image::Rgb([0x44, 0x01, 0x54]), heatmap[(0, 0)]);
+/// assert_eq!(image::Rgb([0xfd, 0xe7, 0x25]), heatmap[(1, 0)]);
+///
+/// let c = image::RgbImage::new(2, 2);
+/// assert!(diff_heatmap(&a, &c, 10.0, Colourmap::Viridis).is_err());
+/// ```
+pub fn diff_heatmap(
+    a: &image::RgbImage,
+    b: &image::RgbImage,
+    max_delta_e: f32,
+    colourmap: Colourmap,
+) -> Result<image::RgbImage, DimensionMismatch> {
+    if a.dimensions() != b.dimensions() {
+        return Err(DimensionMismatch { a: a.dimensions(), b: b.dimensions() });
+    }
+    let mut out = image::RgbImage::new(a.width(), a.height());
+    for ((pa, pb), po) in a.pixels().zip(b.pixels()).zip(out.pixels_mut()) {
+        let delta_e = crate::cie00::diff(pa, pb);
+        *po = image::Rgb(colourmap.map(delta_e / max_delta_e));
+    }
+    Ok(out)
+}
+
+/// Returns a mask of pixels whose colour difference exceeds a threshold.
+///
+/// For each pixel, calculates colour difference between corresponding pixels
+/// of the two images using the `diff` function (with pixel of image `a`
+/// passed as the first argument) and sets the pixel of the mask to 255 if the
+/// difference is greater than `threshold` or to 0 otherwise.  The mask can be
+/// used for pass/fail gating in visual regression tests or fed to
+/// connected-component analysis to locate defects.
+///
+/// Returns an error if the images have different dimensions.
+///
+/// ## Example
+///
+/// ```
+/// use empfindung::{cie00, image_diff};
+///
+/// let a = image::RgbImage::from_pixel(2, 1, image::Rgb([255, 0, 0]));
+/// let mut b = a.clone();
+/// b.put_pixel(1, 0, image::Rgb([250, 0, 0]));
+///
+/// let diff = cie00::diff_fn(cie00::Params::default());
+/// let mask = image_diff::diff_mask(&a, &b, 1.0, diff).unwrap();
+/// assert_eq!(&[0, 255], mask.as_raw().as_slice());
+///
+/// let c = image::RgbImage::new(2, 2);
+/// assert!(image_diff::diff_mask(&a, &c, 1.0, diff).is_err());
+/// ```
+pub fn diff_mask(
+    a: &image::RgbImage,
+    b: &image::RgbImage,
+    threshold: f32,
+    diff: impl crate::Metric,
+) -> Result<image::GrayImage, DimensionMismatch> {
+    use crate::ToLab;
+
+    if a.dimensions() != b.dimensions() {
+        return Err(DimensionMismatch { a: a.dimensions(), b: b.dimensions() });
+    }
+    let mut out = image::GrayImage::new(a.width(), a.height());
+    for ((pa, pb), po) in a.pixels().zip(b.pixels()).zip(out.pixels_mut()) {
+        let delta_e = diff.diff(pa.to_lab(), pb.to_lab());
+        *po = image::Luma([if delta_e > threshold { 255 } else { 0 }]);
+    }
+    Ok(out)
+}
+
+/// Summary of per-pixel colour differences between two images returned by
+/// [`diff_images`].
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub struct ImageDiffStats {
+    /// Mean colour difference over all pixels.
+    pub mean: f32,
+    /// Largest colour difference.
+    pub max: f32,
+    /// Coordinates `(x, y)` of the first pixel (in row-major order) with the
+    /// largest colour difference.
+    pub max_position: (u32, u32),
+    /// Histogram of the colour differences.  Element `i` counts pixels whose
+    /// colour difference is in the `i..i+1` range except for the last element
+    /// which counts all pixels whose difference is ten or more.
+    pub histogram: [u64; 11],
+}
+
+/// Error returned by [`diff_heatmap`], [`diff_mask`] and [`diff_images`] when
+/// the images have different dimensions.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct DimensionMismatch {
+    /// Dimensions `(width, height)` of the first image.
+    pub a: (u32, u32),
+    /// Dimensions `(width, height)` of the second image.
+    pub b: (u32, u32),
+}
+
+impl core::fmt::Display for DimensionMismatch {
+    fn fmt(&self, fmtr: &mut core::fmt::Formatter) -> core::fmt::Result {
+        write!(
+            fmtr,
+            "image dimensions differ: {}×{} vs {}×{}",
+            self.a.0, self.a.1, self.b.0, self.b.1
+        )
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for DimensionMismatch {}
+
+/// Compares two images pixel by pixel and summarises the colour differences.
+///
+/// For each pixel, calculates colour difference between corresponding pixels
+/// of the two images using the `diff` function (with pixel of image `a`
+/// passed as the first argument) and returns statistics of the differences.
+/// This is useful for checking whether two renders are perceptually
+/// identical.  For images with no pixels, all the statistics are zero.
+///
+/// Returns an error if the images have different dimensions.
+///
+/// ## Example
+///
+/// ```
+/// use empfindung::{cie00, image_diff};
+///
+/// let a = image::RgbImage::from_pixel(2, 2, image::Rgb([255, 0, 0]));
+/// let mut b = a.clone();
+/// b.put_pixel(1, 0, image::Rgb([250, 0, 0]));
+///
+/// let diff = cie00::diff_fn(cie00::Params::default());
+/// let stats = image_diff::diff_images(&a, &b, diff).unwrap();
+/// assert_eq!((1, 0), stats.max_position);
+/// approx::assert_abs_diff_eq!(stats.max / 4.0, stats.mean);
+/// assert_eq!(3, stats.histogram[0]);
+///
+/// let c = image::RgbImage::new(2, 1);
+/// assert!(image_diff::diff_images(&a, &c, diff).is_err());
+/// ```
+pub fn diff_images(
+    a: &image::RgbImage,
+    b: &image::RgbImage,
+    diff: impl crate::Metric,
+) -> Result<ImageDiffStats, DimensionMismatch> {
+    use crate::ToLab;
+
+    if a.dimensions() != b.dimensions() {
+        return Err(DimensionMismatch { a: a.dimensions(), b: b.dimensions() });
+    }
+    let mut stats = ImageDiffStats {
+        mean: 0.0,
+        max: 0.0,
+        max_position: (0, 0),
+        histogram: [0; 11],
+    };
+    let mut sum = 0.0f64;
+    for ((x, y, pa), pb) in a.enumerate_pixels().zip(b.pixels()) {
+        let delta_e = diff.diff(pa.to_lab(), pb.to_lab());
+        sum += f64::from(delta_e);
+        if delta_e > stats.max {
+            stats.max = delta_e;
+            stats.max_position = (x, y);
+        }
+        let bucket = (delta_e.max(0.0) as usize).min(stats.histogram.len() - 1);
+        stats.histogram[bucket] += 1;
+    }
+    let count = u64::from(a.width()) * u64::from(a.height());
+    if count != 0 {
+        stats.mean = (sum / count as f64) as f32;
+    }
+    Ok(stats)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_colourmap_map() {
+        for colourmap in [Colourmap::Greyscale, Colourmap::Viridis] {
+            let first = colourmap.map(0.0);
+            let last = colourmap.map(1.0);
+            assert_eq!(first, colourmap.map(-0.5));
+            assert_eq!(first, colourmap.map(f32::NAN));
+            assert_eq!(last, colourmap.map(1.5));
+            assert_eq!(last, colourmap.map(f32::INFINITY));
+        }
+        assert_eq!([0x2a, 0x78, 0x8e], Colourmap::Viridis.map(0.4));
+        assert_eq!([0x26, 0x85, 0x8d], Colourmap::Viridis.map(0.45));
+    }
+
+    #[test]
+    fn test_diff_heatmap() {
+        let a = image::RgbImage::from_fn(16, 4, |x, y| {
+            image::Rgb([x as u8 * 16, y as u8 * 64, 128])
+        });
+        let b = image::RgbImage::from_fn(16, 4, |x, y| {
+            image::Rgb([x as u8 * 16, y as u8 * 64, 128 + x as u8])
+        });
+        let heatmap = diff_heatmap(&a, &b, 5.0, Colourmap::Greyscale).unwrap();
+        for (x, y, pixel) in heatmap.enumerate_pixels() {
+            let delta_e = crate::cie00::diff(a[(x, y)], b[(x, y)]);
+            let want = Colourmap::Greyscale.map(delta_e / 5.0);
+            assert_eq!(image::Rgb(want), *pixel, "({}, {})", x, y);
+        }
+    }
+
+    #[test]
+    fn test_diff_heatmap_dimensions_mismatch() {
+        let a = image::RgbImage::new(2, 2);
+        let b = image::RgbImage::new(2, 3);
+        let err = diff_heatmap(&a, &b, 1.0, Colourmap::Viridis).unwrap_err();
+        assert_eq!(DimensionMismatch { a: (2, 2), b: (2, 3) }, err);
+    }
+
+    #[test]
+    fn test_diff_mask() {
+        let a = image::RgbImage::from_fn(16, 4, |x, y| {
+            image::Rgb([x as u8 * 16, y as u8 * 64, 128])
+        });
+        let b = image::RgbImage::from_fn(16, 4, |x, y| {
+            image::Rgb([x as u8 * 16, y as u8 * 64, 128 + x as u8])
+        });
+        let diff = crate::cie76::diff_fn();
+        let mask = diff_mask(&a, &b, 2.0, diff).unwrap();
+        let mut count = 0;
+        for (x, y, pixel) in mask.enumerate_pixels() {
+            let delta_e = crate::cie76::diff(a[(x, y)], b[(x, y)]);
+            let want = if delta_e > 2.0 { 255 } else { 0 };
+            assert_eq!(image::Luma([want]), *pixel, "({}, {})", x, y);
+            count += usize::from(want != 0);
+        }
+        assert!(0 < count && count < 64, "{}", count);
+    }
+
+    #[test]
+    fn test_diff_images() {
+        let diff = crate::cie76::diff_fn();
+        let a = image::RgbImage::from_fn(2, 2, |x, y| {
+            image::Rgb([x as u8 * 200, y as u8 * 200, 100])
+        });
+
+        let stats = diff_images(&a, &a, diff).unwrap();
+        let want = ImageDiffStats {
+            mean: 0.0,
+            max: 0.0,
+            max_position: (0, 0),
+            histogram: [4, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0],
+        };
+        assert_eq!(want, stats);
+
+        let mut b = a.clone();
+        b.put_pixel(0, 1, image::Rgb([0, 190, 100]));
+        let stats = diff_images(&a, &b, diff).unwrap();
+        let delta_e = crate::cie76::diff(a[(0, 1)], b[(0, 1)]);
+        assert!(delta_e > 1.0 && delta_e < 10.0, "{}", delta_e);
+        let mut histogram = [0; 11];
+        histogram[0] = 3;
+        histogram[delta_e as usize] = 1;
+        let want = ImageDiffStats {
+            mean: delta_e / 4.0,
+            max: delta_e,
+            max_position: (0, 1),
+            histogram,
+        };
+        assert_eq!(want, stats);
+
+        let empty = image::RgbImage::new(0, 0);
+        let stats = diff_images(&empty, &empty, diff).unwrap();
+        assert_eq!(0.0, stats.mean);
+        assert_eq!([0; 11], stats.histogram);
+    }
+
+    #[test]
+    fn test_diff_images_dimensions_mismatch() {
+        let a = image::RgbImage::new(2, 2);
+        let b = image::RgbImage::new(2, 3);
+        let err = diff_images(&a, &b, crate::cie76::diff_fn()).unwrap_err();
+        assert_eq!(DimensionMismatch { a: (2, 2), b: (2, 3) }, err);
+        assert_eq!("image dimensions differ: 2×2 vs 2×3", err.to_string());
+    }
+
+    #[test]
+    fn test_diff_mask_dimensions_mismatch() {
+        let a = image::RgbImage::new(2, 2);
+        let b = image::RgbImage::new(3, 2);
+        let err = diff_mask(&a, &b, 1.0, crate::cie76::diff_fn()).unwrap_err();
+        assert_eq!(DimensionMismatch { a: (2, 2), b: (3, 2) }, err);
+    }
+}
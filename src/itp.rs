@@ -0,0 +1,189 @@
+// ΔE ITP colour difference implementation.
+// Copyright (c) 2026 Michał Nazarewicz <mina86@mina86.com>
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in
+// all copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+
+//! Implementation of the ΔE ITP colour distance (Rec. ITU-R BT.2124).
+//!
+//! ΔE ITP is the colour difference metric standardised for broadcast and
+//! video applications, in particular for high dynamic range and wide colour
+//! gamut content.  It is the scaled Euclidean distance in the ICtCp colour
+//! space (defined in Rec. ITU-R BT.2100) with the Ct axis halved.  The
+//! scaling factor of 720 is chosen so that a difference of one corresponds to
+//! a just noticeable difference.  As a Euclidean distance it is a metric in
+//! the mathematical sense.
+//!
+//! As with [`crate::jzazbz`], ICtCp is defined in terms of absolute
+//! luminance.  The L\*a\*b\* arguments are converted into XYZ coordinates
+//! relative to D65 white point which is then assumed to have luminance of
+//! [`crate::jzazbz::DEFAULT_PEAK_NITS`].
+
+use crate::float::Float;
+
+/// Returns the ΔE ITP colour difference between two L\*a\*b\* colours.
+///
+/// ## Example
+///
+/// ```
+/// use empfindung::itp;
+///
+/// let colour_1 = (38.972, 58.991, 37.138);
+/// let colour_2 = (54.528, 42.416, 54.497);
+/// let delta_e = itp::diff(colour_1, colour_2);
+/// approx::assert_abs_diff_eq!(76.46, delta_e, epsilon = 0.01);
+/// ```
+pub fn diff(colour_1: impl crate::ToLab, colour_2: impl crate::ToLab) -> f32 {
+    720.0 * diff_scd(colour_1, colour_2)
+}
+
+/// Returns the ΔE ITP colour difference between two L\*a\*b\* colours without
+/// the 720 scaling factor.
+///
+/// That is, the result is the Euclidean distance in the ITP colour space
+/// where the T coordinate equals half of Ct.
+///
+/// ## Example
+///
+/// ```
+/// use empfindung::itp;
+///
+/// let colour_1 = (38.972, 58.991, 37.138);
+/// let colour_2 = (54.528, 42.416, 54.497);
+/// let delta_e = itp::diff_scd(colour_1, colour_2);
+/// approx::assert_abs_diff_eq!(0.1062, delta_e, epsilon = 0.0001);
+/// ```
+pub fn diff_scd(
+    colour_1: impl crate::ToLab,
+    colour_2: impl crate::ToLab,
+) -> f32 {
+    distance(from_lab(colour_1.to_lab()), from_lab(colour_2.to_lab()))
+}
+
+/// Returns the unscaled ΔE ITP distance between two ICtCp colours.
+fn distance(colour_1: (f32, f32, f32), colour_2: (f32, f32, f32)) -> f32 {
+    let di = colour_1.0 - colour_2.0;
+    let dt = 0.5 * (colour_1.1 - colour_2.1);
+    let dp = colour_1.2 - colour_2.2;
+    Float::sqrt(di * di + dt * dt + dp * dp)
+}
+
+/// Converts L\*a\*b\* colour into ICtCp coordinates.
+fn from_lab(lab: (f32, f32, f32)) -> (f32, f32, f32) {
+    let peak = crate::jzazbz::DEFAULT_PEAK_NITS;
+    let (x, y, z) = crate::xyz::from_lab(lab, crate::xyz::D65);
+    let (x, y, z) = (x * peak, y * peak, z * peak);
+
+    // XYZ to linear Rec. 2020 RGB.
+    let r = 1.7166512 * x - 0.3556708 * y - 0.2533663 * z;
+    let g = -0.6666844 * x + 1.6164812 * y + 0.0157685 * z;
+    let b = 0.0176399 * x - 0.0427706 * y + 0.9421031 * z;
+    from_rec2020((r, g, b))
+}
+
+/// Converts linear Rec. 2020 RGB colour with components given in cd/m² into
+/// ICtCp coordinates.
+fn from_rec2020((r, g, b): (f32, f32, f32)) -> (f32, f32, f32) {
+    const M_2: f32 = 2523.0 / 32.0;
+
+    // Rec. 2020 RGB to LMS followed by the PQ non-linearity.
+    let l = (1688.0 * r + 2146.0 * g + 262.0 * b) / 4096.0;
+    let m = (683.0 * r + 2951.0 * g + 462.0 * b) / 4096.0;
+    let s = (99.0 * r + 309.0 * g + 3688.0 * b) / 4096.0;
+    let pq = |v| crate::xyz::pq(v, M_2);
+    let (l, m, s) = (pq(l), pq(m), pq(s));
+
+    let i = 0.5 * l + 0.5 * m;
+    let ct = (6610.0 * l - 13613.0 * m + 7003.0 * s) / 4096.0;
+    let cp = (17933.0 * l - 17390.0 * m - 543.0 * s) / 4096.0;
+    (i, ct, cp)
+}
+
+#[cfg(test)]
+mod tests {
+    // Regression values for the whole L\*a\*b\* → XYZ → Rec. 2020 → ICtCp
+    // pipeline calculated in double precision.  The columns are: ΔE ITP, the
+    // unscaled distance and the two colours.  Since single precision is used,
+    // allow error of 0.01 which is a hundredth of just noticeable difference.
+    type Lab = (f32, f32, f32);
+
+    #[rustfmt::skip]
+    static TESTS: [(f32, f32, Lab, Lab); 7] = [
+        ( 76.4700, 0.1062084, (38.972, 58.991, 37.138), (54.528, 42.416, 54.497)),
+        ( 68.5302, 0.0951808, (50.000,  2.500,  0.000), (73.000, 25.000, -18.000)),
+        ( 62.4564, 0.0867450, ( 0.000,  0.000,  0.000), ( 1.000,  0.000,   0.000)),
+        (  2.6781, 0.0037196, (50.000,  0.000,  0.000), (50.000, -1.000,   2.000)),
+        (418.0952, 0.5806878, (100.00,  0.000,  0.000), ( 0.000,  0.000,   0.000)),
+        (  5.5805, 0.0077507, (22.723, 20.090, -46.694), (23.033, 14.973, -42.562)),
+        (  3.0114, 0.0041825, (50.000,  0.000,  0.000), (51.000,  0.000,   0.000)),
+    ];
+
+    #[test]
+    fn test_difference() {
+        for &(want, want_scd, colour_1, colour_2) in TESTS.iter() {
+            let got = super::diff(colour_1, colour_2);
+            approx::assert_abs_diff_eq!(want, got, epsilon = 0.01);
+            let got = super::diff_scd(colour_1, colour_2);
+            approx::assert_abs_diff_eq!(want_scd, got, epsilon = 0.00002);
+        }
+    }
+
+    #[test]
+    fn test_from_rec2020() {
+        // Example from documentation of colour.RGB_to_ICtCp function in the
+        // colour-science Python library (with the default L_p = 10000).  The
+        // large exponent of the PQ curve amplifies single precision rounding
+        // errors hence the relatively large epsilon.
+        let got = super::from_rec2020((0.45620519, 0.03081071, 0.04091952));
+        approx::assert_abs_diff_eq!(0.0735136, got.0, epsilon = 1e-5);
+        approx::assert_abs_diff_eq!(0.0047525, got.1, epsilon = 1e-5);
+        approx::assert_abs_diff_eq!(0.0935159, got.2, epsilon = 1e-5);
+    }
+
+    #[test]
+    fn test_distance() {
+        // Example from documentation of colour.difference.delta_E_ITP
+        // function in the colour-science Python library.
+        let got = super::distance(
+            (0.4885468072, -0.04739350675, 0.07475401302),
+            (0.4899203231, -0.04567508203, 0.07361341775),
+        );
+        approx::assert_abs_diff_eq!(1.4265723, 720.0 * got, epsilon = 0.0001);
+    }
+
+    #[test]
+    fn test_black() {
+        let (i, ct, cp) = super::from_lab((0.0, 0.0, 0.0));
+        approx::assert_abs_diff_eq!(0.0, i, epsilon = 1e-6);
+        approx::assert_abs_diff_eq!(0.0, ct, epsilon = 1e-6);
+        approx::assert_abs_diff_eq!(0.0, cp, epsilon = 1e-6);
+    }
+
+    #[test]
+    fn test_negative_radicand() {
+        // Highly saturated colours map to negative LMS coordinates.
+        let got = super::diff((10.0, 100.0, -200.0), (10.0, -100.0, 200.0));
+        assert!(got.is_finite(), "{}", got);
+    }
+
+    #[test]
+    fn test_zero() { crate::testutil::do_test_zero(super::diff) }
+
+    #[test]
+    fn test_symmetric() { crate::testutil::do_test_symmetric(super::diff) }
+}
@@ -0,0 +1,170 @@
+// Jzazbz colour difference implementation.
+// Copyright (c) 2026 Michał Nazarewicz <mina86@mina86.com>
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in
+// all copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+
+//! Implementation of the ΔEz colour distance based on the Jzazbz colour space.
+//!
+//! Jzazbz (Safdar et al., 2017) is a colour space designed for high dynamic
+//! range and wide gamut imagery.  It offers better perceptual uniformity than
+//! L\*a\*b\* over a range of luminance levels far exceeding that of
+//! conventional displays.  The ΔEz colour difference is the Euclidean distance
+//! in Jz/az/bz coordinates and as such it is a metric in the mathematical
+//! sense.
+//!
+//! Note that the magnitude of ΔEz differs from ΔE\*ab.  A just noticeable
+//! difference is in the order of 0.002–0.004 rather than 1.
+//!
+//! Unlike L\*a\*b\*, Jzazbz is defined in terms of absolute luminance.  Since
+//! the arguments of the functions are L\*a\*b\* colours, they are converted
+//! into XYZ coordinates relative to D65 white point which is then assumed to
+//! have luminance given by `peak_nits` argument (or [`DEFAULT_PEAK_NITS`] if
+//! not specified).
+
+use crate::float::Float;
+
+/// Luminance in cd/m² of the reference white used by [`diff`].
+///
+/// This is the reference white level for HDR content recommended by ITU-R
+/// BT.2408.
+pub const DEFAULT_PEAK_NITS: f32 = 203.0;
+
+/// Returns the ΔEz colour difference between two L\*a\*b\* colours assuming
+/// reference white of [`DEFAULT_PEAK_NITS`] luminance.
+///
+/// ## Example
+///
+/// ```
+/// use empfindung::jzazbz;
+///
+/// let colour_1 = (38.972, 58.991, 37.138);
+/// let colour_2 = (54.528, 42.416, 54.497);
+/// let delta_e = jzazbz::diff(colour_1, colour_2);
+/// approx::assert_abs_diff_eq!(0.048504, delta_e, epsilon = 0.00001);
+/// ```
+pub fn diff(colour_1: impl crate::ToLab, colour_2: impl crate::ToLab) -> f32 {
+    diff_with_peak(colour_1, colour_2, DEFAULT_PEAK_NITS)
+}
+
+/// Returns the ΔEz colour difference between two L\*a\*b\* colours assuming
+/// reference white of given luminance.
+///
+/// `peak_nits` is the luminance in cd/m² (nits) of the L\*=100 white.
+///
+/// ## Example
+///
+/// ```
+/// use empfindung::jzazbz;
+///
+/// let colour_1 = (38.972, 58.991, 37.138);
+/// let colour_2 = (54.528, 42.416, 54.497);
+/// let delta_e = jzazbz::diff_with_peak(colour_1, colour_2, 1000.0);
+/// approx::assert_abs_diff_eq!(0.076852, delta_e, epsilon = 0.00001);
+/// ```
+pub fn diff_with_peak(
+    colour_1: impl crate::ToLab,
+    colour_2: impl crate::ToLab,
+    peak_nits: f32,
+) -> f32 {
+    let colour_1 = from_lab(colour_1.to_lab(), peak_nits);
+    let colour_2 = from_lab(colour_2.to_lab(), peak_nits);
+    let dj = colour_1.0 - colour_2.0;
+    let da = colour_1.1 - colour_2.1;
+    let db = colour_1.2 - colour_2.2;
+    Float::sqrt(dj * dj + da * da + db * db)
+}
+
+/// Converts L\*a\*b\* colour into Jzazbz coordinates assuming reference white
+/// of given luminance.
+fn from_lab(lab: (f32, f32, f32), peak_nits: f32) -> (f32, f32, f32) {
+    const B: f32 = 1.15;
+    const G: f32 = 0.66;
+    const P: f32 = 1.7 * 2523.0 / 32.0;
+    const D: f32 = -0.56;
+    const D_0: f32 = 1.629_55e-11;
+
+    let (x, y, z) = crate::xyz::from_lab(lab, crate::xyz::D65);
+    let (x, y, z) = (x * peak_nits, y * peak_nits, z * peak_nits);
+    let x_prime = B * x - (B - 1.0) * z;
+    let y_prime = G * y - (G - 1.0) * x;
+
+    let l = 0.41478972 * x_prime + 0.579999 * y_prime + 0.0146480 * z;
+    let m = -0.2015100 * x_prime + 1.120649 * y_prime + 0.0531008 * z;
+    let s = -0.0166008 * x_prime + 0.264800 * y_prime + 0.6684799 * z;
+    let pq = |v| crate::xyz::pq(v, P);
+    let (l, m, s) = (pq(l), pq(m), pq(s));
+
+    let i = 0.5 * l + 0.5 * m;
+    let a = 3.524000 * l - 4.066708 * m + 0.542708 * s;
+    let b = 0.199076 * l + 1.096799 * m - 1.295875 * s;
+    let j = (1.0 + D) * i / (1.0 + D * i) - D_0;
+    (j, a, b)
+}
+
+#[cfg(test)]
+mod tests {
+    // Reference values computed with double precision from formulæ given by
+    // Safdar et al.  The columns are: ΔEz with reference white of 203, 100 and
+    // 1000 cd/m² and the two colours.
+    type Lab = (f32, f32, f32);
+
+    #[rustfmt::skip]
+    static TESTS: [((f32, f32, f32), Lab, Lab); 7] = [
+        ((0.048504, 0.038519, 0.076852), (38.972, 58.991, 37.138), (54.528, 42.416, 54.497)),
+        ((0.067657, 0.054149, 0.109909), (50.000,  2.500,  0.000), (73.000, 25.000, -18.000)),
+        ((0.006823, 0.004165, 0.018667), ( 0.000,  0.000,  0.000), ( 1.000,  0.000,   0.000)),
+        ((0.110410, 0.080430, 0.213699), ( 0.000,  0.000,  0.000), (50.000,  0.000,   0.000)),
+        ((0.003839, 0.003154, 0.005441), (50.000,  0.000,  0.000), (50.000, -1.000,   2.000)),
+        ((0.222065, 0.167173, 0.409123), (100.00,  0.000,  0.000), ( 0.000,  0.000,   0.000)),
+        ((0.007483, 0.006156, 0.010565), (22.723, 20.090, -46.694), (23.033, 14.973, -42.562)),
+    ];
+
+    #[test]
+    fn test_difference() {
+        for &(want, colour_1, colour_2) in TESTS.iter() {
+            for (want, peak) in
+                [(want.0, 203.0), (want.1, 100.0), (want.2, 1000.0)]
+            {
+                let got = super::diff_with_peak(colour_1, colour_2, peak);
+                approx::assert_abs_diff_eq!(want, got, epsilon = 0.00001);
+            }
+        }
+    }
+
+    #[test]
+    fn test_black() {
+        let (j, a, b) = super::from_lab((0.0, 0.0, 0.0), 10000.0);
+        approx::assert_abs_diff_eq!(0.0, j, epsilon = 1e-9);
+        approx::assert_abs_diff_eq!(0.0, a, epsilon = 1e-9);
+        approx::assert_abs_diff_eq!(0.0, b, epsilon = 1e-9);
+    }
+
+    #[test]
+    fn test_negative_radicand() {
+        // Highly saturated blue maps to negative XYZ coordinates.
+        let got = super::diff((10.0, 100.0, -200.0), (10.0, -100.0, 200.0));
+        assert!(got.is_finite(), "{}", got);
+    }
+
+    #[test]
+    fn test_zero() { crate::testutil::do_test_zero(super::diff) }
+
+    #[test]
+    fn test_symmetric() { crate::testutil::do_test_symmetric(super::diff) }
+}
@@ -0,0 +1,123 @@
+// L*a*b* colour type with named coordinates.
+// Copyright (c) 2026 Michał Nazarewicz <mina86@mina86.com>
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in
+// all copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+
+use crate::ToLab;
+
+/// A colour in L\*a\*b\* colour space.
+///
+/// The type is equivalent to `(f32, f32, f32)` tuple but with `approx`
+/// feature enabled it additionally implements `approx::AbsDiffEq` trait where
+/// epsilon is interpreted as a CIE76 colour difference threshold.  That is,
+/// two colours are considered equal if [`cie76::diff`](crate::cie76::diff)
+/// between them is at most epsilon.  This is useful in tests and when
+/// deduplicating near-identical colours.  The default epsilon is
+/// `f32::EPSILON`.
+///
+/// ## Example
+///
+/// ```
+/// use empfindung::LabColour;
+///
+/// let colour_1 = LabColour::new(50.0, 10.0, -10.0);
+/// let colour_2 = LabColour::from((50.3, 10.4, -10.0));
+/// let delta_e = empfindung::cie76::diff(colour_1, colour_2);
+/// approx::assert_abs_diff_eq!(0.5, delta_e, epsilon = 0.0001);
+/// ```
+#[cfg_attr(
+    feature = "approx",
+    doc = r#"
+With `approx` feature enabled, the colours can be compared using `approx`
+crate:
+
+```
+use empfindung::LabColour;
+
+let colour_1 = LabColour::new(50.0, 10.0, -10.0);
+let colour_2 = LabColour::new(50.3, 10.4, -10.0);
+approx::assert_abs_diff_eq!(colour_1, colour_2, epsilon = 1.0);
+approx::assert_abs_diff_ne!(colour_1, colour_2, epsilon = 0.1);
+```
+"#
+)]
+#[derive(Clone, Copy, PartialEq, Debug, Default)]
+pub struct LabColour {
+    /// The lightness L\* in the `0..=100` range.
+    pub l: f32,
+    /// The a\* coordinate.  Colours within the sRGB gamut fall roughly in the
+    /// `-87..=99` range.
+    pub a: f32,
+    /// The b\* coordinate.  Colours within the sRGB gamut fall roughly in the
+    /// `-108..=95` range.
+    pub b: f32,
+}
+
+impl LabColour {
+    /// Constructs a new colour from its L\*, a\* and b\* coordinates.
+    pub const fn new(l: f32, a: f32, b: f32) -> Self { Self { l, a, b } }
+}
+
+impl From<(f32, f32, f32)> for LabColour {
+    fn from((l, a, b): (f32, f32, f32)) -> Self { Self { l, a, b } }
+}
+
+impl ToLab for LabColour {
+    #[inline]
+    fn to_lab(&self) -> (f32, f32, f32) { (self.l, self.a, self.b) }
+}
+
+#[cfg(feature = "approx")]
+impl approx::AbsDiffEq for LabColour {
+    type Epsilon = f32;
+
+    fn default_epsilon() -> f32 { f32::EPSILON }
+
+    /// Returns whether CIE76 colour difference between the colours is at most
+    /// `epsilon`.
+    fn abs_diff_eq(&self, other: &Self, epsilon: f32) -> bool {
+        crate::cie76::diff(self, other) <= epsilon
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_lab_colour() {
+        let colour = LabColour::new(50.0, 10.0, -10.0);
+        assert_eq!((50.0, 10.0, -10.0), colour.to_lab());
+        assert_eq!(colour, LabColour::from((50.0, 10.0, -10.0)));
+
+        #[cfg(feature = "approx")]
+        {
+            use approx::AbsDiffEq;
+
+            // 0.5 ΔE apart.
+            let other = LabColour::new(50.3, 10.4, -10.0);
+            assert!(colour.abs_diff_eq(&other, 1.0));
+            assert!(colour.abs_diff_eq(&other, 0.51));
+            assert!(!colour.abs_diff_eq(&other, 0.49));
+            approx::assert_abs_diff_eq!(colour, other, epsilon = 1.0);
+            approx::assert_abs_diff_ne!(colour, other);
+            approx::assert_abs_diff_eq!(colour, colour);
+        }
+    }
+}
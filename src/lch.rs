@@ -0,0 +1,157 @@
+// Cylindrical L*a*b* representation.
+// Copyright (c) 2026 Michał Nazarewicz <mina86@mina86.com>
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in
+// all copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+
+use crate::float::{Float, FloatExt};
+use crate::ToLab;
+
+/// A colour in the cylindrical L\*C\*h° representation of the L\*a\*b\*
+/// colour space.
+///
+/// `c` is the chroma and `h_deg` is the hue angle in degrees.  Hue outside
+/// of the `0..360` range is wrapped.  When converting to L\*a\*b\*, `a =
+/// c · cos(h)` and `b = c · sin(h)`.
+///
+/// ## Example
+///
+/// ```
+/// use empfindung::{cie00, LchAb};
+///
+/// let colour_1 = LchAb { l: 50.0, c: 40.0, h_deg: 30.0 };
+/// let colour_2 = LchAb { l: 50.0, c: 40.0, h_deg: 390.0 };
+/// approx::assert_abs_diff_eq!(0.0, cie00::diff(colour_1, colour_2),
+///                             epsilon = 0.001);
+///
+/// let colour_2 = LchAb { l: 50.0, c: 40.0, h_deg: 60.0 };
+/// approx::assert_abs_diff_eq!(14.76, cie00::diff(colour_1, colour_2),
+///                             epsilon = 0.01);
+/// ```
+#[derive(Clone, Copy, PartialEq, Debug, Default)]
+pub struct LchAb {
+    /// The lightness L\*, same as in L\*a\*b\*.
+    pub l: f32,
+    /// The chroma C\*ab, i.e. distance from the neutral axis.
+    pub c: f32,
+    /// The hue angle h_ab in degrees.
+    pub h_deg: f32,
+}
+
+impl ToLab for LchAb {
+    fn to_lab(&self) -> (f32, f32, f32) {
+        let h_deg = self.h_deg % 360.0;
+        let h_deg = if h_deg < 0.0 { h_deg + 360.0 } else { h_deg };
+        let (sin, cos) = FloatExt::sin_cos(h_deg.to_radians());
+        (self.l, self.c * cos, self.c * sin)
+    }
+}
+
+/// Returns chroma C\*ab, i.e. `sqrt(a² + b²)`, of a colour.
+///
+/// ## Example
+///
+/// ```
+/// assert_eq!(5.0, empfindung::chroma((50.0, 3.0, -4.0)));
+/// ```
+pub fn chroma(colour: impl ToLab) -> f32 {
+    let (_, a, b) = colour.to_lab();
+    Float::hypot(a, b)
+}
+
+/// Returns hue angle h_ab of a colour in degrees in the `0..360` range.
+///
+/// Hue of achromatic colours (i.e. those with a\* and b\* equal zero) is
+/// undefined; the function returns zero for them.
+///
+/// ## Example
+///
+/// ```
+/// use empfindung::hue_deg;
+///
+/// assert_eq!(90.0, hue_deg((50.0, 0.0, 20.0)));
+/// assert_eq!(270.0, hue_deg((50.0, 0.0, -20.0)));
+/// assert_eq!(0.0, hue_deg((50.0, 0.0, 0.0)));
+/// ```
+pub fn hue_deg(colour: impl ToLab) -> f32 {
+    let (_, a, b) = colour.to_lab();
+    if a == 0.0 && b == 0.0 {
+        return 0.0;
+    }
+    let h_deg = Float::atan2(b, a).to_degrees();
+    if h_deg < 0.0 {
+        // Adding 360 to a tiny negative angle may round to 360.
+        let h_deg = h_deg + 360.0;
+        if h_deg < 360.0 {
+            h_deg
+        } else {
+            0.0
+        }
+    } else {
+        h_deg
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_to_lab() {
+        for (h_deg, a, b) in [
+            (0.0, 20.0, 0.0),
+            (90.0, 0.0, 20.0),
+            (180.0, -20.0, 0.0),
+            (270.0, 0.0, -20.0),
+            (360.0, 20.0, 0.0),
+            (-90.0, 0.0, -20.0),
+            (450.0, 0.0, 20.0),
+            (-720.0, 20.0, 0.0),
+            (3600045.0, 14.142136, 14.142136),
+        ] {
+            let got = LchAb { l: 50.0, c: 20.0, h_deg }.to_lab();
+            assert_eq!(50.0, got.0);
+            approx::assert_abs_diff_eq!(a, got.1, epsilon = 0.0001);
+            approx::assert_abs_diff_eq!(b, got.2, epsilon = 0.0001);
+        }
+    }
+
+    #[test]
+    fn test_chroma_and_hue() {
+        for (a, b, h_deg) in [
+            (20.0, 0.0, 0.0),
+            (0.0, 20.0, 90.0),
+            (-20.0, 0.0, 180.0),
+            (0.0, -20.0, 270.0),
+            (14.142136, 14.142136, 45.0),
+            (14.142136, -14.142136, 315.0),
+        ] {
+            let colour = (50.0, a, b);
+            let lch = LchAb { l: 50.0, c: 20.0, h_deg };
+            approx::assert_abs_diff_eq!(20.0, chroma(colour), epsilon = 1e-4);
+            approx::assert_abs_diff_eq!(h_deg, hue_deg(colour), epsilon = 1e-4);
+            approx::assert_abs_diff_eq!(h_deg, hue_deg(lch), epsilon = 1e-3);
+        }
+
+        assert_eq!(0.0, chroma((50.0, 0.0, 0.0)));
+        assert_eq!(0.0, hue_deg((50.0, 0.0, 0.0)));
+        assert_eq!(0.0, hue_deg((50.0, -0.0, -0.0)));
+        let got = hue_deg((50.0, 1.0, -1e-30));
+        assert!((0.0..360.0).contains(&got), "{}", got);
+    }
+}
@@ -27,7 +27,10 @@
 //! stands for German ‘Empfindung’).
 //!
 //! The crate provides CIEDE2000 (in [`cie00`] module), CIE94 (in [`cie94`]),
-//! CIE76 (in [`cie76`] module) and CMC l:c (in [`cmc`] module) implementations.
+//! CIE76 (in [`cie76`] module), CMC l:c (in [`cmc`] module), DIN99 (in
+//! [`din99`] module), HyAB (in [`hyab`] module), ΔEz (in [`jzazbz`] module),
+//! ΔE ITP (in [`itp`] module), OSA-UCS (in [`osa_ucs`] module) and ΔE\*uv (in
+//! [`luv`] module) implementations.
 //!
 //! ## Example
 //!
@@ -72,7 +75,14 @@ approx::assert_abs_diff_eq!(58.90164, delta_e, epsilon = 0.001);
 //!
 //! ## Crate Features
 //!
-//! The crate defines `lab` and `rgb` features which are enabled by default.
+//! The crate defines `std`, `lab` and `rgb` features which are enabled by
+//! default.
+//!
+//! Disabling the `std` feature makes the crate `no_std` (though it still
+//! requires `alloc`).  In that case the crate uses the `libm` crate for
+//! functions such as square root or sine which are otherwise provided by the
+//! standard library.  The `libm` feature is only needed to enable the same
+//! functionality in the `palette` crate when `palette` feature is used.
 //!
 //! With both of them enabled, create provides [`ToLab`] implementation for
 //! `rgb::RGB<u8>` type which means that `diff` functions can be used with
@@ -81,30 +91,653 @@ approx::assert_abs_diff_eq!(58.90164, delta_e, epsilon = 0.001);
 //! Furthermore, if `lab` enabled the `diff` functions can accept `lab::Lab`
 //! argument and `diff_rgb` functions as well as `DE2000` is provided.  Note
 //! that the latter two are a deprecated features.
+//!
+//! Enabling the `no-deprecated` feature removes all deprecated items (such as
+//! `diff_rgb` functions, `DE2000` type or `KSubParams` aliases) from the
+//! crate.  All of them have non-deprecated replacements: `diff` functions
+//! accepting `rgb::RGB8` arguments and `Params` types.
+//!
+//! The optional `fast-trig` feature makes CIEDE2000 implementation use
+//! a lookup table with linear interpolation in place of sine and cosine
+//! functions in the hue-dependent terms of the formula.  On the test data
+//! published by Sharma et al. this changes the results by at most 0.00025
+//! while the calculation gets around 14% faster (as measured by the `diff`
+//! benchmark on x86-64).
+//!
+//! The optional `simd` feature provides `cie00::diff_slice_simd` function
+//! which calculates CIEDE2000 for eight colour pairs at a time.  It works on
+//! stable compilers; on nightly, building with `--cfg empfindung_portable_simd`
+//! makes it use portable SIMD.
+//!
+//! The optional `image` feature provides [`ToLab`] implementation for
+//! `image::Rgb<u8>` type and enables `image_diff` module with functions
+//! operating on whole images.
+//!
+//! The optional `rayon` feature provides `batch::par_diff_many` function
+//! which calculates colour differences in parallel using the `rayon` crate.
+//!
+//! The optional `palette` feature provides [`ToLab`] implementations for
+//! `palette::Lab<D65, f32>`, `palette::Srgb<u8>` and `palette::Srgb<f32>`
+//! types.
+//!
+//! The optional `spectral` feature enables `spectral` module with `Spectrum`
+//! type which describes colours by their reflectance spectra.
+//!
+//! The optional `approx` feature implements `approx::AbsDiffEq` for
+//! [`LabColour`] type which allows comparing colours within a given colour
+//! difference.
+
+// Colorimetric constants are often tabulated with more digits than f32 can
+// represent.  Keep them as published rather than truncating.
+#![allow(clippy::excessive_precision)]
+// `Option::is_none_or` suggested by the lint needs Rust 1.82.  Stick with
+// `map_or` so older compilers can build the crate.
+#![allow(clippy::unnecessary_map_or)]
+#![cfg_attr(
+    all(feature = "simd", empfindung_portable_simd),
+    feature(portable_simd)
+)]
+#![cfg_attr(all(not(feature = "std"), not(test)), no_std)]
+
+extern crate alloc;
 
+use float::{Float, FloatExt};
+
+pub mod batch;
 pub mod cie00;
 pub mod cie76;
 pub mod cie94;
 pub mod cmc;
+pub mod din99;
+pub mod hyab;
+#[cfg(feature = "image")]
+pub mod image_diff;
+pub mod itp;
+pub mod jzazbz;
+pub mod luv;
+pub mod naming;
+pub mod osa_ucs;
+pub mod set;
+#[cfg(feature = "spectral")]
+pub mod spectral;
+pub mod xyz;
 
+#[cfg(not(feature = "no-deprecated"))]
 #[doc(hidden)]
 pub use cie00 as de2000;
+#[cfg(not(feature = "no-deprecated"))]
 #[allow(deprecated)]
 pub use cie00::DE2000;
 
-
 /// Object which can be converted to L\*a\*\b* colour representation.
+///
+/// The trait is implemented for references to types implementing it which
+/// means that functions taking `impl ToLab` arguments accept colours both by
+/// value and by reference.  The latter includes references to unsized types
+/// such as `&[f32]` or `&dyn ToLab`.
+///
+/// Note that `[f32; 3]` arrays hold L\*a\*b\* coordinates while `[u8; 3]`
+/// arrays (with `lab` and `rgb` features enabled) are treated as sRGB colours
+/// the same way `rgb::RGB8` is.
+///
+/// ## Example
+///
+/// ```
+/// use empfindung::{cie00, ToLab};
+///
+/// let colour_1 = (38.972, 58.991, 37.138);
+/// let colour_2 = [54.528, 42.416, 54.497];
+/// let delta_e = cie00::diff(colour_1, colour_2);
+/// assert_eq!(delta_e, cie00::diff(&colour_1, &colour_2));
+/// assert_eq!(delta_e, cie00::diff(&colour_1 as &dyn ToLab, &colour_2[..]));
+/// ```
 pub trait ToLab {
     /// Returns L\*, a\* and b\* coordinates of a colour.
     fn to_lab(&self) -> (f32, f32, f32);
 }
 
-impl<T: ToLab> ToLab for &T {
+impl<T: ToLab + ?Sized> ToLab for &T {
     #[inline]
     fn to_lab(&self) -> (f32, f32, f32) { (*self).to_lab() }
 }
 
+/// Object which can be converted to L\*a\*b\* representation with double
+/// precision.
+///
+/// This is used by the `diff_f64` functions.  The trait is implemented for
+/// `(f64, f64, f64)` tuples and `[f64; 3]` arrays as well as for all types
+/// implementing [`ToLab`] (whose coordinates are converted from `f32`).
+pub trait ToLab64 {
+    /// Returns L\*, a\* and b\* coordinates of a colour.
+    fn to_lab64(&self) -> (f64, f64, f64);
+}
+
+impl<T: ToLab> ToLab64 for T {
+    #[inline]
+    fn to_lab64(&self) -> (f64, f64, f64) {
+        let (l, a, b) = self.to_lab();
+        (l.into(), a.into(), b.into())
+    }
+}
+
+impl ToLab64 for (f64, f64, f64) {
+    #[inline]
+    fn to_lab64(&self) -> (f64, f64, f64) { *self }
+}
+
+impl ToLab64 for [f64; 3] {
+    #[inline]
+    fn to_lab64(&self) -> (f64, f64, f64) { (self[0], self[1], self[2]) }
+}
+
+/// A colour with cached L\*a\*b\* coordinates.
+///
+/// Converting colours into L\*a\*b\* (especially from sRGB) may take
+/// significant portion of time spent calculating colour differences.  When
+/// the same colour is compared with many other colours, wrapping it in this
+/// type makes the conversion happen only once, when the object is
+/// constructed.  [`ToLab`] implementation returns the cached coordinates.
+///
+/// ## Example
+///
+/// ```
+/// use empfindung::{cie00, Cached};
+///
+/// let colour = Cached::new([38.972, 58.991, 37.138]);
+/// let delta_e = cie00::diff(&colour, (54.528, 42.416, 54.497));
+/// approx::assert_abs_diff_eq!(20.553642, delta_e, epsilon = 0.001);
+/// assert_eq!(&[38.972, 58.991, 37.138], colour.colour());
+/// ```
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub struct Cached<C> {
+    colour: C,
+    lab: (f32, f32, f32),
+}
+
+impl<C: ToLab> Cached<C> {
+    /// Converts the colour into L\*a\*b\* and returns it with the
+    /// coordinates cached.
+    pub fn new(colour: C) -> Self {
+        let lab = colour.to_lab();
+        Self { colour, lab }
+    }
+}
+
+impl<C> Cached<C> {
+    /// Returns the original colour.
+    pub fn colour(&self) -> &C { &self.colour }
+
+    /// Returns the original colour consuming the object.
+    pub fn into_inner(self) -> C { self.colour }
+}
+
+impl<C> ToLab for Cached<C> {
+    #[inline]
+    fn to_lab(&self) -> (f32, f32, f32) { self.lab }
+}
+
+/// Sequence of colours which can be converted to L\*a\*b\* representation.
+///
+/// The trait abstracts over the way a collection of colours is stored so that
+/// functions operating on many colours (such as those in [`set`] module) can
+/// accept slices, vectors, arrays or arbitrary iterators without forcing
+/// a particular memory layout or an intermediate copy.  It is implemented for
+/// all types which implement [`IntoIterator`] with items implementing
+/// [`ToLab`].  To make a custom container usable with such functions,
+/// implement [`IntoIterator`] for it (or for a reference to it).
+///
+/// ## Example
+///
+/// ```
+/// use empfindung::Colours;
+///
+/// let colours = [(50.0, 0.0, 0.0), (60.0, 10.0, -10.0)];
+/// let labs = colours.iter().lab_iter().collect::<Vec<_>>();
+/// assert_eq!(&colours[..], &labs[..]);
+/// ```
+pub trait Colours {
+    /// Iterator over L\*a\*b\* coordinates of the colours.
+    type LabIter: Iterator<Item = (f32, f32, f32)>;
+
+    /// Returns an iterator over L\*, a\* and b\* coordinates of the colours.
+    fn lab_iter(self) -> Self::LabIter;
+}
+
+impl<I> Colours for I
+where
+    I: IntoIterator,
+    I::Item: ToLab,
+{
+    type LabIter = core::iter::Map<I::IntoIter, fn(I::Item) -> (f32, f32, f32)>;
+
+    fn lab_iter(self) -> Self::LabIter {
+        fn to_lab<C: ToLab>(colour: C) -> (f32, f32, f32) { colour.to_lab() }
+        self.into_iter().map(to_lab::<I::Item> as fn(_) -> _)
+    }
+}
+
+/// Estimates CIEDE2000 colour difference from a CIE76 colour difference.
+///
+/// This is a crude approximation useful only for back-of-the-envelope
+/// conversions when the colours themselves are not available (e.g. when only
+/// ΔE₇₆ values were logged).  The two metrics weigh lightness, chroma and hue
+/// differently so there is no function mapping one into the other.  Whenever
+/// possible, calculate the colour difference with [`cie00::diff`] instead.
+///
+/// The function uses a power curve `0.8046 · ΔE₇₆^0.9606` fitted (by least
+/// squares in log-log space) to the CIEDE2000 test data published by Sharma
+/// et al.  On that data set the mean relative error of the estimate is 46%
+/// with the worst case of 141%.  Note that the data set deliberately exercises
+/// edge cases of the formula so the errors for typical colours are smaller.
+///
+/// ## Example
+///
+/// ```
+/// let delta_e = empfindung::approximate_cie00_from_cie76(28.601656);
+/// approx::assert_abs_diff_eq!(20.16, delta_e, epsilon = 0.01);
+/// ```
+pub fn approximate_cie00_from_cie76(delta_e: f32) -> f32 {
+    APPROX_SCALE * FloatExt::powf(delta_e, APPROX_EXPONENT)
+}
+
+/// Estimates CIE76 colour difference from a CIEDE2000 colour difference.
+///
+/// This is an inverse of [`approximate_cie00_from_cie76`] and the same
+/// caveats apply.  The approximation is crude and should be used only when
+/// recomputing the difference from colours is not possible.
+///
+/// ## Example
+///
+/// ```
+/// let delta_e = empfindung::approximate_cie76_from_cie00(20.553642);
+/// approx::assert_abs_diff_eq!(29.18, delta_e, epsilon = 0.01);
+/// ```
+pub fn approximate_cie76_from_cie00(delta_e: f32) -> f32 {
+    FloatExt::powf(delta_e / APPROX_SCALE, 1.0 / APPROX_EXPONENT)
+}
+
+const APPROX_SCALE: f32 = 0.8046;
+const APPROX_EXPONENT: f32 = 0.9606;
+
+/// Returns an approximation of a square root of a non-negative number.
+///
+/// The function uses the well known bit-level approximation of an inverse
+/// square root refined with one iteration of Newton’s method.  It avoids both
+/// square root and division instructions which makes it faster on targets
+/// without hardware support for them.  The relative error of the result is
+/// below 0.18% (i.e. ΔE of 50 may be off by about 0.09) which is usually
+/// negligible compared to accuracy of the colour difference formulæ
+/// themselves.  Result for negative arguments is unspecified.
+///
+/// The function is meant to be passed to `diff_with_sqrt` functions (e.g.
+/// [`cie76::diff_with_sqrt`]) which use it for the final square root of the
+/// sum of squared components.
+///
+/// ## Example
+///
+/// ```
+/// approx::assert_relative_eq!(
+///     5.0, empfindung::fast_sqrt(25.0), max_relative = 0.002);
+/// assert_eq!(0.0, empfindung::fast_sqrt(0.0));
+/// ```
+pub fn fast_sqrt(x: f32) -> f32 {
+    let y = f32::from_bits(0x5f37_5a86 - (x.to_bits() >> 1));
+    // Multiply by x first so that zero argument yields zero rather than NaN.
+    let xy = x * y;
+    xy * (1.5 - 0.5 * xy * y)
+}
+
+/// Weighted lightness, chroma and hue terms of a colour difference.
+///
+/// The terms are the values which are squared and summed to calculate the
+/// colour difference (e.g. ΔL′/(k_L·S_L) for lightness in CIEDE2000).  They
+/// are signed and their magnitudes show whether the difference is dominated
+/// by lightness, chroma or hue.  `rotation` is the R_T rotation factor of
+/// the CIEDE2000 formula which multiplies rather than adds to the terms.
+///
+/// Objects of this type are returned by `diff_components` functions such as
+/// [`cie00::diff_components`].  [`Components::delta_e`] recombines the terms
+/// into the colour difference.
+#[derive(Clone, Copy, PartialEq, Debug, Default)]
+pub struct Components {
+    /// The weighted lightness difference term L taken before squaring, e.g.
+    /// ΔL′/(k_L·S_L) in CIEDE2000.  Its sign depends on the order of the
+    /// colours.
+    pub lightness: f32,
+    /// The weighted chroma difference term C taken before squaring, e.g.
+    /// ΔC′/(k_C·S_C) in CIEDE2000.  Its sign depends on the order of the
+    /// colours.
+    pub chroma: f32,
+    /// The weighted hue difference term H taken before squaring, e.g.
+    /// ΔH′/(k_H·S_H) in CIEDE2000.  Formulæ which derive ΔH from the other
+    /// differences (CIE94 and CMC) always return a non-negative value.
+    pub hue: f32,
+    /// The rotation factor R, i.e. R_T in CIEDE2000.  Unlike the other fields
+    /// it isn’t a term which is squared but a multiplier of the chroma and hue
+    /// interaction term R·C·H which is added to the sum of squares.  Zero for
+    /// formulæ which don’t have it.
+    pub rotation: f32,
+}
+
+impl Components {
+    /// Returns the colour difference, i.e. `sqrt(L² + C² + H² + R·C·H)`.
+    pub fn delta_e(&self) -> f32 {
+        Float::sqrt(
+            Float::powi(self.lightness, 2) +
+                Float::powi(self.chroma, 2) +
+                Float::powi(self.hue, 2) +
+                self.rotation * self.chroma * self.hue,
+        )
+    }
+}
+
+/// Colour differences between two colours calculated with commonly used
+/// formulæ and parameters.  See [`all_metrics`].
+#[derive(Clone, Copy, PartialEq, Debug, Default)]
+pub struct AllMetrics {
+    /// CIE76 colour difference; see [`cie76::diff`].
+    pub cie76: f32,
+    /// CIE94 colour difference with parameters for graphic arts.
+    pub cie94_graphic: f32,
+    /// CIE94 colour difference with parameters for textiles.
+    pub cie94_textiles: f32,
+    /// CIEDE2000 colour difference with default parameters.
+    pub cie00: f32,
+    /// CIEDE2000 colour difference with parameters by Yang et al.; see
+    /// [`cie00::Params::yang2012`].
+    pub cie00_yang: f32,
+    /// CMC 1:1 colour difference.
+    pub cmc_11: f32,
+    /// CMC 2:1 colour difference.
+    pub cmc_21: f32,
+}
+
+/// Returns colour differences between two colours calculated with commonly
+/// used formulæ and parameters.
+///
+/// Each colour is converted to L\*a\*b\* only once.  For the asymmetric
+/// formulæ (CIE94 and CMC l:c), `reference` is passed as the first argument.
+///
+/// ## Example
+///
+/// ```
+/// let colour_1 = (38.972, 58.991, 37.138);
+/// let colour_2 = (54.528, 42.416, 54.497);
+/// let metrics = empfindung::all_metrics(colour_1, colour_2);
+/// approx::assert_abs_diff_eq!(28.601656, metrics.cie76, epsilon = 0.001);
+/// approx::assert_abs_diff_eq!(20.553642, metrics.cie00, epsilon = 0.001);
+/// approx::assert_abs_diff_eq!(17.743946, metrics.cmc_21, epsilon = 0.001);
+/// ```
+pub fn all_metrics(reference: impl ToLab, colour: impl ToLab) -> AllMetrics {
+    let reference = reference.to_lab();
+    let colour = colour.to_lab();
+    AllMetrics {
+        cie76: cie76::diff(reference, colour),
+        cie94_graphic: cie94::diff(reference, colour, cie94::Params::GRAPHIC),
+        cie94_textiles: cie94::diff(
+            reference,
+            colour,
+            cie94::Params::TEXTILES,
+        ),
+        cie00: cie00::diff(reference, colour),
+        cie00_yang: cie00::diff_with_params(
+            reference,
+            colour,
+            cie00::Params::yang2012(),
+        ),
+        cmc_11: cmc::diff(reference, colour, cmc::LC11),
+        cmc_21: cmc::diff(reference, colour, cmc::LC21),
+    }
+}
+
+/// Returns range of possible colour differences between a reference colour
+/// known with limited precision and a sample.
+///
+/// The reference colour is given as L\*a\*b\* coordinates together with
+/// their uncertainty such that the true reference lies within the box
+/// `reference.0 ± uncertainty.0`, `reference.1 ± uncertainty.1` and
+/// `reference.2 ± uncertainty.2`.  The function evaluates `diff` (with the
+/// reference passed as the first argument) at each of the eight corners of
+/// the box as well as at the point of the box closest to the sample and
+/// returns the smallest and largest of the results.  If the sample lies
+/// within the box, the closest point is the sample itself and thus the lower
+/// bound is zero.
+///
+/// For CIE76 the bounds are exact.  Other colour difference functions are not
+/// Euclidean distances in L\*a\*b\* space so their extreme values may lie
+/// elsewhere in the box and the result is an approximation which may
+/// overestimate the minimum and underestimate the maximum.  For small
+/// uncertainties typical for measurement devices the error is negligible.
+///
+/// ## Example
+///
+/// ```
+/// use empfindung::cie76;
+///
+/// let (min, max) = empfindung::diff_uncertain(
+///     (50.0, 0.0, 0.0), (1.0, 1.0, 1.0), (60.0, 0.0, 0.0), cie76::diff_fn());
+/// assert_eq!((9.0, 123.0f32.sqrt()), (min, max));
+/// ```
+pub fn diff_uncertain(
+    reference: (f32, f32, f32),
+    uncertainty: (f32, f32, f32),
+    sample: impl ToLab,
+    diff: impl crate::Metric,
+) -> (f32, f32) {
+    let sample = sample.to_lab();
+    let (mut min, mut max) = (f32::INFINITY, 0.0f32);
+    for corner in 0..8 {
+        let pick = |bit: u32, v: f32, u: f32| {
+            if corner & (1 << bit) == 0 {
+                v - u
+            } else {
+                v + u
+            }
+        };
+        let corner = (
+            pick(0, reference.0, uncertainty.0),
+            pick(1, reference.1, uncertainty.1),
+            pick(2, reference.2, uncertainty.2),
+        );
+        let delta_e = diff.diff(corner, sample);
+        min = min.min(delta_e);
+        max = max.max(delta_e);
+    }
+    let closest = |v: f32, r: f32, u: f32| v.clamp(r - u.abs(), r + u.abs());
+    let closest = (
+        closest(sample.0, reference.0, uncertainty.0),
+        closest(sample.1, reference.1, uncertainty.1),
+        closest(sample.2, reference.2, uncertainty.2),
+    );
+    min = min.min(diff.diff(closest, sample));
+    (min, max)
+}
+
+mod diff_key;
+mod float;
+mod hex;
+mod lab_colour;
+mod lch;
+mod metric;
+mod perceptibility;
 mod to_lab_impls;
+#[cfg(feature = "simd")]
+mod simd;
+#[cfg(feature = "fast-trig")]
+mod trig;
+mod validate;
+
+mod srgb;
+
+#[cfg(feature = "rgb")]
+pub use srgb::CompositedRgba;
+#[cfg(feature = "rgb")]
+pub use srgb::{GamutMapped, GamutMapping};
+#[cfg(feature = "rgb")]
+pub use srgb::Matrixed;
+#[cfg(feature = "rgb")]
+pub use srgb::RgbWith;
+#[cfg(feature = "rgb")]
+pub use srgb::suggest_gains;
+#[cfg(feature = "rgb")]
+pub use srgb::{lab_to_srgb, lab_to_srgb_checked};
+#[cfg(feature = "rgb")]
+pub use srgb::rgb8;
+pub use srgb::{LinearRgb, Srgb8Lut};
+
+pub use diff_key::DiffKey;
+pub use hex::{Hex, ParseHexError};
+pub use lab_colour::LabColour;
+pub use lch::{chroma, hue_deg, LchAb};
+pub use metric::{
+    default_diff, Cie00, Cie76, Cie94, Cie94Symmetric, Cmc, CmcSymmetric,
+    DefaultMetric, Metric, MetricProperties,
+};
+pub use perceptibility::{is_just_noticeable, Delta, Perceptibility};
+pub use validate::{validate_lab, LabRangeError};
 
 #[cfg(test)]
 pub(crate) mod testutil;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    #[cfg(all(feature = "lab", feature = "rgb"))]
+    fn test_cached() {
+        let colours = (0..=255)
+            .step_by(15)
+            .map(|c| rgb::RGB8::new(c, 255 - c, c / 3))
+            .collect::<Vec<_>>();
+        let reference = rgb::RGB8::new(234, 76, 76);
+        let cached = Cached::new(reference);
+        assert_eq!(reference.to_lab(), cached.to_lab());
+        assert_eq!(reference, *cached.colour());
+        for colour in colours.iter() {
+            assert_eq!(cie00::diff(reference, colour), cie00::diff(cached, colour));
+            assert_eq!(
+                cie94::diff(colour, reference, cie94::Params::graphic()),
+                cie94::diff(colour, cached, cie94::Params::graphic())
+            );
+            let colour = Cached::new(*colour);
+            assert_eq!(
+                cie76::diff(reference, colour.into_inner()),
+                cie76::diff(cached, colour)
+            );
+        }
+    }
+
+    #[test]
+    fn test_all_metrics() {
+        let colour_1 = (38.972, 58.991, 37.138);
+        let colour_2 = (54.528, 42.416, 54.497);
+        let got = all_metrics(colour_1, colour_2);
+        let (graphic, textiles) =
+            (cie94::Params::graphic(), cie94::Params::textiles());
+        let want = AllMetrics {
+            cie76: cie76::diff(colour_1, colour_2),
+            cie94_graphic: cie94::diff(colour_1, colour_2, graphic),
+            cie94_textiles: cie94::diff(colour_1, colour_2, textiles),
+            cie00: cie00::diff(colour_1, colour_2),
+            cie00_yang: cie00::diff_with_params(
+                colour_1,
+                colour_2,
+                cie00::Params::yang2012(),
+            ),
+            cmc_11: cmc::diff(colour_1, colour_2, cmc::LC11),
+            cmc_21: cmc::diff(colour_1, colour_2, cmc::LC21),
+        };
+        assert_eq!(want, got);
+    }
+
+    #[test]
+    // The borrows are what is being tested.
+    #[allow(clippy::needless_borrows_for_generic_args)]
+    fn test_diff_by_value_and_reference() {
+        let colour_1 = (38.972, 58.991, 37.138);
+        let colour_2 = [54.528, 42.416, 54.497];
+        let ksub94 = cie94::Params::graphic();
+
+        let want = cie76::diff(colour_1, colour_2);
+        assert_eq!(want, cie76::diff(&colour_1, &colour_2));
+        assert_eq!(want, cie76::diff(&colour_1, colour_2));
+        let want = cie94::diff(colour_1, colour_2, ksub94);
+        assert_eq!(want, cie94::diff(&colour_1, &colour_2, ksub94));
+        assert_eq!(want, cie94::diff(colour_1, &&colour_2, ksub94));
+        let want = cie00::diff(colour_1, colour_2);
+        assert_eq!(want, cie00::diff(&colour_1, &colour_2));
+        assert_eq!(want, cie00::diff(&colour_1 as &dyn ToLab, &colour_2[..]));
+        let want = cmc::diff(colour_1, colour_2, cmc::LC21);
+        assert_eq!(want, cmc::diff(&colour_1, &colour_2, cmc::LC21));
+        assert_eq!(want, cmc::diff(&colour_1, &colour_2[..], cmc::LC21));
+    }
+
+    #[test]
+    fn test_diff_uncertain() {
+        let diff = cie00::diff_fn(cie00::Params::default());
+        let reference = (50.0, 20.0, -10.0);
+        let sample = (55.0, 25.0, -5.0);
+
+        // Zero uncertainty gives exact value.
+        let want = diff(reference, sample);
+        let got = diff_uncertain(reference, (0.0, 0.0, 0.0), sample, diff);
+        assert_eq!((want, want), got);
+
+        let got = diff_uncertain(reference, (1.0, 1.0, 1.0), sample, diff);
+        assert!(got.0 < want && want < got.1, "{:?} {}", got, want);
+        let nearest = diff((51.0, 21.0, -9.0), sample);
+        let farthest = diff((49.0, 19.0, -11.0), sample);
+        assert_eq!((nearest, farthest), got);
+
+        // Sample inside of the box.
+        let got = diff_uncertain(reference, (6.0, 6.0, 6.0), sample, diff);
+        assert_eq!(0.0, got.0);
+    }
+
+    #[test]
+    fn test_diff_uncertain_cie76() {
+        let diff = cie76::diff_fn();
+
+        // The closest point of the box lies on its face rather than in a corner.
+        let (reference, sample) = ((50.0, 0.0, 0.0), (60.0, 0.0, 0.0));
+        let got = diff_uncertain(reference, (1.0, 1.0, 1.0), sample, diff);
+        assert_eq!((9.0, 123.0f32.sqrt()), got);
+
+        // The closest point lies on an edge of the box.
+        let reference = (50.0, 20.0, -10.0);
+        let uncertainty = (1.0, 2.0, 3.0);
+        let sample = (50.5, 30.0, 0.0);
+        let got = diff_uncertain(reference, uncertainty, sample, diff);
+        approx::assert_abs_diff_eq!(113.0f32.sqrt(), got.0, epsilon = 0.0001);
+        assert_eq!(diff((49.0, 18.0, -13.0), sample), got.1);
+
+        // Negative uncertainty is treated as its absolute value.
+        let uncertainty = (-1.0, -2.0, -3.0);
+        let got = diff_uncertain(reference, uncertainty, sample, diff);
+        approx::assert_abs_diff_eq!(113.0f32.sqrt(), got.0, epsilon = 0.0001);
+    }
+
+    #[test]
+    fn test_fast_sqrt() {
+        let mut x = 1e-6f32;
+        while x < 1e6 {
+            approx::assert_relative_eq!(
+                x.sqrt(),
+                fast_sqrt(x),
+                max_relative = 0.00176
+            );
+            x *= 1.001;
+        }
+        assert_eq!(0.0, fast_sqrt(0.0));
+    }
+
+    #[test]
+    fn test_approximate_cie_conversion() {
+        for delta_e in [0.0, 0.5, 1.0, 2.0, 10.0, 50.0, 100.0] {
+            let de00 = approximate_cie00_from_cie76(delta_e);
+            let de76 = approximate_cie76_from_cie00(de00);
+            approx::assert_abs_diff_eq!(delta_e, de76, epsilon = 0.001);
+        }
+    }
+}
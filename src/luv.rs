@@ -0,0 +1,105 @@
+// CIELUV colour difference implementation.
+// Copyright (c) 2026 Michał Nazarewicz <mina86@mina86.com>
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in
+// all copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+
+//! Implementation of the CIE 1976 L\*u\*v\* colour distance.
+//!
+//! The ΔE\*uv colour difference is the Euclidean distance in the CIELUV
+//! colour space.  Like [`crate::cie76`], it is a metric in the mathematical
+//! sense.
+//!
+//! Since L\*u\*v\* and L\*a\*b\* are different colour spaces, colours in this
+//! module are represented by a dedicated [`Luv`] type which intentionally
+//! doesn’t implement [`crate::ToLab`].  Distances calculated by this module
+//! are not comparable with those calculated by the L\*a\*b\*-based formulæ.
+
+use crate::float::Float;
+
+/// A colour in the CIE 1976 L\*u\*v\* colour space.
+#[derive(Clone, Copy, PartialEq, Debug, Default)]
+pub struct Luv {
+    /// The lightness L\* in the `0..=100` range.
+    pub l: f32,
+    /// The u\* coordinate.  Colours within the sRGB gamut fall roughly in the
+    /// `-84..=176` range.
+    pub u: f32,
+    /// The v\* coordinate.  Colours within the sRGB gamut fall roughly in the
+    /// `-135..=108` range.
+    pub v: f32,
+}
+
+impl Luv {
+    /// Constructs a new colour with given coordinates.
+    pub const fn new(l: f32, u: f32, v: f32) -> Self { Self { l, u, v } }
+}
+
+/// Returns the ΔE\*uv colour difference between two L\*u\*v\* colours.
+///
+/// ## Example
+///
+/// ```
+/// use empfindung::luv::{self, Luv};
+///
+/// let colour_1 = Luv::new(50.0, 10.0, 20.0);
+/// let colour_2 = Luv::new(55.0, 13.0, 24.0);
+/// approx::assert_abs_diff_eq!(7.0711, luv::diff(colour_1, colour_2),
+///                             epsilon = 0.0001);
+/// ```
+pub fn diff(colour_1: Luv, colour_2: Luv) -> f32 {
+    let dl = colour_1.l - colour_2.l;
+    let du = colour_1.u - colour_2.u;
+    let dv = colour_1.v - colour_2.v;
+    Float::sqrt(dl * dl + du * du + dv * dv)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Luv;
+
+    type Triple = (f32, f32, f32);
+
+    fn diff(colour_1: Triple, colour_2: Triple) -> f32 {
+        let luv = |(l, u, v)| Luv { l, u, v };
+        super::diff(luv(colour_1), luv(colour_2))
+    }
+
+    #[rustfmt::skip]
+    static TESTS: [crate::testutil::TestCase; 5] = [
+        ( 0.0,    (50.0, 10.0, 20.0), (50.0, 10.0, 20.0)),
+        ( 5.0,    (50.0,  0.0,  0.0), (50.0,  3.0,  4.0)),
+        (13.0,    (50.0,  0.0,  0.0), (62.0,  0.0, -5.0)),
+        ( 7.0711, (50.0, 10.0, 20.0), (55.0, 13.0, 24.0)),
+        (38.6782, (30.0, 40.0, -9.0), (60.0, 20.0,  5.0)),
+    ];
+
+    #[test]
+    fn test_difference() {
+        crate::testutil::do_test_difference(&TESTS, diff);
+    }
+
+    #[test]
+    fn test_zero() { crate::testutil::do_test_zero(diff) }
+
+    #[test]
+    fn test_symmetric() { crate::testutil::do_test_symmetric(diff) }
+
+    #[test]
+    fn test_triangle() { crate::testutil::do_test_triangle(diff) }
+}
@@ -0,0 +1,396 @@
+// Colour difference metrics abstraction.
+// Copyright (c) 2026 Michał Nazarewicz <mina86@mina86.com>
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in
+// all copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+
+use crate::{cie00, cie76, cie94, cmc, ToLab};
+
+/// Colour difference formula.
+///
+/// The trait allows writing code generic over the colour difference formula
+/// or choosing the formula at run time (e.g. through `Box<dyn Metric>`).
+/// Functions operating on many colours (such as those in [`crate::set`] and
+/// [`crate::batch`] modules) accept any `impl Metric`.
+///
+/// The trait is implemented by [`Cie76`], [`Cie94`], [`Cie00`] and [`Cmc`]
+/// types which carry parameters of the respective formula as well as by all
+/// closures taking two L\*a\*b\* colours and returning a `f32` (such as those
+/// returned by `diff_fn` functions).
+///
+/// ## Example
+///
+/// ```
+/// use empfindung::{cie76, Cie00, Cie76, Metric};
+///
+/// fn metric_by_name(name: &str) -> Box<dyn Metric> {
+///     match name {
+///         "cie76" => Box::new(Cie76),
+///         _ => Box::new(Cie00::default()),
+///     }
+/// }
+///
+/// let colour_1 = (38.972, 58.991, 37.138);
+/// let colour_2 = (54.528, 42.416, 54.497);
+/// let metric = metric_by_name("cie76");
+/// assert_eq!(cie76::diff(colour_1, colour_2),
+///            metric.diff(colour_1, colour_2));
+/// ```
+pub trait Metric {
+    /// Returns the colour difference between two L\*a\*b\* colours.
+    ///
+    /// For asymmetric formulæ, `reference` is the colour with respect to
+    /// which the difference is calculated.
+    fn diff(&self, reference: (f32, f32, f32), colour: (f32, f32, f32)) -> f32;
+}
+
+/// Mathematical properties of a colour difference formula.
+///
+/// The properties are exposed as associated constants so that generic code
+/// (e.g. spatial data structures such as BK-trees or vantage-point trees which
+/// rely on the triangle inequality) can check them at compile time.  They are
+/// kept in a separate trait so that [`Metric`] can still be used as a trait
+/// object.
+///
+/// The trait is not implemented for closures since their properties cannot be
+/// known.
+///
+/// ## Example
+///
+/// ```
+/// use empfindung::{Cie00, Cie76, Metric, MetricProperties};
+///
+/// fn require_metric<M: Metric + MetricProperties>(metric: M) -> M {
+///     assert!(M::IS_METRIC, "triangle inequality is required");
+///     metric
+/// }
+///
+/// require_metric(Cie76);
+/// assert!(Cie00::IS_SYMMETRIC);
+/// assert!(!Cie00::IS_METRIC);
+/// ```
+pub trait MetricProperties {
+    /// Whether the formula is a metric in the mathematical sense, i.e. it is
+    /// symmetric and satisfies the triangle inequality.
+    const IS_METRIC: bool;
+    /// Whether the formula is symmetric, i.e. `diff(a, b) == diff(b, a)`.
+    const IS_SYMMETRIC: bool;
+}
+
+impl<F> Metric for F
+where
+    F: Fn((f32, f32, f32), (f32, f32, f32)) -> f32,
+{
+    #[inline]
+    fn diff(&self, reference: (f32, f32, f32), colour: (f32, f32, f32)) -> f32 {
+        self(reference, colour)
+    }
+}
+
+/// The CIE76 colour difference formula; see [`cie76::diff`].
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Default)]
+pub struct Cie76;
+
+impl Metric for Cie76 {
+    #[inline]
+    fn diff(&self, reference: (f32, f32, f32), colour: (f32, f32, f32)) -> f32 {
+        cie76::diff(reference, colour)
+    }
+}
+
+impl MetricProperties for Cie76 {
+    const IS_METRIC: bool = true;
+    const IS_SYMMETRIC: bool = true;
+}
+
+/// The CIE94 colour difference formula with given parameters; see
+/// [`cie94::diff`].
+#[derive(Clone, Copy, PartialEq, Debug, Default)]
+pub struct Cie94(pub cie94::Params);
+
+impl Metric for Cie94 {
+    #[inline]
+    fn diff(&self, reference: (f32, f32, f32), colour: (f32, f32, f32)) -> f32 {
+        cie94::diff(reference, colour, self.0)
+    }
+}
+
+impl Cie94 {
+    /// Returns a symmetric variant of the CIE94 formula with given parameters;
+    /// see [`Cie94Symmetric`].
+    #[inline]
+    pub const fn symmetric(params: cie94::Params) -> Cie94Symmetric {
+        Cie94Symmetric(params)
+    }
+}
+
+impl MetricProperties for Cie94 {
+    const IS_METRIC: bool = false;
+    const IS_SYMMETRIC: bool = false;
+}
+
+/// The CIEDE2000 colour difference formula with given parameters; see
+/// [`cie00::diff_with_params`].
+#[derive(Clone, Copy, PartialEq, Debug, Default)]
+pub struct Cie00(pub cie00::Params);
+
+impl Metric for Cie00 {
+    #[inline]
+    fn diff(&self, reference: (f32, f32, f32), colour: (f32, f32, f32)) -> f32 {
+        cie00::diff_with_params(reference, colour, self.0)
+    }
+}
+
+impl MetricProperties for Cie00 {
+    const IS_METRIC: bool = false;
+    const IS_SYMMETRIC: bool = true;
+}
+
+/// The colour difference formula recommended by the crate.
+///
+/// Currently this is [`Cie00`] with default parameters.  Code which doesn’t
+/// care about a particular formula can use this alias (or [`default_diff`]
+/// function) to follow the recommendation as it changes in future versions
+/// of the crate.  Note that such change will be considered a breaking change.
+///
+/// ## Example
+///
+/// ```
+/// use empfindung::{DefaultMetric, Metric};
+///
+/// let colour_1 = (38.972, 58.991, 37.138);
+/// let colour_2 = (54.528, 42.416, 54.497);
+/// let delta_e = DefaultMetric::default().diff(colour_1, colour_2);
+/// approx::assert_abs_diff_eq!(20.553642, delta_e, epsilon = 0.001);
+/// ```
+pub type DefaultMetric = Cie00;
+
+/// Returns the colour difference between two colours using formula
+/// recommended by the crate; see [`DefaultMetric`].
+///
+/// ## Example
+///
+/// ```
+/// let colour_1 = (38.972, 58.991, 37.138);
+/// let colour_2 = (54.528, 42.416, 54.497);
+/// let delta_e = empfindung::default_diff(colour_1, colour_2);
+/// approx::assert_abs_diff_eq!(20.553642, delta_e, epsilon = 0.001);
+/// ```
+pub fn default_diff(reference: impl ToLab, colour: impl ToLab) -> f32 {
+    DefaultMetric::default().diff(reference.to_lab(), colour.to_lab())
+}
+
+/// The CMC l:c colour difference formula with given `l` and `c` parameters;
+/// see [`cmc::diff`].
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub struct Cmc(pub f32, pub f32);
+
+impl Metric for Cmc {
+    #[inline]
+    fn diff(&self, reference: (f32, f32, f32), colour: (f32, f32, f32)) -> f32 {
+        cmc::diff(reference, colour, (self.0, self.1))
+    }
+}
+
+impl Cmc {
+    /// Returns a symmetric variant of the CMC l:c formula with given `l` and
+    /// `c` parameters; see [`CmcSymmetric`].
+    #[inline]
+    pub const fn symmetric(l: f32, c: f32) -> CmcSymmetric {
+        CmcSymmetric(l, c)
+    }
+}
+
+impl MetricProperties for Cmc {
+    const IS_METRIC: bool = false;
+    const IS_SYMMETRIC: bool = false;
+}
+
+/// The symmetric variant of the CIE94 colour difference formula with given
+/// parameters; see [`cie94::diff_symmetric`].
+///
+/// The formula is symmetric but still does not satisfy the triangle
+/// inequality.
+///
+/// ## Example
+///
+/// ```
+/// use empfindung::{cie94, Cie94, Cie94Symmetric, Metric, MetricProperties};
+///
+/// let colour_1 = (38.972, 58.991, 37.138);
+/// let colour_2 = (54.528, 42.416, 54.497);
+/// let metric = Cie94::symmetric(cie94::Params::graphic());
+/// assert!(<Cie94Symmetric as MetricProperties>::IS_SYMMETRIC);
+/// assert_eq!(metric.diff(colour_1, colour_2),
+///            metric.diff(colour_2, colour_1));
+/// ```
+#[derive(Clone, Copy, PartialEq, Debug, Default)]
+pub struct Cie94Symmetric(pub cie94::Params);
+
+impl Metric for Cie94Symmetric {
+    #[inline]
+    fn diff(&self, reference: (f32, f32, f32), colour: (f32, f32, f32)) -> f32 {
+        cie94::diff_symmetric(reference, colour, self.0)
+    }
+}
+
+impl MetricProperties for Cie94Symmetric {
+    const IS_METRIC: bool = false;
+    const IS_SYMMETRIC: bool = true;
+}
+
+/// The symmetric variant of the CMC l:c colour difference formula with given
+/// `l` and `c` parameters; see [`cmc::diff_symmetric`].
+///
+/// The formula is symmetric but still does not satisfy the triangle
+/// inequality.
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub struct CmcSymmetric(pub f32, pub f32);
+
+impl Metric for CmcSymmetric {
+    #[inline]
+    fn diff(&self, reference: (f32, f32, f32), colour: (f32, f32, f32)) -> f32 {
+        cmc::diff_symmetric(reference, colour, (self.0, self.1))
+    }
+}
+
+impl MetricProperties for CmcSymmetric {
+    const IS_METRIC: bool = false;
+    const IS_SYMMETRIC: bool = true;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn check(
+        metric: Box<dyn Metric>,
+        want: impl Fn((f32, f32, f32), (f32, f32, f32)) -> f32,
+    ) {
+        for pair in crate::testutil::generate_colours(100).windows(2) {
+            let (a, b) = (pair[0], pair[1]);
+            assert_eq!(want(a, b), metric.diff(a, b));
+        }
+    }
+
+    #[test]
+    fn test_dyn_metric() {
+        let ksub94 = cie94::Params::textiles();
+        let ksub00 = cie00::Params::yang2012();
+        check(Box::new(Cie76), cie76::diff);
+        check(Box::new(Cie94(ksub94)), |a, b| cie94::diff(a, b, ksub94));
+        check(Box::new(Cie00(ksub00)), |a, b| {
+            cie00::diff_with_params(a, b, ksub00)
+        });
+        check(Box::new(Cmc(2.0, 1.0)), |a, b| cmc::diff(a, b, cmc::LC21));
+        check(Box::new(cie00::diff_fn(ksub00)), |a, b| {
+            cie00::diff_with_params(a, b, ksub00)
+        });
+    }
+
+    #[test]
+    fn test_default_diff() {
+        for pair in crate::testutil::generate_colours(100).windows(2) {
+            let (a, b) = (pair[0], pair[1]);
+            assert_eq!(cie00::diff(a, b), default_diff(a, b));
+            assert_eq!(cie00::diff(a, b), DefaultMetric::default().diff(a, b));
+        }
+    }
+
+    /// Checks that properties are consistent with the formula.  Asymmetric
+    /// formulæ are checked to actually be asymmetric; for non-metrics the
+    /// triangle inequality is not checked since violations are rare and need
+    /// carefully chosen colours (see `cie00::tests::test_not_triangle`).
+    fn check_properties<M: Metric + MetricProperties>(metric: M) {
+        assert!(!M::IS_METRIC || M::IS_SYMMETRIC);
+        let diff = |a, b| metric.diff(a, b);
+        if M::IS_SYMMETRIC {
+            crate::testutil::do_test_symmetric(diff);
+        } else {
+            let colours = crate::testutil::generate_colours(100);
+            assert!(colours
+                .windows(2)
+                .any(|pair| diff(pair[0], pair[1]) != diff(pair[1], pair[0])));
+        }
+        if M::IS_METRIC {
+            crate::testutil::do_test_triangle(diff);
+        }
+    }
+
+    #[test]
+    fn test_properties() {
+        fn props<M: MetricProperties>(_: &M) -> (bool, bool) {
+            (M::IS_METRIC, M::IS_SYMMETRIC)
+        }
+
+        assert_eq!((true, true), props(&Cie76));
+        assert_eq!((false, false), props(&Cie94::default()));
+        assert_eq!((false, true), props(&Cie00::default()));
+        assert_eq!((false, false), props(&Cmc(2.0, 1.0)));
+
+        check_properties(Cie76);
+        check_properties(Cie94(cie94::Params::graphic()));
+        check_properties(Cie00::default());
+        check_properties(Cmc(2.0, 1.0));
+        check_properties(Cie94::symmetric(cie94::Params::graphic()));
+        check_properties(Cmc::symmetric(2.0, 1.0));
+    }
+
+    #[test]
+    fn test_symmetric() {
+        let cie94 = Cie94(cie94::Params::textiles());
+        let cmc = Cmc(2.0, 1.0);
+        let cie94_sym = Cie94::symmetric(cie94.0);
+        let cmc_sym = Cmc::symmetric(cmc.0, cmc.1);
+        for pair in crate::testutil::generate_colours(1000).windows(2) {
+            let (a, b) = (pair[0], pair[1]);
+
+            // Weighting functions of CIE94 are monotonic in chroma so the
+            // symmetric result lies between the asymmetric ones.
+            let got = cie94_sym.diff(a, b);
+            assert_eq!(got, cie94_sym.diff(b, a));
+            let (x, y) = (cie94.diff(a, b), cie94.diff(b, a));
+            let (min, max) = (x.min(y), x.max(y));
+            assert!(
+                min - 0.001 <= got && got <= max + 0.001,
+                "{} not in {}..={}; {:?} {:?}",
+                got,
+                min,
+                max,
+                a,
+                b
+            );
+
+            // CMC weights lightness and hue in a more complicated manner so
+            // the symmetric result may fall slightly outside of the range.
+            let got = cmc_sym.diff(a, b);
+            assert_eq!(got, cmc_sym.diff(b, a));
+            let (x, y) = (cmc.diff(a, b), cmc.diff(b, a));
+            let (min, max) = (x.min(y), x.max(y));
+            assert!(
+                min / 1.5 <= got && got <= max * 1.5,
+                "{} not near {}..={}; {:?} {:?}",
+                got,
+                min,
+                max,
+                a,
+                b
+            );
+        }
+    }
+}
@@ -0,0 +1,362 @@
+// Colour naming.
+// Copyright (c) 2026 Michał Nazarewicz <mina86@mina86.com>
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in
+// all copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+
+//! Assigning names to colours.
+//!
+//! The module provides [`iscc_nbs`] function which names colours according to
+//! the ISCC–NBS System of Colour Designation.
+
+use crate::ToLab;
+
+/// Returns ISCC–NBS name of the colour.
+///
+/// The ISCC–NBS System of Colour Designation divides the colour solid into
+/// 267 regions each with a name such as ‘vivid red’ or ‘light greenish
+/// blue’.  This function approximates the regions by their centroids and
+/// returns name of the centroid closest to the colour as measured by the
+/// CIEDE2000 colour difference.  Note that since the regions are not
+/// spherical, this may differ from the proper ISCC–NBS designation near
+/// region boundaries.
+///
+/// Centroid colours are the sRGB approximations of the centroids defined in
+/// Kenneth L. Kelly and Deane B. Judd, ‘Color: Universal Language and
+/// Dictionary of Names’, NBS Special Publication 440, 1976.  The spelling
+/// (e.g. ‘gray’) follows the publication.
+///
+/// ## Example
+///
+/// ```
+/// use empfindung::naming;
+///
+/// assert_eq!("vivid red", naming::iscc_nbs((40.85, 67.81, 30.86)));
+/// assert_eq!("light greenish blue",
+///            naming::iscc_nbs((65.32, -18.12, -17.91)));
+/// assert_eq!("black", naming::iscc_nbs((0.0, 0.0, 0.0)));
+/// ```
+pub fn iscc_nbs(colour: impl ToLab) -> &'static str {
+    let centroids = ISCC_NBS_CENTROIDS.iter().map(|&(_, centroid)| centroid);
+    let diff = crate::cie00::diff_fn(crate::cie00::Params::default());
+    // The table is not empty so nearest always returns Some.
+    let (index, _) = crate::set::nearest(colour, centroids, diff).unwrap();
+    ISCC_NBS_CENTROIDS[index].0
+}
+
+/// Names and L\*a\*b\* coordinates of the ISCC–NBS centroid colours.
+///
+/// The coordinates were calculated from the sRGB values noted in comments.
+#[rustfmt::skip]
+static ISCC_NBS_CENTROIDS: [(&str, (f32, f32, f32)); 267] = [
+    ("vivid pink", (80.66, 27.53, 8.24)), // #FFB5BA
+    ("strong pink", (69.94, 33.51, 10.72)), // #EA9399
+    ("deep pink", (61.41, 45.41, 16.16)), // #E4717A
+    ("light pink", (85.80, 15.55, 6.98)), // #F9CCCA
+    ("moderate pink", (72.97, 20.88, 8.77)), // #DEA5A4
+    ("dark pink", (60.10, 24.90, 9.63)), // #C08081
+    ("pale pink", (87.73, 6.02, 2.73)), // #EAD8D7
+    ("grayish pink", (72.91, 7.74, 3.41)), // #C4AEAD
+    ("pinkish white", (90.72, 2.02, 1.82)), // #EAE3E1
+    ("pinkish gray", (74.86, 3.36, 2.92)), // #C1B6B3
+    ("vivid red", (39.88, 65.25, 29.72)), // #BE0032
+    ("strong red", (45.28, 50.80, 21.64)), // #BC3F4A
+    ("deep red", (29.11, 44.58, 16.90)), // #841B2D
+    ("very deep red", (18.33, 37.02, 7.90)), // #5C0923
+    ("moderate red", (45.24, 38.49, 16.23)), // #AB4E52
+    ("dark red", (29.13, 30.36, 9.72)), // #722F37
+    ("very dark red", (14.53, 21.71, -1.96)), // #3F1728
+    ("light grayish red", (60.03, 13.47, 7.54)), // #AD8884
+    ("grayish red", (45.01, 20.88, 8.65)), // #905D5D
+    ("dark grayish red", (28.33, 10.43, 2.72)), // #543D3F
+    ("blackish red", (13.04, 9.08, 0.64)), // #2E1D21
+    ("reddish gray", (55.15, 4.95, 3.01)), // #8F817F
+    ("dark reddish gray", (35.16, 4.77, 2.41)), // #5C504F
+    ("reddish black", (16.59, 6.04, 0.83)), // #322628
+    ("vivid yellowish pink", (80.65, 23.83, 19.53)), // #FFB7A5
+    ("strong yellowish pink", (71.14, 35.82, 30.31)), // #F99379
+    ("deep yellowish pink", (59.33, 48.86, 27.74)), // #E66761
+    ("light yellowish pink", (82.76, 17.82, 6.81)), // #F4C2C2
+    ("moderate yellowish pink", (72.82, 19.20, 5.73)), // #D9A6A9
+    ("dark yellowish pink", (61.09, 23.81, 15.66)), // #C48379
+    ("pale yellowish pink", (86.73, 5.41, 10.80)), // #ECD5C5
+    ("grayish yellowish pink", (72.68, 7.78, 8.60)), // #C7ADA3
+    ("brownish pink", (71.74, 4.90, 12.69)), // #C2AC99
+    ("vivid reddish orange", (55.35, 51.34, 55.69)), // #E25822
+    ("strong reddish orange", (55.52, 45.23, 43.60)), // #D9603B
+    ("deep reddish orange", (40.36, 45.40, 40.46)), // #AA381E
+    ("moderate reddish orange", (56.32, 34.61, 32.12)), // #CB6D51
+    ("dark reddish orange", (41.23, 34.65, 29.81)), // #9E4732
+    ("grayish reddish orange", (55.08, 22.68, 22.57)), // #B4745E
+    ("strong reddish brown", (32.20, 37.71, 34.09)), // #882D17
+    ("deep reddish brown", (16.29, 34.44, 20.16)), // #56070C
+    ("light reddish brown", (55.93, 15.00, 14.89)), // #A87C6D
+    ("moderate reddish brown", (35.03, 21.76, 15.36)), // #79443B
+    ("dark reddish brown", (15.47, 16.16, 6.60)), // #3E1D1E
+    ("light grayish reddish brown", (55.07, 7.25, 9.95)), // #977F73
+    ("grayish reddish brown", (35.02, 10.62, 7.42)), // #674C47
+    ("dark grayish reddish brown", (21.96, 8.31, 4.66)), // #43302E
+    ("vivid orange", (66.18, 36.08, 72.64)), // #F38400
+    ("brilliant orange", (71.16, 33.23, 59.59)), // #FD943F
+    ("strong orange", (66.16, 32.86, 61.46)), // #ED872D
+    ("deep orange", (52.19, 30.79, 55.16)), // #BE6516
+    ("light orange", (78.99, 18.96, 37.31)), // #FAB57F
+    ("moderate orange", (66.06, 22.07, 40.69)), // #D99058
+    ("brownish orange", (51.14, 23.18, 38.57)), // #AE6938
+    ("strong brown", (36.08, 21.31, 35.41)), // #80461B
+    ("deep brown", (25.45, 14.49, 23.40)), // #593319
+    ("light brown", (55.10, 12.58, 24.01)), // #A67B5B
+    ("moderate brown", (36.18, 10.87, 19.10)), // #6F4E37
+    ("dark brown", (18.13, 12.17, 14.42)), // #422518
+    ("light grayish brown", (55.07, 5.41, 11.66)), // #958070
+    ("grayish brown", (35.97, 5.77, 8.69)), // #635147
+    ("dark grayish brown", (21.90, 4.28, 5.84)), // #3E322C
+    ("light brownish gray", (55.17, 2.93, 6.51)), // #8E8279
+    ("brownish gray", (35.06, 4.35, 2.25)), // #5B504F
+    ("brownish black", (13.03, 3.03, 4.15)), // #28201C
+    ("vivid orange yellow", (74.11, 19.62, 77.89)), // #F6A600
+    ("brilliant orange yellow", (81.82, 11.34, 63.74)), // #FFC14F
+    ("strong orange yellow", (71.86, 17.05, 70.07)), // #EAA221
+    ("deep orange yellow", (60.95, 17.86, 66.61)), // #C98500
+    ("light orange yellow", (83.87, 8.91, 43.52)), // #FBC97F
+    ("moderate orange yellow", (72.91, 13.28, 49.52)), // #E3A857
+    ("dark orange yellow", (61.19, 12.08, 47.74)), // #BE8A3D
+    ("pale orange yellow", (87.55, 5.76, 28.88)), // #FAD6A5
+    ("strong yellowish brown", (47.15, 14.54, 49.29)), // #996515
+    ("deep yellowish brown", (32.12, 9.71, 26.27)), // #654522
+    ("light yellowish brown", (66.15, 8.37, 30.15)), // #C19A6B
+    ("moderate yellowish brown", (45.17, 6.53, 23.46)), // #826644
+    ("dark yellowish brown", (24.46, 6.41, 16.81)), // #4B3621
+    ("light grayish yellowish brown", (65.01, 2.99, 15.68)), // #AE9B82
+    ("grayish yellowish brown", (47.12, 3.54, 13.09)), // #7E6D5A
+    ("dark grayish yellowish brown", (26.27, 3.42, 8.13)), // #483C32
+    ("vivid yellow", (80.79, 3.17, 82.29)), // #F3C300
+    ("brilliant yellow", (87.62, -2.72, 63.39)), // #FADA5E
+    ("strong yellow", (72.85, 1.38, 62.91)), // #D4AF37
+    ("deep yellow", (60.08, 2.06, 61.40)), // #AF8D13
+    ("light yellow", (88.78, -3.15, 50.02)), // #F8DE7E
+    ("moderate yellow", (71.88, -0.39, 44.73)), // #C9AE5D
+    ("dark yellow", (61.07, 0.35, 43.69)), // #AB9144
+    ("pale yellow", (90.80, -3.83, 30.07)), // #F3E5AB
+    ("grayish yellow", (72.81, -1.75, 27.68)), // #C2B280
+    ("dark grayish yellow", (59.95, -0.19, 27.47)), // #A18F60
+    ("yellowish white", (92.67, -1.32, 10.43)), // #F0EAD6
+    ("yellowish gray", (74.87, -0.79, 10.54)), // #BFB8A5
+    ("light olive brown", (49.93, 6.32, 50.86)), // #967117
+    ("moderate olive brown", (37.16, 3.64, 34.20)), // #6C541E
+    ("dark olive brown", (20.97, 1.72, 11.90)), // #3B3121
+    ("vivid greenish yellow", (82.81, -14.85, 82.77)), // #DCD300
+    ("brilliant greenish yellow", (88.63, -15.53, 69.74)), // #E9E450
+    ("strong greenish yellow", (72.90, -12.72, 65.40)), // #BEB72E
+    ("deep greenish yellow", (60.02, -11.18, 63.70)), // #9B9400
+    ("light greenish yellow", (89.61, -13.18, 53.13)), // #EAE679
+    ("moderate greenish yellow", (72.01, -10.60, 46.49)), // #B9B459
+    ("dark greenish yellow", (60.07, -10.08, 44.97)), // #98943E
+    ("pale greenish yellow", (90.81, -9.23, 33.53)), // #EBE8A4
+    ("grayish greenish yellow", (72.79, -7.29, 29.11)), // #B9B57D
+    ("light olive", (52.13, -6.74, 39.48)), // #867E36
+    ("moderate olive", (39.15, -4.64, 36.09)), // #665D1E
+    ("dark olive", (25.37, -3.59, 17.69)), // #403D21
+    ("light grayish olive", (55.97, -3.56, 17.93)), // #8C8767
+    ("grayish olive", (37.11, -2.83, 13.23)), // #5B5842
+    ("dark grayish olive", (21.86, -2.41, 9.02)), // #363527
+    ("light olive gray", (56.13, -1.98, 9.55)), // #8A8776
+    ("olive gray", (36.07, -0.99, 5.54)), // #57554C
+    ("olive black", (14.08, -1.10, 4.86)), // #25241D
+    ("vivid yellow green", (68.84, -34.13, 69.78)), // #8DB600
+    ("brilliant yellow green", (82.73, -27.99, 59.65)), // #BDDA57
+    ("strong yellow green", (61.16, -27.95, 52.41)), // #7E9F2E
+    ("deep yellow green", (43.17, -28.58, 34.48)), // #467129
+    ("light yellow green", (84.69, -19.41, 38.69)), // #C9DC89
+    ("moderate yellow green", (61.02, -16.28, 31.17)), // #8A9A5B
+    ("pale yellow green", (87.53, -8.37, 19.08)), // #DADFB7
+    ("grayish yellow green", (61.09, -8.37, 14.87)), // #8F9779
+    ("strong olive green", (31.07, -16.67, 38.85)), // #404F00
+    ("deep olive green", (17.42, -13.02, 24.72)), // #232F00
+    ("moderate olive green", (36.75, -17.39, 30.32)), // #4A5D23
+    ("dark olive green", (23.64, -12.59, 11.92)), // #2B3D26
+    ("grayish olive green", (35.95, -6.23, 10.27)), // #515744
+    ("dark grayish olive green", (21.82, -4.62, 6.21)), // #31362B
+    ("vivid yellowish green", (60.09, -53.19, 36.78)), // #27A64C
+    ("brilliant yellowish green", (77.81, -41.41, 35.03)), // #83D37D
+    ("strong yellowish green", (54.97, -40.54, 31.68)), // #44944A
+    ("deep yellowish green", (35.84, -38.10, 23.17)), // #00622D
+    ("very deep yellowish green", (16.81, -22.98, 11.64)), // #003118
+    ("very light yellowish green", (86.54, -25.22, 21.49)), // #B6E5AF
+    ("light yellowish green", (74.96, -26.36, 20.43)), // #93C592
+    ("moderate yellowish green", (56.51, -23.70, 18.22)), // #679267
+    ("dark yellowish green", (36.13, -22.79, 15.69)), // #355E3B
+    ("very dark yellowish green", (19.74, -17.57, 10.40)), // #173620
+    ("vivid green", (49.83, -43.98, 18.21)), // #008856
+    ("brilliant green", (66.11, -43.17, 12.45)), // #3EB489
+    ("strong green", (44.79, -37.23, 9.54)), // #007959
+    ("deep green", (31.06, -28.75, 7.25)), // #00543D
+    ("very light green", (78.79, -27.77, 8.69)), // #8ED1B2
+    ("light green", (65.08, -27.45, 8.44)), // #6AAB8E
+    ("moderate green", (45.94, -25.73, 6.62)), // #3B7861
+    ("dark green", (29.13, -20.96, 3.95)), // #1B4D3E
+    ("very dark green", (19.97, -12.01, 2.02)), // #1C352D
+    ("very pale green", (88.75, -13.02, 3.89)), // #C7E6D7
+    ("pale green", (65.09, -9.72, 2.60)), // #8DA399
+    ("grayish green", (45.95, -8.60, 1.53)), // #5E716A
+    ("dark grayish green", (30.37, -7.67, 0.17)), // #3A4B47
+    ("blackish green", (13.18, -5.23, 0.56)), // #1A2421
+    ("greenish white", (92.61, -5.54, 0.82)), // #DFEDE8
+    ("light greenish gray", (75.84, -5.84, 3.12)), // #B2BEB5
+    ("greenish gray", (56.00, -5.40, 1.18)), // #7D8984
+    ("dark greenish gray", (36.16, -3.99, -0.05)), // #4E5755
+    ("greenish black", (13.16, -2.79, 0.57)), // #1E2321
+    ("vivid bluish green", (50.94, -32.04, -5.64)), // #008882
+    ("brilliant bluish green", (61.11, -40.54, -0.39)), // #00A693
+    ("strong bluish green", (45.88, -29.80, -4.91)), // #007A74
+    ("deep bluish green", (25.24, -20.37, -2.44)), // #00443F
+    ("very light bluish green", (83.61, -25.20, -0.77)), // #96DED1
+    ("light bluish green", (66.05, -24.49, -2.38)), // #66ADA4
+    ("moderate bluish green", (46.07, -23.33, -3.93)), // #317873
+    ("dark bluish green", (28.15, -20.56, -4.65)), // #004B49
+    ("very dark bluish green", (14.49, -14.09, -3.38)), // #002A29
+    ("vivid greenish blue", (51.09, -20.07, -23.47)), // #0085A1
+    ("brilliant greenish blue", (60.21, -22.51, -23.70)), // #239EBA
+    ("strong greenish blue", (45.96, -18.29, -22.01)), // #007791
+    ("deep greenish blue", (50.98, -19.71, -16.60)), // #2E8495
+    ("very light greenish blue", (80.65, -14.50, -10.98)), // #9CD1DC
+    ("light greenish blue", (65.91, -16.71, -15.95)), // #66AABC
+    ("moderate greenish blue", (46.02, -14.25, -16.49)), // #367588
+    ("dark greenish blue", (28.03, -13.82, -14.52)), // #004958
+    ("very dark greenish blue", (16.84, -9.13, -12.43)), // #002E3B
+    ("vivid blue", (61.12, -23.19, -26.82)), // #00A1C2
+    ("brilliant blue", (59.98, -6.31, -36.32)), // #4997D0
+    ("strong blue", (41.87, -1.14, -40.26)), // #0067A5
+    ("deep blue", (26.22, -1.21, -28.94)), // #00416A
+    ("very light blue", (79.71, -4.41, -23.59)), // #A1CAF1
+    ("light blue", (64.96, -6.00, -26.24)), // #70A3CC
+    ("moderate blue", (44.06, -1.28, -27.20)), // #436B95
+    ("dark blue", (18.64, -1.99, -22.59)), // #00304E
+    ("very pale blue", (83.70, -4.28, -11.55)), // #BCD4E6
+    ("pale blue", (66.02, -3.50, -8.84)), // #91A3B0
+    ("grayish blue", (42.91, -3.72, -11.49)), // #536878
+    ("dark grayish blue", (28.39, -3.25, -7.96)), // #36454F
+    ("blackish blue", (15.69, -1.17, -6.37)), // #202830
+    ("bluish white", (92.45, 0.72, -1.94)), // #E9E9ED
+    ("light bluish gray", (75.75, -1.99, -2.98)), // #B4BCC0
+    ("bluish gray", (55.95, -1.38, -2.91)), // #81878B
+    ("dark bluish gray", (37.00, -1.36, -4.40)), // #51585E
+    ("bluish black", (13.96, -0.72, -3.27)), // #202428
+    ("vivid purplish blue", (21.65, 30.21, -46.42)), // #30267A
+    ("brilliant purplish blue", (52.20, 11.49, -34.75)), // #6C79B8
+    ("strong purplish blue", (41.19, 19.31, -42.34)), // #545AA7
+    ("deep purplish blue", (17.45, 17.76, -31.11)), // #272458
+    ("very light purplish blue", (76.70, 4.84, -19.99)), // #B3BCE2
+    ("light purplish blue", (60.92, 7.03, -25.05)), // #8791BF
+    ("moderate purplish blue", (36.10, 11.29, -26.88)), // #4E5180
+    ("dark purplish blue", (15.62, 8.73, -17.66)), // #252440
+    ("very pale purplish blue", (80.71, 2.26, -13.32)), // #C0C8E1
+    ("pale purplish blue", (60.88, 3.44, -14.34)), // #8C92AC
+    ("grayish purplish blue", (35.05, 5.13, -16.75)), // #4C516D
+    ("vivid violet", (51.27, 37.98, -46.35)), // #9065CA
+    ("brilliant violet", (52.01, 20.14, -34.95)), // #7E73B8
+    ("strong violet", (38.15, 25.11, -37.57)), // #604E97
+    ("deep violet", (14.55, 25.65, -28.26)), // #32174D
+    ("very light violet", (85.78, 13.08, -21.44)), // #DCD0FF
+    ("light violet", (57.00, 15.02, -25.96)), // #8C82B6
+    ("moderate violet", (36.97, 19.06, -26.03)), // #604E81
+    ("dark violet", (15.81, 14.34, -17.29)), // #2F2140
+    ("very pale violet", (79.61, 5.39, -12.79)), // #C4C3DD
+    ("pale violet", (61.11, 7.70, -13.35)), // #9690AB
+    ("grayish violet", (34.23, 10.36, -15.44)), // #554C69
+    ("vivid purple", (46.14, 46.80, -38.16)), // #9A4EAE
+    ("brilliant purple", (71.11, 34.87, -30.47)), // #D399E6
+    ("strong purple", (44.06, 30.91, -24.96)), // #875692
+    ("deep purple", (28.33, 32.46, -25.79)), // #602F6B
+    ("very deep purple", (17.23, 27.56, -23.17)), // #401A4C
+    ("very light purple", (78.68, 15.46, -12.96)), // #D5BADB
+    ("light purple", (62.61, 28.13, -23.23)), // #B687C1
+    ("moderate purple", (46.16, 23.89, -19.34)), // #86608E
+    ("dark purple", (29.37, 17.80, -14.61)), // #563C5C
+    ("very dark purple", (13.06, 16.96, -13.11)), // #301934
+    ("very pale purple", (82.78, 7.61, -7.91)), // #D6CADD
+    ("pale purple", (64.86, 9.74, -6.44)), // #AA98A9
+    ("grayish purple", (46.07, 9.81, -6.42)), // #796878
+    ("dark grayish purple", (29.18, 9.53, -5.23)), // #50404D
+    ("blackish purple", (12.99, 7.82, -5.35)), // #291E29
+    ("purplish white", (90.67, 2.04, -0.37)), // #E8E3E5
+    ("light purplish gray", (75.73, 2.84, -1.27)), // #BFB9BD
+    ("purplish gray", (56.16, 3.01, -1.34)), // #8B8589
+    ("dark purplish gray", (37.08, 4.48, -2.30)), // #5D555B
+    ("purplish black", (13.17, 2.14, -1.51)), // #242124
+    ("vivid reddish purple", (30.43, 58.43, -27.78)), // #870074
+    ("strong reddish purple", (45.14, 40.37, -17.04)), // #9E4F88
+    ("deep reddish purple", (29.24, 38.87, -19.03)), // #702963
+    ("very deep reddish purple", (20.56, 34.38, -19.05)), // #54194E
+    ("light reddish purple", (61.10, 25.22, -10.77)), // #B784A7
+    ("moderate reddish purple", (46.06, 27.90, -12.78)), // #915C83
+    ("dark reddish purple", (29.14, 20.61, -9.69)), // #5D3954
+    ("very dark reddish purple", (13.01, 19.08, -10.96)), // #341731
+    ("pale reddish purple", (60.95, 15.56, -6.00)), // #AA8A9E
+    ("grayish reddish purple", (46.07, 16.19, -6.91)), // #836479
+    ("brilliant purplish pink", (85.72, 21.60, 0.55)), // #FFC8D6
+    ("strong purplish pink", (69.08, 36.66, -1.19)), // #E68FAC
+    ("deep purplish pink", (61.20, 48.82, -6.69)), // #DE6FA1
+    ("light purplish pink", (80.88, 21.27, -1.35)), // #EFBBCC
+    ("moderate purplish pink", (68.99, 26.62, -2.65)), // #D597AE
+    ("dark purplish pink", (60.16, 28.60, 0.56)), // #C17E91
+    ("pale purplish pink", (84.61, 11.62, -1.84)), // #E8CCD7
+    ("grayish purplish pink", (70.91, 12.42, -1.72)), // #C3A6B1
+    ("vivid purplish red", (50.47, 57.36, 2.56)), // #CE4676
+    ("strong purplish red", (45.36, 48.72, 0.67)), // #B3446C
+    ("deep purplish red", (27.13, 44.55, -5.67)), // #78184A
+    ("very deep purplish red", (18.66, 33.86, -8.91)), // #54133B
+    ("moderate purplish red", (46.09, 38.92, 0.31)), // #A8516E
+    ("dark purplish red", (28.21, 26.99, -2.41)), // #673147
+    ("very dark purplish red", (12.98, 20.83, -7.33)), // #38152C
+    ("light grayish purplish red", (60.00, 16.93, 1.79)), // #AF868E
+    ("grayish purplish red", (46.05, 22.44, 0.48)), // #915F6D
+    ("white", (95.79, -0.15, -0.60)), // #F2F3F4
+    ("light gray", (74.78, -0.20, 1.64)), // #B9B8B5
+    ("medium gray", (55.09, -0.39, 1.08)), // #848482
+    ("dark gray", (36.15, 0.00, 0.00)), // #555555
+    ("black", (13.23, 0.00, 0.00)), // #222222
+];
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_centroids() {
+        for &(name, centroid) in ISCC_NBS_CENTROIDS.iter() {
+            assert_eq!(name, iscc_nbs(centroid));
+        }
+    }
+
+    #[test]
+    fn test_names() {
+        assert_eq!("white", iscc_nbs((100.0, 0.0, 0.0)));
+        assert_eq!("medium gray", iscc_nbs((53.0, 0.0, 0.0)));
+        assert_eq!("vivid green", iscc_nbs((50.0, -60.0, 20.0)));
+        assert_eq!("deep purplish blue", iscc_nbs((15.0, 20.0, -40.0)));
+    }
+
+    #[cfg(all(feature = "lab", feature = "rgb"))]
+    #[test]
+    fn test_rgb() {
+        assert_eq!("vivid red", iscc_nbs(rgb::RGB8::new(0xBE, 0x00, 0x32)));
+        assert_eq!("vivid blue", iscc_nbs(rgb::RGB8::new(0x00, 0xA1, 0xC2)));
+        assert_eq!("vivid orange", iscc_nbs(rgb::RGB8::new(0xF3, 0x84, 0)));
+    }
+}
@@ -0,0 +1,210 @@
+// OSA-UCS colour difference implementation.
+// Copyright (c) 2026 Michał Nazarewicz <mina86@mina86.com>
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in
+// all copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+
+//! Implementation of colour distances based on the OSA-UCS colour space.
+//!
+//! The Optical Society of America Uniform Color Scales (MacAdam, 1974) define
+//! a colour space with lightness L, yellowness j and greenness g coordinates.
+//! The module provides [`diff`] function returning Euclidean distance in that
+//! space as well as [`diff_ucs`] returning the ΔE_E difference (Oleari,
+//! Melgosa and Huertas) which operates on logarithmically compressed OSA-UCS
+//! coordinates and is better suited for small and medium colour differences.
+//! Both are metrics in the mathematical sense.
+//!
+//! OSA-UCS is defined in terms of CIE 1964 (10°) XYZ coordinates.  The
+//! functions use XYZ coordinates obtained from L\*a\*b\* colour with D65
+//! reference white instead which is a reasonable approximation in practice.
+//!
+//! The OSA-UCS lightness formula isn’t well defined for very dark colours
+//! (with Y around 0.3 and below) where the chroma scaling factor diverges.
+//! To keep the results finite, the divisor of the factor is clamped.  As
+//! a result, the distances for colours with L\* below about 3 are not
+//! meaningful.
+
+use crate::float::{Float, FloatExt};
+
+/// Returns the Euclidean distance between two L\*a\*b\* colours in the
+/// OSA-UCS colour space.
+///
+/// ## Example
+///
+/// ```
+/// use empfindung::osa_ucs;
+///
+/// let colour_1 = (38.972, 58.991, 37.138);
+/// let colour_2 = (54.528, 42.416, 54.497);
+/// let delta_e = osa_ucs::diff(colour_1, colour_2);
+/// approx::assert_abs_diff_eq!(5.2303, delta_e, epsilon = 0.001);
+/// ```
+pub fn diff(colour_1: impl crate::ToLab, colour_2: impl crate::ToLab) -> f32 {
+    let colour_1 = from_lab(colour_1.to_lab());
+    let colour_2 = from_lab(colour_2.to_lab());
+    euclidean(colour_1, colour_2)
+}
+
+/// Returns the ΔE_E colour difference between two L\*a\*b\* colours.
+///
+/// This is the Euclidean distance in the log-compressed OSA-UCS space
+/// proposed by Oleari, Melgosa and Huertas (2006, 2009).
+///
+/// ## Example
+///
+/// ```
+/// use empfindung::osa_ucs;
+///
+/// let colour_1 = (38.972, 58.991, 37.138);
+/// let colour_2 = (54.528, 42.416, 54.497);
+/// let delta_e = osa_ucs::diff_ucs(colour_1, colour_2);
+/// approx::assert_abs_diff_eq!(16.244, delta_e, epsilon = 0.001);
+/// ```
+pub fn diff_ucs(
+    colour_1: impl crate::ToLab,
+    colour_2: impl crate::ToLab,
+) -> f32 {
+    let colour_1 = compress(from_lab(colour_1.to_lab()));
+    let colour_2 = compress(from_lab(colour_2.to_lab()));
+    euclidean(colour_1, colour_2)
+}
+
+fn euclidean(colour_1: (f32, f32, f32), colour_2: (f32, f32, f32)) -> f32 {
+    let dl = colour_1.0 - colour_2.0;
+    let dj = colour_1.1 - colour_2.1;
+    let dg = colour_1.2 - colour_2.2;
+    Float::sqrt(dl * dl + dj * dj + dg * dg)
+}
+
+/// Converts L\*a\*b\* colour into OSA-UCS L, j, g coordinates.
+fn from_lab(lab: (f32, f32, f32)) -> (f32, f32, f32) {
+    let white = crate::xyz::D65;
+    let (x, y, z) = crate::xyz::from_lab(lab, white);
+    from_xyz((x * 100.0, y * 100.0, z * 100.0))
+}
+
+/// Converts XYZ colour (with Y of the white point equal 100) into OSA-UCS L,
+/// j, g coordinates.
+fn from_xyz((x, y, z): (f32, f32, f32)) -> (f32, f32, f32) {
+    let white = crate::xyz::D65;
+
+    // Chromaticity coordinates.  Those are undefined for black so use the
+    // white point’s in that case.
+    let sum = x + y + z;
+    let (cx, cy) = if sum > 0.0 {
+        (x / sum, y / sum)
+    } else {
+        let sum = white.0 + white.1 + white.2;
+        (white.0 / sum, white.1 / sum)
+    };
+
+    let k = 4.4934 * cx * cx + 4.3034 * cy * cy - 4.2760 * cx * cy -
+        1.3744 * cx - 2.5643 * cy + 1.8103;
+    let y_0 = k * y;
+    // Cube root rather than powf so that negative values don’t produce NaN.
+    let y_0_cbrt = (FloatExt::cbrt(y_0) - 2.0 / 3.0).max(0.01);
+    let l_prime = 5.9 * (y_0_cbrt + 0.042 * FloatExt::cbrt(y_0 - 30.0));
+    let l = (l_prime - 14.3993) / core::f32::consts::SQRT_2;
+    let c = l_prime / (5.9 * y_0_cbrt);
+
+    let r = FloatExt::cbrt(0.799 * x + 0.4194 * y - 0.1648 * z);
+    let g = FloatExt::cbrt(-0.4493 * x + 1.3265 * y + 0.0927 * z);
+    let b = FloatExt::cbrt(-0.1149 * x + 0.3394 * y + 0.717 * z);
+    let j = c * (1.7 * r + 8.0 * g - 9.7 * b);
+    let g = c * (-13.7 * r + 17.7 * g - 4.0 * b);
+    (l, j, g)
+}
+
+/// Applies logarithmic compression of lightness and chroma used by ΔE_E.
+fn compress((l, j, g): (f32, f32, f32)) -> (f32, f32, f32) {
+    const A_L: f32 = 2.890;
+    const B_L: f32 = 0.015;
+    const A_C: f32 = 1.256;
+    const B_C: f32 = 0.050;
+
+    let l = FloatExt::ln_1p(B_L / A_L * 10.0 * l) / B_L;
+    let c = Float::hypot(j, g);
+    if c == 0.0 {
+        return (l, 0.0, 0.0);
+    }
+    let scale = FloatExt::ln_1p(B_C / A_C * 10.0 * c) / (B_C * c);
+    (l, j * scale, g * scale)
+}
+
+#[cfg(test)]
+mod tests {
+    // Regression values for the L\*a\*b\* → XYZ → OSA-UCS conversion and both
+    // distances calculated in double precision.  The columns are: Euclidean
+    // OSA-UCS distance, ΔE_E and the two colours.
+    type Lab = (f32, f32, f32);
+
+    #[rustfmt::skip]
+    static TESTS: [(f32, f32, Lab, Lab); 7] = [
+        ( 5.2303, 16.2440, (38.972, 58.991, 37.138), (54.528, 42.416, 54.497)),
+        ( 7.0043, 26.3340, (50.000,  2.500,  0.000), (73.000, 25.000, -18.000)),
+        ( 0.3368,  2.5712, (50.000,  0.000,  0.000), (50.000, -1.000,   2.000)),
+        ( 0.1770,  0.6935, (50.000,  0.000,  0.000), (51.000,  0.000,   0.000)),
+        (17.8232, 74.9356, (100.00,  0.000,  0.000), ( 0.000,  0.000,   0.000)),
+        ( 0.7228,  1.7607, (22.723, 20.090, -46.694), (23.033, 14.973, -42.562)),
+        ( 0.4053,  1.1025, (60.257, -34.010, 36.268), (60.463, -34.175, 39.439)),
+    ];
+
+    #[test]
+    fn test_difference() {
+        for &(want, want_ucs, colour_1, colour_2) in TESTS.iter() {
+            let got = super::diff(colour_1, colour_2);
+            approx::assert_abs_diff_eq!(want, got, epsilon = 0.001);
+            let got = super::diff_ucs(colour_1, colour_2);
+            approx::assert_abs_diff_eq!(want_ucs, got, epsilon = 0.001);
+        }
+    }
+
+    #[test]
+    fn test_from_xyz() {
+        // Example from documentation of colour.XYZ_to_OSA_UCS function in the
+        // colour-science Python library.  The library uses 14.4 as the
+        // lightness offset (rather than 14.3993) which makes its L smaller by
+        // about 0.0005.
+        let got = super::from_xyz((20.654008, 12.197225, 5.136952));
+        approx::assert_abs_diff_eq!(-3.0049979, got.0, epsilon = 0.001);
+        approx::assert_abs_diff_eq!(2.9971370, got.1, epsilon = 0.0001);
+        approx::assert_abs_diff_eq!(-9.6678423, got.2, epsilon = 0.0001);
+    }
+
+    #[test]
+    fn test_dark() {
+        for l in 0..=5 {
+            let (l, j, g) = super::from_lab((l as f32 * 0.5, 0.0, 0.0));
+            assert!(l.is_finite() && j.is_finite() && g.is_finite());
+        }
+        let got = super::diff_ucs((0.0, 0.0, 0.0), (1.0, 2.0, -3.0));
+        assert!(got.is_finite(), "{}", got);
+    }
+
+    #[test]
+    fn test_zero() {
+        crate::testutil::do_test_zero(super::diff);
+        crate::testutil::do_test_zero(super::diff_ucs);
+    }
+
+    #[test]
+    fn test_symmetric() {
+        crate::testutil::do_test_symmetric(super::diff);
+        crate::testutil::do_test_symmetric(super::diff_ucs);
+    }
+}
@@ -0,0 +1,207 @@
+// Perceptibility of colour differences.
+// Copyright (c) 2026 Michał Nazarewicz <mina86@mina86.com>
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in
+// all copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+
+/// Rough description of how noticeable a colour difference is.
+///
+/// The categories follow commonly cited rules of thumb for interpreting
+/// ΔE₀₀ and ΔE₇₆ values.  They are heuristics rather than results of any
+/// standard: perceptibility depends on viewing conditions, the colours
+/// involved and the observer.  The thresholds are unlikely to be meaningful
+/// for other colour difference formulæ.
+///
+/// ## Example
+///
+/// ```
+/// use empfindung::{cie00, Perceptibility};
+///
+/// let delta_e = cie00::diff((50.0, 0.0, 0.0), (52.0, 1.0, 0.0));
+/// assert_eq!(Perceptibility::Perceptible, Perceptibility::classify(delta_e));
+/// ```
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Debug)]
+pub enum Perceptibility {
+    /// ΔE ≤ 1; difference is not perceptible by human eye.
+    NotPerceptible,
+    /// 1 < ΔE ≤ 2; difference is perceptible through close observation.
+    PerceptibleOnClose,
+    /// 2 < ΔE ≤ 10; difference is perceptible at a glance.
+    Perceptible,
+    /// 10 < ΔE < 50; colours are more similar than opposite.
+    Distinct,
+    /// ΔE ≥ 50; colours are clearly different or unrelated.
+    Different,
+}
+
+impl Perceptibility {
+    /// Returns perceptibility category of given colour difference.
+    ///
+    /// NaN is classified as [`Perceptibility::Different`].
+    pub fn classify(delta_e: f32) -> Self {
+        if delta_e <= 1.0 {
+            Self::NotPerceptible
+        } else if delta_e <= 2.0 {
+            Self::PerceptibleOnClose
+        } else if delta_e <= 10.0 {
+            Self::Perceptible
+        } else if delta_e < 50.0 {
+            Self::Distinct
+        } else {
+            Self::Different
+        }
+    }
+
+    /// Returns human-readable description of the category, e.g.
+    /// `"perceptible"`.
+    pub const fn label(self) -> &'static str {
+        match self {
+            Self::NotPerceptible => "not perceptible",
+            Self::PerceptibleOnClose => "perceptible on close observation",
+            Self::Perceptible => "perceptible",
+            Self::Distinct => "distinct",
+            Self::Different => "different",
+        }
+    }
+}
+
+impl core::fmt::Display for Perceptibility {
+    fn fmt(&self, fmtr: &mut core::fmt::Formatter) -> core::fmt::Result {
+        fmtr.write_str(self.label())
+    }
+}
+
+/// Colour difference together with name of the formula used to calculate it.
+///
+/// The type is meant for logging and presenting results to the user.  Its
+/// [`Display`](core::fmt::Display) implementation formats the value with two
+/// decimal places (unless precision is specified explicitly).  The alternate
+/// form (i.e. `{:#}`) additionally appends the [`Perceptibility`] category
+/// of the difference.  The name of the formula isn’t included in either form;
+/// it’s there so that callers can label the value as they see fit.
+///
+/// ## Example
+///
+/// ```
+/// use empfindung::{cie00, Delta};
+///
+/// let colour_1 = (38.972, 58.991, 37.138);
+/// let colour_2 = (54.528, 42.416, 54.497);
+/// let value = cie00::diff(colour_1, colour_2);
+/// let delta = Delta { value, metric: "ΔE00" };
+/// assert_eq!("20.55", delta.to_string());
+/// assert_eq!("20.6", format!("{:.1}", delta));
+/// assert_eq!("20.55 (distinct)", format!("{:#}", delta));
+/// assert_eq!("ΔE00 = 20.55", format!("{} = {}", delta.metric, delta));
+/// ```
+#[derive(Clone, Copy, PartialEq, PartialOrd, Debug)]
+pub struct Delta {
+    /// The colour difference.
+    pub value: f32,
+    /// Name of the formula used to calculate the difference, e.g. `"ΔE00"`.
+    pub metric: &'static str,
+}
+
+impl Delta {
+    /// Returns perceptibility category of the colour difference; see
+    /// [`Perceptibility::classify`].
+    pub fn perceptibility(&self) -> Perceptibility {
+        Perceptibility::classify(self.value)
+    }
+}
+
+impl core::fmt::Display for Delta {
+    fn fmt(&self, fmtr: &mut core::fmt::Formatter) -> core::fmt::Result {
+        let precision = fmtr.precision().unwrap_or(2);
+        write!(fmtr, "{:.*}", precision, self.value)?;
+        if fmtr.alternate() {
+            write!(fmtr, " ({})", self.perceptibility())?;
+        }
+        Ok(())
+    }
+}
+
+/// Returns whether colour difference is at most one just noticeable
+/// difference, i.e. whether `delta_e ≤ 1`.
+///
+/// This is equivalent to checking whether [`Perceptibility::classify`]
+/// returns [`Perceptibility::NotPerceptible`] and the same caveats apply.
+///
+/// ## Example
+///
+/// ```
+/// assert!(empfindung::is_just_noticeable(0.5));
+/// assert!(!empfindung::is_just_noticeable(1.5));
+/// ```
+pub fn is_just_noticeable(delta_e: f32) -> bool { delta_e <= 1.0 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_perceptibility() {
+        use Perceptibility::*;
+
+        for &(delta_e, want) in &[
+            (0.0, NotPerceptible),
+            (1.0, NotPerceptible),
+            (1.001, PerceptibleOnClose),
+            (2.0, PerceptibleOnClose),
+            (2.001, Perceptible),
+            (10.0, Perceptible),
+            (10.5, Distinct),
+            (11.0, Distinct),
+            (49.0, Distinct),
+            (49.999, Distinct),
+            (50.0, Different),
+            (100.0, Different),
+            (f32::INFINITY, Different),
+        ] {
+            assert_eq!(want, Perceptibility::classify(delta_e), "{}", delta_e);
+            assert_eq!(want == NotPerceptible, is_just_noticeable(delta_e));
+        }
+
+        // 50 is the lowest value classified as Different.
+        let below_50 = f32::from_bits(50.0f32.to_bits() - 1);
+        assert_eq!(Distinct, Perceptibility::classify(below_50));
+        assert_eq!(Different, Perceptibility::classify(50.0));
+    }
+
+    #[test]
+    fn test_delta_display() {
+        let delta = |value| Delta {
+            value,
+            metric: "ΔE00",
+        };
+        for &(value, want, want_alt) in &[
+            (0.0, "0.00", "0.00 (not perceptible)"),
+            (1.5, "1.50", "1.50 (perceptible on close observation)"),
+            (20.553642, "20.55", "20.55 (distinct)"),
+            (58.90164, "58.90", "58.90 (different)"),
+            (f32::NAN, "NaN", "NaN (different)"),
+        ] {
+            assert_eq!(want, format!("{}", delta(value)));
+            assert_eq!(want_alt, format!("{:#}", delta(value)));
+        }
+
+        assert_eq!("20.5536", format!("{:.4}", delta(20.553642)));
+        assert_eq!("21 (distinct)", format!("{:#.0}", delta(20.553642)));
+        assert_eq!("perceptible", Perceptibility::Perceptible.to_string());
+    }
+}
@@ -0,0 +1,816 @@
+// Operations on sets of colours.
+// Copyright (c) 2026 Michał Nazarewicz <mina86@mina86.com>
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in
+// all copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+
+//! Operations on sets of colours such as palettes or clusters.
+//!
+//! Functions in this module are parameterised by the colour difference
+//! function used to compare colours.  It is passed as a `diff` argument
+//! implementing [`crate::Metric`] trait.  This can be one of the types
+//! representing the formulæ (e.g. [`crate::Cie00`]) or a closure taking two
+//! L\*a\*b\* colours as `(f32, f32, f32)` tuples.  The `diff_fn` functions
+//! defined in each of the algorithm modules (e.g. [`crate::cie00::diff_fn`])
+//! return suitable closures.
+//!
+//! Sets of colours are accepted as [`Colours`] which is implemented for slices,
+//! vectors, arrays and iterators of any type implementing [`crate::ToLab`].
+//!
+//! Where order of the arguments matters (which is the case for quasimetrics
+//! such as CIE94 and CMC l:c), the function documentation specifies which
+//! colour is passed as the first argument.
+
+use alloc::vec;
+use alloc::vec::Vec;
+
+use crate::{Colours, DiffKey, ToLab};
+
+/// Returns the centroid, i.e. the arithmetic mean, of given colours in the
+/// L\*a\*b\* colour space.
+///
+/// Returns `None` if `colours` is empty.
+///
+/// ## Example
+///
+/// ```
+/// use empfindung::set;
+///
+/// let colours = [(40.0, 10.0, -20.0), (60.0, -10.0, 0.0)];
+/// assert_eq!(Some((50.0, 0.0, -10.0)), set::centroid(&colours));
+/// assert_eq!(None, set::centroid(std::iter::empty::<(f32, f32, f32)>()));
+/// ```
+pub fn centroid(colours: impl Colours) -> Option<(f32, f32, f32)> {
+    let (n, sum) = colours.lab_iter().fold(
+        (0usize, (0.0, 0.0, 0.0)),
+        |(n, sum), lab| (n + 1, (sum.0 + lab.0, sum.1 + lab.1, sum.2 + lab.2)),
+    );
+    if n == 0 {
+        return None;
+    }
+    let n = n as f32;
+    Some((sum.0 / n, sum.1 / n, sum.2 / n))
+}
+
+/// Returns the weighted average of given colours mixed in linear light.
+///
+/// Each element of `colours` is a colour and its non-negative weight (e.g.
+/// the coverage of a pixel by a shape).  The colours are converted into XYZ
+/// where averaging corresponds to physical mixing of light, and the result is
+/// converted back into L\*a\*b\* (using D65 reference white).  This is unlike
+/// [`centroid`] which averages colours in L\*a\*b\* and is not suitable for
+/// modelling colours of anti-aliased edges or downscaled images.  For
+/// example, black and white mixed in equal proportions give grey with
+/// lightness L\* ≈ 76 rather than 50.
+///
+/// Returns `None` if total weight is not positive.
+///
+/// ## Example
+///
+/// ```
+/// use empfindung::set;
+///
+/// let black = (0.0, 0.0, 0.0);
+/// let white = (100.0, 0.0, 0.0);
+/// let grey = set::mix(&[(black, 0.5), (white, 0.5)]).unwrap();
+/// approx::assert_abs_diff_eq!(76.07, grey.0, epsilon = 0.01);
+/// assert_eq!(None, set::mix(&[(black, 0.0), (white, 0.0)]));
+/// ```
+pub fn mix<C: ToLab>(colours: &[(C, f32)]) -> Option<(f32, f32, f32)> {
+    let colours = colours
+        .iter()
+        .map(|(colour, weight)| (colour.to_lab(), *weight));
+    mix_impl(colours)
+}
+
+fn mix_impl(
+    colours: impl Iterator<Item = ((f32, f32, f32), f32)>,
+) -> Option<(f32, f32, f32)> {
+    use crate::xyz::D65;
+
+    let (total, sum) =
+        colours.fold((0.0, (0.0, 0.0, 0.0)), |(total, sum), (lab, weight)| {
+            let xyz = crate::xyz::from_lab(lab, D65);
+            let sum = (
+                sum.0 + xyz.0 * weight,
+                sum.1 + xyz.1 * weight,
+                sum.2 + xyz.2 * weight,
+            );
+            (total + weight, sum)
+        });
+    if total <= 0.0 {
+        return None;
+    }
+    let xyz = (sum.0 / total, sum.1 / total, sum.2 / total);
+    Some(crate::xyz::to_lab(xyz, D65))
+}
+
+/// Error returned by [`diff_block`] when the blocks have different sizes.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct LengthMismatch {
+    /// Number of colours in the first block.
+    pub a: usize,
+    /// Number of colours in the second block.
+    pub b: usize,
+}
+
+impl core::fmt::Display for LengthMismatch {
+    fn fmt(&self, fmtr: &mut core::fmt::Formatter) -> core::fmt::Result {
+        write!(fmtr, "block lengths differ: {} vs {}", self.a, self.b)
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for LengthMismatch {}
+
+/// Returns the colour difference between two blocks of supersampled colours.
+///
+/// Each block (e.g. 2×2 or 3×3 samples of a single rendered pixel) is
+/// averaged in linear light as with [`mix`] with all samples weighted
+/// equally, and the colour difference between the two averages is returned.
+/// Comparing individual samples over-reports differences on sharp edges (such
+/// as in rendered text) which become invisible once the samples are blended
+/// into a single pixel.  Average of block `a` is passed as the first argument
+/// to `diff`.
+///
+/// Returns zero if both blocks are empty and an error if the blocks have
+/// different lengths.
+///
+/// ## Example
+///
+/// ```
+/// use empfindung::{cie00, set};
+///
+/// let black = (0.0, 0.0, 0.0);
+/// let white = (100.0, 0.0, 0.0);
+/// let a = [black, white, white, black];
+/// let b = [white, black, black, white];
+/// let diff = cie00::diff_fn(Default::default());
+/// approx::assert_abs_diff_eq!(0.0, set::diff_block(&a, &b, diff).unwrap(),
+///                             epsilon = 0.001);
+/// assert!(set::diff_block(&a, &b[..3], diff).is_err());
+/// ```
+pub fn diff_block<A: ToLab, B: ToLab>(
+    a: &[A],
+    b: &[B],
+    diff: impl crate::Metric,
+) -> Result<f32, LengthMismatch> {
+    if a.len() != b.len() {
+        return Err(LengthMismatch {
+            a: a.len(),
+            b: b.len(),
+        });
+    }
+    let a = mix_impl(a.iter().map(|colour| (colour.to_lab(), 1.0)));
+    let b = mix_impl(b.iter().map(|colour| (colour.to_lab(), 1.0)));
+    Ok(match (a, b) {
+        (Some(a), Some(b)) => diff.diff(a, b),
+        _ => 0.0,
+    })
+}
+
+/// Returns the largest colour difference between the centroid of a set of
+/// colours and any of the colours.
+///
+/// This is the radius of the smallest sphere centred at the [`centroid`]
+/// containing all the colours as measured by the `diff` function.  It
+/// quantifies how spread out a cluster of colours is and can be used to decide
+/// whether it is tight enough to be represented by a single colour.  The
+/// centroid is passed as the first argument to `diff`.
+///
+/// Returns zero if `colours` is empty.
+///
+/// ## Example
+///
+/// ```
+/// use empfindung::{cie76, set};
+///
+/// let colours = [(40.0, 0.0, 0.0), (60.0, 0.0, 0.0), (50.0, 5.0, 0.0)];
+/// let radius = set::cluster_radius(&colours, cie76::diff_fn());
+/// approx::assert_abs_diff_eq!(10.137, radius, epsilon = 0.001);
+/// ```
+pub fn cluster_radius(
+    colours: impl Colours,
+    diff: impl crate::Metric,
+) -> f32 {
+    let labs = colours.lab_iter().collect::<Vec<_>>();
+    let centre = match centroid(labs.iter()) {
+        Some(centre) => centre,
+        None => return 0.0,
+    };
+    labs.into_iter()
+        .map(|lab| diff.diff(centre, lab))
+        .fold(0.0, f32::max)
+}
+
+/// Returns how well a palette covers a set of target colours.
+///
+/// For each target colour finds the smallest colour difference to any of the
+/// palette entries and returns the largest of those values.  In other words,
+/// this is the covering radius of the palette: every target colour is within
+/// that distance from some palette entry.  Lower is better.  Target colour is
+/// passed as the first argument to `diff`.
+///
+/// Returns zero if there are no targets and infinity if there are targets but
+/// the palette is empty.
+///
+/// ## Example
+///
+/// ```
+/// use empfindung::{cie76, set};
+///
+/// let targets = [(10.0, 0.0, 0.0), (45.0, 0.0, 0.0), (90.0, 0.0, 0.0)];
+/// let palette = [(0.0, 0.0, 0.0), (50.0, 0.0, 0.0), (100.0, 0.0, 0.0)];
+/// let radius = set::coverage(&targets, &palette, cie76::diff_fn());
+/// assert_eq!(10.0, radius);
+/// ```
+pub fn coverage(
+    targets: impl Colours,
+    palette: impl Colours,
+    diff: impl crate::Metric,
+) -> f32 {
+    let palette = palette.lab_iter().collect::<Vec<_>>();
+    targets
+        .lab_iter()
+        .map(|target| {
+            palette
+                .iter()
+                .map(|&entry| diff.diff(target, entry))
+                .fold(f32::INFINITY, f32::min)
+        })
+        .fold(0.0, f32::max)
+}
+
+/// Returns indices of and distance between the two closest colours in a set.
+///
+/// For each pair of indices `i < j`, calculates `diff(colours[i],
+/// colours[j])` and returns `(i, j, distance)` for the pair with the smallest
+/// distance.  If there are multiple such pairs, the first one in
+/// lexicographic order of `(i, j)` is returned.  NaN distances are ordered
+/// after all other values (see [`crate::DiffKey`]).  Returns `None` if there
+/// are fewer than two colours.
+///
+/// ## Example
+///
+/// ```
+/// use empfindung::{cie76, set};
+///
+/// let colours = [(50.0, 0.0, 0.0), (80.0, 0.0, 0.0), (52.0, 0.0, 0.0)];
+/// let got = set::closest_pair(&colours, cie76::diff_fn());
+/// assert_eq!(Some((0, 2, 2.0)), got);
+/// ```
+pub fn closest_pair(
+    colours: impl Colours,
+    diff: impl crate::Metric,
+) -> Option<(usize, usize, f32)> {
+    let labs = colours.lab_iter().collect::<Vec<_>>();
+    closest_pair_impl(&labs, &diff)
+}
+
+fn closest_pair_impl(
+    labs: &[(f32, f32, f32)],
+    diff: &impl crate::Metric,
+) -> Option<(usize, usize, f32)> {
+    let mut best: Option<(usize, usize, f32)> = None;
+    for (i, &lhs) in labs.iter().enumerate() {
+        for (j, &rhs) in labs.iter().enumerate().skip(i + 1) {
+            let distance = diff.diff(lhs, rhs);
+            if best.map_or(true, |best| DiffKey(distance) < DiffKey(best.2)) {
+                best = Some((i, j, distance));
+            }
+        }
+    }
+    best
+}
+
+/// Returns index of and distance to the palette colour closest to the
+/// reference colour.
+///
+/// Calculates `diff(reference, entry)` for each palette entry and returns
+/// `(index, distance)` of the entry with the smallest distance.  If there are
+/// multiple such entries, the one with the lowest index is returned.  NaN
+/// distances are ordered after all other values (see [`crate::DiffKey`]).
+/// Returns `None` if the palette is empty.
+///
+/// The reference colour is converted to L\*a\*b\* once while palette entries
+/// are converted as they are iterated over.  Like with [`closer_of`], only the
+/// order of the distances matters so any function monotonic in the colour
+/// difference can be used.
+///
+/// ## Example
+///
+/// ```
+/// use empfindung::{cie00, set};
+///
+/// let palette = [(0.0, 0.0, 0.0), (50.0, 0.0, 0.0), (100.0, 0.0, 0.0)];
+/// let diff = cie00::diff_fn(cie00::Params::default());
+/// let (index, _) = set::nearest((60.0, 5.0, 5.0), &palette, diff).unwrap();
+/// assert_eq!(1, index);
+/// let empty: [(f32, f32, f32); 0] = [];
+/// assert_eq!(None, set::nearest((60.0, 5.0, 5.0), &empty, diff));
+/// ```
+pub fn nearest(
+    reference: impl ToLab,
+    palette: impl Colours,
+    diff: impl crate::Metric,
+) -> Option<(usize, f32)> {
+    let reference = reference.to_lab();
+    let mut best: Option<(usize, f32)> = None;
+    for (index, entry) in palette.lab_iter().enumerate() {
+        let distance = diff.diff(reference, entry);
+        if best.map_or(true, |best| DiffKey(distance) < DiffKey(best.1)) {
+            best = Some((index, distance));
+        }
+    }
+    best
+}
+
+/// Returns indices of and distances to up to `k` palette colours closest to
+/// the reference colour.
+///
+/// Calculates `diff(reference, entry)` for each palette entry and returns
+/// `(index, distance)` pairs of the `k` entries with the smallest distances
+/// sorted by the distance.  Ties are resolved in favour of entries with lower
+/// index.  If the palette has fewer than `k` entries, all of them are
+/// returned.  NaN distances are ordered after all other values (see
+/// [`crate::DiffKey`]).
+///
+/// The function keeps at most `k` best entries at any time so it runs in
+/// O(n log k) time for a palette with n entries.
+///
+/// ## Example
+///
+/// ```
+/// use empfindung::{cie76, set};
+///
+/// let palette = [(0.0, 0.0, 0.0), (50.0, 0.0, 0.0), (100.0, 0.0, 0.0),
+///                (55.0, 0.0, 0.0)];
+/// let diff = cie76::diff_fn();
+/// assert_eq!(vec![(3, 5.0), (1, 10.0)],
+///            set::nearest_k((60.0, 0.0, 0.0), &palette, 2, diff));
+/// ```
+pub fn nearest_k(
+    reference: impl ToLab,
+    palette: impl Colours,
+    k: usize,
+    diff: impl crate::Metric,
+) -> Vec<(usize, f32)> {
+    if k == 0 {
+        return Vec::new();
+    }
+    let reference = reference.to_lab();
+    // Max-heap so that the worst of the best k entries is at the top.
+    let mut heap = alloc::collections::BinaryHeap::with_capacity(k + 1);
+    for (index, entry) in palette.lab_iter().enumerate() {
+        let item = (DiffKey(diff.diff(reference, entry)), index);
+        if heap.len() < k {
+            heap.push(item);
+        } else if heap.peek().is_some_and(|top| item < *top) {
+            heap.pop();
+            heap.push(item);
+        }
+    }
+    heap.into_sorted_vec()
+        .into_iter()
+        .map(|(DiffKey(distance), index)| (index, distance))
+        .collect()
+}
+
+/// Returns which of two candidate colours is closer to the target.
+///
+/// Calculates `diff(target, a)` and `diff(target, b)` and returns `(0,
+/// distance)` if the former is not greater than the latter or `(1, distance)`
+/// otherwise.  In other words, ties are resolved in favour of `a`.  The target
+/// is passed as the first argument to `diff` so it is treated as the
+/// reference colour by quasimetrics.
+///
+/// Only the order of the distances matters for the choice, so any function
+/// which is monotonic in the colour difference can be used as `diff`.  For
+/// example, a function returning squared Euclidean distance chooses the same
+/// candidate as CIE76 while skipping the square root (though then the
+/// returned distance is squared as well).
+///
+/// ## Example
+///
+/// ```
+/// use empfindung::{cie00, set};
+///
+/// let target = (50.0, 10.0, 10.0);
+/// let diff = cie00::diff_fn(cie00::Params::default());
+/// let (index, _) = set::closer_of(
+///     target, (50.0, 0.0, 0.0), (55.0, 10.0, 10.0), diff);
+/// assert_eq!(1, index);
+/// ```
+pub fn closer_of(
+    target: impl ToLab,
+    a: impl ToLab,
+    b: impl ToLab,
+    diff: impl crate::Metric,
+) -> (usize, f32) {
+    let target = target.to_lab();
+    let distance_a = diff.diff(target, a.to_lab());
+    let distance_b = diff.diff(target, b.to_lab());
+    if distance_b < distance_a {
+        (1, distance_b)
+    } else {
+        (0, distance_a)
+    }
+}
+
+/// Chooses `n` colours from the candidates which are as distinct from each
+/// other as possible.
+///
+/// Uses greedy farthest-point selection: starts with the two candidates which
+/// are farthest from each other and then repeatedly adds the candidate whose
+/// distance to the closest already chosen colour is the largest.  This does
+/// not guarantee an optimal solution but gives good results in practice and
+/// is useful for generating categorical colour scales.
+///
+/// Returns indices of the chosen candidates in order in which they were
+/// selected.  If `n` is greater than number of candidates, all candidates are
+/// returned.  Ties are resolved in favour of lower indices.  When measuring
+/// distances, the already chosen colour (or the one with the lower index for
+/// the initial pair) is passed as the first argument to `diff`.
+///
+/// ## Example
+///
+/// ```
+/// use empfindung::{cie76, set};
+///
+/// let candidates = [
+///     (50.0, 0.0, 0.0), (0.0, 0.0, 0.0), (100.0, 0.0, 0.0),
+///     (55.0, 0.0, 0.0), (25.0, 0.0, 0.0),
+/// ];
+/// let chosen = set::max_distinct(&candidates, 3, cie76::diff_fn());
+/// assert_eq!(vec![1, 2, 0], chosen);
+/// ```
+pub fn max_distinct(
+    candidates: impl Colours,
+    n: usize,
+    diff: impl crate::Metric,
+) -> Vec<usize> {
+    let labs = candidates.lab_iter().collect::<Vec<_>>();
+    let n = n.min(labs.len());
+    if n == 0 {
+        return Vec::new();
+    }
+
+    let mut chosen = Vec::with_capacity(n);
+    let mut best: Option<(usize, usize, f32)> = None;
+    for (i, &lhs) in labs.iter().enumerate() {
+        for (j, &rhs) in labs.iter().enumerate().skip(i + 1) {
+            let distance = diff.diff(lhs, rhs);
+            if best.map_or(true, |best| distance > best.2) {
+                best = Some((i, j, distance));
+            }
+        }
+    }
+    match best {
+        Some((i, j, _)) => chosen.extend_from_slice(&[i, j][..n.min(2)]),
+        None => chosen.push(0),
+    }
+
+    // Distance of each candidate to the closest chosen colour.  Chosen
+    // colours have it set to negative value so they are never picked again.
+    let mut min_distance = vec![f32::INFINITY; labs.len()];
+    for &idx in chosen.iter() {
+        update_min_distance(&labs, &mut min_distance, idx, &diff);
+    }
+    while chosen.len() < n {
+        let mut pick = 0;
+        for (idx, &distance) in min_distance.iter().enumerate() {
+            if distance > min_distance[pick] {
+                pick = idx;
+            }
+        }
+        chosen.push(pick);
+        update_min_distance(&labs, &mut min_distance, pick, &diff);
+    }
+    chosen
+}
+
+fn update_min_distance(
+    labs: &[(f32, f32, f32)],
+    min_distance: &mut [f32],
+    chosen: usize,
+    diff: &impl crate::Metric,
+) {
+    for (distance, &lab) in min_distance.iter_mut().zip(labs.iter()) {
+        *distance = distance.min(diff.diff(labs[chosen], lab));
+    }
+    min_distance[chosen] = -1.0;
+}
+
+/// Merges colours which are closer to each other than given threshold.
+///
+/// Repeatedly finds the closest pair of colours (see [`closest_pair`]) and,
+/// if their distance is below `threshold`, replaces them by their centroid.
+/// The centroid is weighted by how many of the original colours were merged
+/// into each of the two so that the result does not depend on the order of
+/// merges as much.  The process stops once no pair of colours is closer than
+/// `threshold`.
+///
+/// Returns the reduced palette as L\*a\*b\* colours.  Colours which were not
+/// merged with any other are returned unchanged and the relative order of the
+/// colours is preserved (with a merged colour taking place of the first of
+/// the pair).
+///
+/// ## Example
+///
+/// ```
+/// use empfindung::{cie76, set};
+///
+/// let palette = [
+///     (50.0, 0.0, 0.0), (80.0, 0.0, 0.0), (52.0, 0.0, 0.0),
+///     (81.0, 0.0, 0.0),
+/// ];
+/// let merged = set::merge_similar(palette, 5.0, cie76::diff_fn());
+/// assert_eq!(vec![(51.0, 0.0, 0.0), (80.5, 0.0, 0.0)], merged);
+/// ```
+pub fn merge_similar(
+    palette: impl Colours,
+    threshold: f32,
+    diff: impl crate::Metric,
+) -> Vec<(f32, f32, f32)> {
+    let mut labs = palette.lab_iter().collect::<Vec<_>>();
+    let mut weights = vec![1.0f32; labs.len()];
+    loop {
+        let (i, j) = match closest_pair_impl(&labs, &diff) {
+            Some((i, j, distance)) if distance < threshold => (i, j),
+            _ => break,
+        };
+        let (lhs, rhs) = (labs[i], labs.remove(j));
+        let (w_lhs, w_rhs) = (weights[i], weights.remove(j));
+        let w = w_lhs + w_rhs;
+        labs[i] = (
+            (lhs.0 * w_lhs + rhs.0 * w_rhs) / w,
+            (lhs.1 * w_lhs + rhs.1 * w_rhs) / w,
+            (lhs.2 * w_lhs + rhs.2 * w_rhs) / w,
+        );
+        weights[i] = w;
+    }
+    labs
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_colours_containers() {
+        let colours = [(40.0, 10.0, -20.0), (60.0, -10.0, 0.0)];
+        let want = Some((50.0, 0.0, -10.0));
+        assert_eq!(want, centroid(colours));
+        assert_eq!(want, centroid(&colours[..]));
+        assert_eq!(want, centroid(colours.to_vec()));
+        assert_eq!(want, centroid(colours.iter().copied()));
+        assert_eq!(want, centroid(colours.iter().filter(|c| c.0 > 0.0)));
+    }
+
+    #[test]
+    fn test_mix() {
+        let black = (0.0, 0.0, 0.0);
+        let white = (100.0, 0.0, 0.0);
+
+        let grey = mix(&[(black, 1.0), (white, 1.0)]).unwrap();
+        approx::assert_abs_diff_eq!(76.0693, grey.0, epsilon = 0.001);
+        approx::assert_abs_diff_eq!(0.0, grey.1, epsilon = 0.001);
+        approx::assert_abs_diff_eq!(0.0, grey.2, epsilon = 0.001);
+
+        // Weights are normalised and a single colour mixes to itself.
+        let colour = (54.528, 42.416, 54.497);
+        let got = mix(&[(colour, 0.3)]).unwrap();
+        approx::assert_abs_diff_eq!(colour.0, got.0, epsilon = 0.001);
+        approx::assert_abs_diff_eq!(colour.1, got.1, epsilon = 0.001);
+        approx::assert_abs_diff_eq!(colour.2, got.2, epsilon = 0.001);
+
+        // Zero weight colours are ignored.
+        let got = mix(&[(colour, 2.0), (black, 0.0)]).unwrap();
+        approx::assert_abs_diff_eq!(colour.0, got.0, epsilon = 0.001);
+
+        assert_eq!(None, mix::<(f32, f32, f32)>(&[]));
+        assert_eq!(None, mix(&[(black, 0.0), (white, 0.0)]));
+    }
+
+    #[test]
+    fn test_diff_block() {
+        let black = (0.0, 0.0, 0.0);
+        let white = (100.0, 0.0, 0.0);
+        let diff = crate::cie00::diff_fn(Default::default());
+
+        let block = [(54.528, 42.416, 54.497), black, white, white];
+        assert_eq!(Ok(0.0), diff_block(&block, &block, diff));
+        let empty: [(f32, f32, f32); 0] = [];
+        assert_eq!(Ok(0.0), diff_block(&empty, &empty, diff));
+
+        // Half of the samples differ.  Difference is the same as between
+        // colours mixed by hand and smaller than between individual samples.
+        let got = diff_block(&[white; 4], &[white, black, white, black], diff)
+            .unwrap();
+        let grey = mix(&[(white, 1.0), (black, 1.0)]).unwrap();
+        approx::assert_abs_diff_eq!(diff(white, grey), got, epsilon = 0.001);
+        assert!(0.0 < got && got < diff(white, black), "{}", got);
+
+        // Samples may be given in any order and colour representations may
+        // differ between the blocks.
+        let arrays: [[f32; 3]; 2] = [[0.0, 0.0, 0.0], [100.0, 0.0, 0.0]];
+        let got = diff_block(&[white, black], &arrays, diff).unwrap();
+        approx::assert_abs_diff_eq!(0.0, got, epsilon = 0.001);
+
+        assert_eq!(
+            Err(LengthMismatch { a: 2, b: 3 }),
+            diff_block(&[white; 2], &[white; 3], diff)
+        );
+        assert_eq!(
+            "block lengths differ: 2 vs 3",
+            LengthMismatch { a: 2, b: 3 }.to_string()
+        );
+    }
+
+    #[test]
+    fn test_cluster_radius() {
+        let diff = crate::cie76::diff_fn();
+        let empty: [(f32, f32, f32); 0] = [];
+        assert_eq!(0.0, cluster_radius(empty, diff));
+        assert_eq!(0.0, cluster_radius([(50.0, 10.0, 10.0)], diff));
+        let colours = [(40.0, 0.0, 0.0), (60.0, 0.0, 0.0)];
+        assert_eq!(10.0, cluster_radius(colours, diff));
+    }
+
+    #[test]
+    fn test_coverage() {
+        let diff = crate::cie76::diff_fn();
+        let empty: [(f32, f32, f32); 0] = [];
+        let colours = [(10.0, 0.0, 0.0), (20.0, 0.0, 0.0)];
+        assert_eq!(0.0, coverage(empty, colours, diff));
+        assert_eq!(f32::INFINITY, coverage(colours, empty, diff));
+        assert_eq!(0.0, coverage(colours, colours, diff));
+        assert_eq!(5.0, coverage(colours, [(15.0, 0.0, 0.0)], diff));
+    }
+
+    #[test]
+    fn test_closest_pair() {
+        let diff = crate::cie76::diff_fn();
+        let empty: [(f32, f32, f32); 0] = [];
+        assert_eq!(None, closest_pair(empty, diff));
+        assert_eq!(None, closest_pair([(50.0, 0.0, 0.0)], diff));
+
+        // Ties resolve to the first pair.
+        let colours = [(10.0, 0.0, 0.0), (20.0, 0.0, 0.0), (30.0, 0.0, 0.0)];
+        assert_eq!(Some((0, 1, 10.0)), closest_pair(colours, diff));
+
+        // NaN distances are ordered last.
+        let colours =
+            [(f32::NAN, 0.0, 0.0), (20.0, 0.0, 0.0), (30.0, 0.0, 0.0)];
+        assert_eq!(Some((1, 2, 10.0)), closest_pair(colours, diff));
+        let got = closest_pair([(f32::NAN, 0.0, 0.0), (20.0, 0.0, 0.0)], diff);
+        assert!(matches!(got, Some((0, 1, distance)) if distance.is_nan()));
+    }
+
+    #[test]
+    fn test_closer_of() {
+        let diff = crate::cie76::diff_fn();
+        let target = (50.0, 0.0, 0.0);
+        let (near, far) = ((52.0, 0.0, 0.0), (40.0, 0.0, 0.0));
+        assert_eq!((0, 2.0), closer_of(target, near, far, diff));
+        assert_eq!((1, 2.0), closer_of(target, far, near, diff));
+        // Ties resolve to the first candidate.
+        assert_eq!((0, 2.0), closer_of(target, near, near, diff));
+        assert_eq!(
+            (0, 2.0),
+            closer_of(target, near, (48.0, 0.0, 0.0), diff)
+        );
+    }
+
+    #[test]
+    fn test_nearest() {
+        let diff = crate::cie76::diff_fn();
+        let empty: [(f32, f32, f32); 0] = [];
+        assert_eq!(None, nearest((50.0, 0.0, 0.0), empty, diff));
+
+        let palette = [
+            (40.0, 0.0, 0.0),
+            (52.0, 0.0, 0.0),
+            (48.0, 0.0, 0.0),
+            (52.0, 0.0, 0.0),
+        ];
+        assert_eq!(Some((1, 2.0)), nearest((50.0, 0.0, 0.0), palette, diff));
+        assert_eq!(Some((0, 5.0)), nearest((35.0, 0.0, 0.0), palette, diff));
+        // Ties resolve to the lowest index.
+        assert_eq!(Some((1, 0.0)), nearest((52.0, 0.0, 0.0), palette, diff));
+
+        // NaN distances are ordered last, consistently with nearest_k.
+        let palette =
+            [(f32::NAN, 0.0, 0.0), (40.0, 0.0, 0.0), (52.0, 0.0, 0.0)];
+        let reference = (50.0, 0.0, 0.0);
+        assert_eq!(Some((2, 2.0)), nearest(reference, palette, diff));
+        assert_eq!(
+            nearest(reference, palette, diff),
+            nearest_k(reference, palette, 1, diff).first().copied()
+        );
+        let got = nearest(reference, [(f32::NAN, 0.0, 0.0)], diff);
+        assert!(matches!(got, Some((0, distance)) if distance.is_nan()));
+    }
+
+    #[test]
+    fn test_nearest_k() {
+        let diff = crate::cie00::diff_fn(crate::cie00::Params::default());
+        let empty: [(f32, f32, f32); 0] = [];
+        assert!(nearest_k((50.0, 0.0, 0.0), empty, 3, diff).is_empty());
+
+        let colours = crate::testutil::generate_colours(100);
+        let (reference, palette) = (colours[0], &colours[1..]);
+        let mut want = palette
+            .iter()
+            .enumerate()
+            .map(|(index, entry)| (index, diff(reference, *entry)))
+            .collect::<Vec<_>>();
+        want.sort_by_key(|&(index, delta)| (crate::DiffKey(delta), index));
+        for k in [0, 1, 3, 10, 99, 200] {
+            let got = nearest_k(reference, palette, k, diff);
+            assert_eq!(&want[..k.min(want.len())], &got[..]);
+        }
+        assert_eq!(
+            nearest(reference, palette, diff),
+            nearest_k(reference, palette, 1, diff).first().copied()
+        );
+
+        // Ties resolve to the lowest index.
+        let palette = [
+            (52.0, 0.0, 0.0),
+            (48.0, 0.0, 0.0),
+            (52.0, 0.0, 0.0),
+            (48.0, 0.0, 0.0),
+            (50.0, 0.0, 0.0),
+        ];
+        let diff = crate::cie76::diff_fn();
+        assert_eq!(
+            vec![(4, 0.0), (0, 2.0), (1, 2.0)],
+            nearest_k((50.0, 0.0, 0.0), palette, 3, diff)
+        );
+    }
+
+    #[test]
+    fn test_max_distinct() {
+        let diff = crate::cie76::diff_fn();
+        let empty: [(f32, f32, f32); 0] = [];
+        assert!(max_distinct(empty, 3, diff).is_empty());
+
+        let candidates = [
+            (50.0, 0.0, 0.0),
+            (50.0, 60.0, 0.0),
+            (50.0, -60.0, 0.0),
+            (50.0, 0.0, 60.0),
+            (50.0, 55.0, 5.0),
+        ];
+        assert!(max_distinct(candidates, 0, diff).is_empty());
+        assert_eq!(vec![1], max_distinct(candidates, 1, diff));
+        assert_eq!(vec![1, 2], max_distinct(candidates, 2, diff));
+        assert_eq!(vec![1, 2, 3], max_distinct(candidates, 3, diff));
+        assert_eq!(vec![1, 2, 3, 0, 4], max_distinct(candidates, 10, diff));
+        assert_eq!(vec![0], max_distinct([(1.0, 2.0, 3.0)], 2, diff));
+    }
+
+    #[test]
+    fn test_merge_similar() {
+        let diff = crate::cie76::diff_fn();
+        let palette = [(10.0, 0.0, 0.0), (20.0, 0.0, 0.0), (30.0, 0.0, 0.0)];
+
+        // Nothing closer than threshold.
+        let got = merge_similar(palette, 10.0, diff);
+        assert_eq!(&palette[..], &got[..]);
+
+        // Everything collapses into the centroid.
+        let got = merge_similar(palette, 16.0, diff);
+        assert_eq!(vec![(20.0, 0.0, 0.0)], got);
+
+        let empty: [(f32, f32, f32); 0] = [];
+        assert!(merge_similar(empty, 1.0, diff).is_empty());
+    }
+
+    #[test]
+    fn test_merge_similar_weighted() {
+        // First two colours get merged to 11.  Third is then at distance 9.5
+        // which is below the threshold.  Weighted centroid is 14.17 rather
+        // than 15.75 which plain average of the two would give.
+        let palette = [(10.0, 0.0, 0.0), (12.0, 0.0, 0.0), (20.5, 0.0, 0.0)];
+        let got = merge_similar(palette, 10.0, crate::cie76::diff_fn());
+        assert_eq!(1, got.len());
+        approx::assert_abs_diff_eq!(14.166667, got[0].0, epsilon = 0.001);
+    }
+}
@@ -0,0 +1,295 @@
+// Vectorised CIEDE2000 implementation.
+// Copyright (c) 2026 Michał Nazarewicz <mina86@mina86.com>
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in
+// all copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+
+//! CIEDE2000 calculation on eight colour pairs at once.
+//!
+//! Used by [`crate::cie00::diff_slice_simd`] when `simd` feature is enabled.
+//! Trigonometric and exponential functions are approximated with polynomials
+//! since the standard library offers no vectorised implementations.
+//!
+//! By default the vectors are the array-backed types from the `lanes` module
+//! which work on stable compilers.  With `--cfg empfindung_portable_simd`
+//! (which requires a nightly compiler) `std::simd` types are used instead.
+
+use std::f32::consts::{FRAC_PI_2, LOG2_E, PI, TAU};
+#[cfg(empfindung_portable_simd)]
+pub(crate) use std::simd::f32x8;
+#[cfg(empfindung_portable_simd)]
+use std::simd::{
+    cmp::{SimdPartialEq, SimdPartialOrd},
+    num::SimdFloat,
+    Select, StdFloat,
+};
+
+#[cfg(not(empfindung_portable_simd))]
+pub(crate) use lanes::f32x8;
+
+#[cfg(not(empfindung_portable_simd))]
+mod lanes;
+
+use crate::cie00::Params;
+
+/// Number of colour pairs processed at once.
+pub(crate) const LANES: usize = 8;
+
+const TWENTY_FIVE_TO_SEVENTH: f32 = 6103515625.0;
+
+/// Returns CIEDE2000 colour differences between corresponding lanes of two
+/// sets of L\*a\*b\* colours given as three vectors holding L\*, a\* and b\*
+/// coordinates.
+pub(crate) fn diff(
+    colour_1: [f32x8; 3],
+    colour_2: [f32x8; 3],
+    ksub: Params,
+) -> f32x8 {
+    let [l_1, a_1, b_1] = colour_1;
+    let [l_2, a_2, b_2] = colour_2;
+    let half = f32x8::splat(0.5);
+    let one = f32x8::splat(1.0);
+    let zero = f32x8::splat(0.0);
+
+    let l_bar = (l_1 + l_2) * half;
+    let delta_l = l_2 - l_1;
+
+    let c_1 = hypot(a_1, b_1);
+    let c_2 = hypot(a_2, b_2);
+
+    let tmp = pow7((c_1 + c_2) * half);
+    let tmp = f32x8::splat(1.5) -
+        (tmp / (tmp + f32x8::splat(TWENTY_FIVE_TO_SEVENTH))).sqrt() * half;
+    let a_prime_1 = a_1 * tmp;
+    let a_prime_2 = a_2 * tmp;
+
+    let c_prime_1 = hypot(a_prime_1, b_1);
+    let c_prime_2 = hypot(a_prime_2, b_2);
+    let c_prime_bar = (c_prime_1 + c_prime_2) * half;
+    let delta_c_prime = c_prime_2 - c_prime_1;
+
+    let tmp = l_bar - f32x8::splat(50.0);
+    let tmp = tmp * tmp;
+    let s_sub_l =
+        one + (f32x8::splat(0.015) * tmp) / (f32x8::splat(20.0) + tmp).sqrt();
+    let s_sub_c = one + f32x8::splat(0.045) * c_prime_bar;
+
+    let h_prime_1 = h_prime(b_1, a_prime_1);
+    let h_prime_2 = h_prime(b_2, a_prime_2);
+
+    let pi = f32x8::splat(PI);
+    let tau = f32x8::splat(TAU);
+    let delta_h_prime = h_prime_2 - h_prime_1;
+    let delta_h_prime = delta_h_prime.abs().simd_le(pi).select(
+        delta_h_prime,
+        h_prime_2
+            .simd_le(h_prime_1)
+            .select(delta_h_prime + tau, delta_h_prime - tau),
+    );
+    let delta_h_prime =
+        (c_1.simd_eq(zero) | c_2.simd_eq(zero)).select(zero, delta_h_prime);
+
+    let delta_upcase_h_prime = f32x8::splat(2.0) *
+        (c_prime_1 * c_prime_2).sqrt() *
+        sin(half * delta_h_prime);
+
+    let h_bar = (h_prime_1 + h_prime_2) * half;
+    let h_bar = (h_prime_1 - h_prime_2)
+        .abs()
+        .simd_gt(pi)
+        .select(h_bar + pi, h_bar);
+
+    let upcase_t = one -
+        f32x8::splat(0.17) * cos(h_bar - f32x8::splat(TAU / 12.0)) +
+        f32x8::splat(0.24) * cos(f32x8::splat(2.0) * h_bar) +
+        f32x8::splat(0.32) *
+            cos(f32x8::splat(3.0) * h_bar + f32x8::splat(TAU / 60.0)) -
+        f32x8::splat(0.20) *
+            cos(f32x8::splat(4.0) * h_bar - f32x8::splat(TAU * 7.0 / 40.0));
+    let s_sub_upcase_h = one + f32x8::splat(0.015) * c_prime_bar * upcase_t;
+
+    let c7 = pow7(c_prime_bar);
+    let h = h_bar * f32x8::splat(14.4 / TAU) - f32x8::splat(11.0);
+    let r_sub_t = f32x8::splat(-2.0) *
+        (c7 / (c7 + f32x8::splat(TWENTY_FIVE_TO_SEVENTH))).sqrt() *
+        sin(exp(-h * h) * f32x8::splat(TAU / 6.0));
+
+    let lightness = delta_l / (f32x8::splat(ksub.l) * s_sub_l);
+    let chroma = delta_c_prime / (f32x8::splat(ksub.c) * s_sub_c);
+    let hue = delta_upcase_h_prime / (f32x8::splat(ksub.h) * s_sub_upcase_h);
+
+    (lightness * lightness +
+        chroma * chroma +
+        hue * hue +
+        r_sub_t * chroma * hue)
+        .sqrt()
+}
+
+/// Returns `sqrt(x² + y²)` without overflow or underflow of the intermediate
+/// values.
+///
+/// The smaller magnitude is scaled by the larger one so the squared value is
+/// at most one.  This matters for very large coordinates (whose squares would
+/// overflow to infinity) and subnormal ones (whose squares would flush to
+/// zero).
+fn hypot(x: f32x8, y: f32x8) -> f32x8 {
+    let (x, y) = (x.abs(), y.abs());
+    let max = x.simd_max(y);
+    let ratio = x.simd_min(y) / max;
+    let res = max * (f32x8::splat(1.0) + ratio * ratio).sqrt();
+    // Zero and infinite max make the ratio NaN; the result is max then.
+    let special = max.simd_eq(f32x8::splat(0.0)) | max.is_infinite();
+    special.select(max, res)
+}
+
+fn pow7(x: f32x8) -> f32x8 {
+    let x2 = x * x;
+    let x3 = x2 * x;
+    x3 * x3 * x
+}
+
+/// Returns hue angle in `0..2π` range; zero if both arguments are zero.
+fn h_prime(y: f32x8, x: f32x8) -> f32x8 {
+    let rad = atan2(y, x);
+    rad.simd_lt(f32x8::splat(0.0))
+        .select(rad + f32x8::splat(TAU), rad)
+}
+
+/// Returns an approximation of `y.atan2(x)`.  The absolute error is below
+/// 10⁻⁵.  Returns zero if both arguments are zero.
+fn atan2(y: f32x8, x: f32x8) -> f32x8 {
+    let zero = f32x8::splat(0.0);
+    let (abs_x, abs_y) = (x.abs(), y.abs());
+    let max = abs_x.simd_max(abs_y);
+    let min = abs_x.simd_min(abs_y);
+    let t = max.simd_eq(zero).select(zero, min / max);
+    let s = t * t;
+    // Minimax approximation of atan(t) for t in [0, 1].
+    let mut r = f32x8::splat(-0.01172120);
+    for &coeff in &[0.05265332, -0.11643287, 0.19354346, -0.33262347] {
+        r = r.mul_add(s, f32x8::splat(coeff));
+    }
+    let r = r.mul_add(s, f32x8::splat(0.99997726)) * t;
+    let r = abs_y.simd_gt(abs_x).select(f32x8::splat(FRAC_PI_2) - r, r);
+    let r = x.simd_lt(zero).select(f32x8::splat(PI) - r, r);
+    y.simd_lt(zero).select(-r, r)
+}
+
+/// Returns an approximation of cosine of `x`.  For arguments within ±40 (which
+/// covers all values used in the formula) the absolute error is below 4·10⁻⁶.
+fn cos(x: f32x8) -> f32x8 {
+    // Reduce to [-π, π] and then to [0, π/2] using cos(x) = -cos(π - x).
+    let x =
+        (x - (x * f32x8::splat(1.0 / TAU)).round() * f32x8::splat(TAU)).abs();
+    let flip = x.simd_gt(f32x8::splat(FRAC_PI_2));
+    let x = flip.select(f32x8::splat(PI) - x, x);
+    // Taylor series up to x¹⁰ term.
+    let s = x * x;
+    let mut r = f32x8::splat(-1.0 / 3628800.0);
+    for &coeff in &[1.0 / 40320.0, -1.0 / 720.0, 1.0 / 24.0, -0.5, 1.0] {
+        r = r.mul_add(s, f32x8::splat(coeff));
+    }
+    flip.select(-r, r)
+}
+
+/// Returns an approximation of sine of `x`.  See [`cos`].
+fn sin(x: f32x8) -> f32x8 { cos(x - f32x8::splat(FRAC_PI_2)) }
+
+/// Returns an approximation of `x.exp()` for non-positive `x`.  The relative
+/// error is below 10⁻⁶.  Results which would be subnormal are flushed to
+/// zero.
+fn exp(x: f32x8) -> f32x8 {
+    let t = x * f32x8::splat(LOG2_E);
+    let underflow = t.simd_lt(f32x8::splat(-126.0));
+    let t = t.simd_max(f32x8::splat(-126.0));
+    let n = t.floor();
+    let f = t - n;
+    // Minimax approximation of 2^f for f in [0, 1).
+    let mut r = f32x8::splat(1.8775767e-3);
+    for &coeff in &[8.9893397e-3, 5.5826318e-2, 2.4015361e-1, 6.9315308e-1] {
+        r = r.mul_add(f, f32x8::splat(coeff));
+    }
+    let r = r.mul_add(f, f32x8::splat(1.0));
+    underflow.select(f32x8::splat(0.0), r * exp2_int(n))
+}
+
+/// Returns `2ⁿ` for each lane holding an integer `n` in `-126..=127` range.
+#[cfg(empfindung_portable_simd)]
+fn exp2_int(n: f32x8) -> f32x8 {
+    use std::simd::{i32x8, num::SimdInt};
+    let bits = (n.cast::<i32>() + i32x8::splat(127)) << i32x8::splat(23);
+    f32x8::from_bits(bits.cast::<u32>())
+}
+
+#[cfg(not(empfindung_portable_simd))]
+fn exp2_int(n: f32x8) -> f32x8 { n.exp2_int() }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn check(
+        name: &str,
+        approx: impl Fn(f32x8) -> f32x8,
+        exact: impl Fn(f32) -> f32,
+        range: (f32, f32),
+        epsilon: f32,
+    ) {
+        const STEPS: usize = 10_000;
+        let step = (range.1 - range.0) / STEPS as f32;
+        for i in (0..STEPS).step_by(LANES) {
+            let xs = f32x8::from_array(core::array::from_fn(|lane| {
+                range.0 + (i + lane) as f32 * step
+            }));
+            let got = approx(xs).to_array();
+            for (x, got) in xs.to_array().iter().zip(got.iter()) {
+                let want = exact(*x);
+                let err = (want - got).abs();
+                assert!(
+                    err <= epsilon * want.abs().max(1.0),
+                    "{}({}): {} ≠ {}",
+                    name,
+                    x,
+                    want,
+                    got
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_approximations() {
+        check("cos", cos, f32::cos, (-40.0, 40.0), 4e-6);
+        check("sin", sin, f32::sin, (-40.0, 40.0), 4e-6);
+        check("exp", exp, f32::exp, (-100.0, 0.0), 1e-6);
+        let atan = |x| atan2(x, f32x8::splat(1.0));
+        check("atan", atan, f32::atan, (-20.0, 20.0), 1e-5);
+        let atan = |x| atan2(f32x8::splat(-1.0), x);
+        check("atan2", atan, |x| (-1f32).atan2(x), (-20.0, 20.0), 1e-5);
+        let zero = f32x8::splat(0.0);
+        assert_eq!(zero, atan2(zero, zero));
+    }
+
+    #[test]
+    fn test_hypot() {
+        for (x, y) in crate::float::tests::hypot_test_cases() {
+            let got = hypot(f32x8::splat(x), f32x8::splat(y)).to_array()[0];
+            crate::float::tests::check_hypot(x, y, got);
+        }
+    }
+}
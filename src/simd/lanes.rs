@@ -0,0 +1,147 @@
+// Eight-lane vector types for stable compilers.
+// Copyright (c) 2026 Michał Nazarewicz <mina86@mina86.com>
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in
+// all copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+
+//! Subset of `std::simd` API used by the vectorised CIEDE2000 implementation.
+//!
+//! The types hold plain arrays and every operation is a loop over the lanes
+//! which the compiler is able to turn into SIMD instructions.  This lets the
+//! `simd` feature build on stable compilers; portable SIMD is used instead
+//! when compiling with `--cfg empfindung_portable_simd` on nightly.
+
+use core::ops::{Add, BitOr, Div, Mul, Neg, Sub};
+
+use super::LANES;
+
+/// Vector of eight `f32` values.
+#[allow(non_camel_case_types)]
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub(crate) struct f32x8([f32; LANES]);
+
+/// Vector of eight booleans resulting from lane-wise comparisons.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub(crate) struct Mask([bool; LANES]);
+
+impl f32x8 {
+    #[inline]
+    pub fn splat(value: f32) -> Self { Self([value; LANES]) }
+    #[inline]
+    pub fn from_array(array: [f32; LANES]) -> Self { Self(array) }
+    #[cfg(test)]
+    pub fn to_array(self) -> [f32; LANES] { self.0 }
+    #[inline]
+    pub fn as_array(&self) -> &[f32; LANES] { &self.0 }
+
+    #[inline]
+    fn map(self, f: impl Fn(f32) -> f32) -> Self { Self(self.0.map(f)) }
+
+    #[inline]
+    fn zip(self, other: Self, f: impl Fn(f32, f32) -> f32) -> Self {
+        Self(core::array::from_fn(|i| f(self.0[i], other.0[i])))
+    }
+
+    #[inline]
+    fn test(self, other: Self, f: impl Fn(f32, f32) -> bool) -> Mask {
+        Mask(core::array::from_fn(|i| f(self.0[i], other.0[i])))
+    }
+
+    #[inline]
+    pub fn abs(self) -> Self { self.map(f32::abs) }
+    #[inline]
+    pub fn sqrt(self) -> Self { self.map(f32::sqrt) }
+    #[inline]
+    pub fn round(self) -> Self { self.map(f32::round) }
+    #[inline]
+    pub fn floor(self) -> Self { self.map(f32::floor) }
+    #[inline]
+    pub fn mul_add(self, a: Self, b: Self) -> Self {
+        Self(core::array::from_fn(|i| self.0[i].mul_add(a.0[i], b.0[i])))
+    }
+    #[inline]
+    pub fn simd_max(self, other: Self) -> Self { self.zip(other, f32::max) }
+    #[inline]
+    pub fn simd_min(self, other: Self) -> Self { self.zip(other, f32::min) }
+
+    #[inline]
+    pub fn simd_eq(self, other: Self) -> Mask {
+        self.test(other, |a, b| a == b)
+    }
+    #[inline]
+    pub fn simd_lt(self, other: Self) -> Mask { self.test(other, |a, b| a < b) }
+    #[inline]
+    pub fn simd_le(self, other: Self) -> Mask {
+        self.test(other, |a, b| a <= b)
+    }
+    #[inline]
+    pub fn simd_gt(self, other: Self) -> Mask { self.test(other, |a, b| a > b) }
+    #[inline]
+    pub fn is_infinite(self) -> Mask { self.test(self, |a, _| a.is_infinite()) }
+
+    /// Returns `2ⁿ` for each lane holding an integer `n` in `-126..=127`
+    /// range by constructing the exponent bits directly.
+    #[inline]
+    pub fn exp2_int(self) -> Self {
+        self.map(|n| f32::from_bits(((n as i32 + 127) << 23) as u32))
+    }
+}
+
+impl Mask {
+    /// Returns vector with lanes taken from `yes` where the mask is set and
+    /// from `no` elsewhere.
+    #[inline]
+    pub fn select(self, yes: f32x8, no: f32x8) -> f32x8 {
+        f32x8(core::array::from_fn(|i| {
+            if self.0[i] {
+                yes.0[i]
+            } else {
+                no.0[i]
+            }
+        }))
+    }
+}
+
+impl BitOr for Mask {
+    type Output = Self;
+    #[inline]
+    fn bitor(self, other: Self) -> Self {
+        Self(core::array::from_fn(|i| self.0[i] | other.0[i]))
+    }
+}
+
+macro_rules! impl_op {
+    ($trait:ident, $fn:ident, $op:tt) => {
+        impl $trait for f32x8 {
+            type Output = Self;
+            #[inline]
+            fn $fn(self, other: Self) -> Self { self.zip(other, |a, b| a $op b) }
+        }
+    };
+}
+
+impl_op!(Add, add, +);
+impl_op!(Sub, sub, -);
+impl_op!(Mul, mul, *);
+impl_op!(Div, div, /);
+
+impl Neg for f32x8 {
+    type Output = Self;
+    #[inline]
+    fn neg(self) -> Self { self.map(|x| -x) }
+}
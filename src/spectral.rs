@@ -0,0 +1,514 @@
+// Spectral data handling.
+// Copyright (c) 2026 Michał Nazarewicz <mina86@mina86.com>
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in
+// all copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+
+//! Handling of colours given by their reflectance spectra.
+//!
+//! The module is available with the `spectral` feature enabled.  It provides
+//! [`Spectrum`] type which describes a surface colour by its spectral
+//! reflectance (as measured e.g. by a spectrophotometer).  The type implements
+//! [`ToLab`] trait which integrates the spectrum against the CIE 1931 standard
+//! observer under chosen [`Illuminant`] (D65 by default) so that spectral
+//! measurements can be passed directly to colour difference functions.
+//!
+//! The bundled colour matching functions and illuminant spectral power
+//! distributions are tabulated at 10 nm intervals from 380 to 780 nm.
+
+use alloc::vec::Vec;
+use core::convert::TryFrom;
+
+use crate::xyz::WhitePoint;
+use crate::ToLab;
+
+/// Wavelength of the first entry of the bundled tables in nanometres.
+const START_NM: f32 = 380.0;
+/// Interval between entries of the bundled tables in nanometres.
+const STEP_NM: f32 = 10.0;
+/// Number of entries in the bundled tables.
+const LEN: usize = 41;
+
+/// The CIE 1931 2° standard observer colour matching functions x̄, ȳ and z̄.
+#[rustfmt::skip]
+const OBSERVER: [[f32; 3]; LEN] = [
+    [0.001368, 0.000039, 0.006450],
+    [0.004243, 0.000120, 0.020050],
+    [0.014310, 0.000396, 0.067850],
+    [0.043510, 0.001210, 0.207400],
+    [0.134380, 0.004000, 0.645600],
+    [0.283900, 0.011600, 1.385600],
+    [0.348280, 0.023000, 1.747060],
+    [0.336200, 0.038000, 1.772110],
+    [0.290800, 0.060000, 1.669200],
+    [0.195360, 0.090980, 1.287640],
+    [0.095640, 0.139020, 0.812950],
+    [0.032010, 0.208020, 0.465180],
+    [0.004900, 0.323000, 0.272000],
+    [0.009300, 0.503000, 0.158200],
+    [0.063270, 0.710000, 0.078250],
+    [0.165500, 0.862000, 0.042160],
+    [0.290400, 0.954000, 0.020300],
+    [0.433450, 0.994950, 0.008750],
+    [0.594500, 0.995000, 0.003900],
+    [0.762100, 0.952000, 0.002100],
+    [0.916300, 0.870000, 0.001650],
+    [1.026300, 0.757000, 0.001100],
+    [1.062200, 0.631000, 0.000800],
+    [1.002600, 0.503000, 0.000340],
+    [0.854450, 0.381000, 0.000190],
+    [0.642400, 0.265000, 0.000050],
+    [0.447900, 0.175000, 0.000020],
+    [0.283500, 0.107000, 0.000000],
+    [0.164900, 0.061000, 0.000000],
+    [0.087400, 0.032000, 0.000000],
+    [0.046770, 0.017000, 0.000000],
+    [0.022700, 0.008210, 0.000000],
+    [0.011359, 0.004102, 0.000000],
+    [0.005790, 0.002091, 0.000000],
+    [0.002899, 0.001047, 0.000000],
+    [0.001440, 0.000520, 0.000000],
+    [0.000690, 0.000249, 0.000000],
+    [0.000332, 0.000120, 0.000000],
+    [0.000166, 0.000060, 0.000000],
+    [0.000083, 0.000030, 0.000000],
+    [0.000042, 0.000015, 0.000000],
+];
+
+/// Relative spectral power distribution of the CIE standard illuminant D65.
+#[rustfmt::skip]
+const D65: [f32; LEN] = [
+    49.9755, 54.6482, 82.7549, 91.4860, 93.4318,
+    86.6823, 104.8650, 117.0080, 117.8120, 114.8610,
+    115.9230, 108.8110, 109.3540, 107.8020, 104.7900,
+    107.6890, 104.4050, 104.0460, 100.0000, 96.3342,
+    95.7880, 88.6856, 90.0062, 89.5991, 87.6987,
+    83.2886, 83.6992, 80.0268, 80.2146, 82.2778,
+    78.2842, 69.7213, 71.6091, 74.3490, 61.6040,
+    69.8856, 75.0870, 63.5927, 46.4182, 66.8054,
+    63.3828,
+];
+
+/// Relative spectral power distribution of the CIE standard illuminant D50
+/// calculated from the CIE daylight model.
+#[rustfmt::skip]
+const D50: [f32; LEN] = [
+    24.5495, 29.9346, 49.4004, 56.6079, 60.1251,
+    57.8977, 74.9113, 87.3335, 90.6914, 91.4369,
+    95.1701, 92.0119, 95.7636, 96.6462, 97.1522,
+    102.1163, 100.7656, 102.3223, 100.0000, 97.7308,
+    98.9085, 93.4813, 97.6602, 99.2342, 99.0008,
+    95.6773, 98.8019, 95.6093, 98.1228, 102.9264,
+    99.0548, 87.3154, 91.5289, 92.8203, 76.7982,
+    86.4500, 92.5154, 78.1760, 57.6502, 82.8636,
+    78.2192,
+];
+
+/// Relative spectral power distribution of the CIE standard illuminant A
+/// calculated from Planck’s law.
+#[rustfmt::skip]
+const A: [f32; LEN] = [
+    9.7951, 12.0853, 14.7080, 17.6753, 20.9950,
+    24.6709, 28.7027, 33.0859, 37.8121, 42.8693,
+    48.2423, 53.9132, 59.8611, 66.0635, 72.4959,
+    79.1326, 85.9470, 92.9120, 100.0000, 107.1838,
+    114.4363, 121.7312, 129.0427, 136.3463, 143.6182,
+    150.8362, 157.9792, 165.0275, 171.9629, 178.7686,
+    185.4293, 191.9309, 198.2612, 204.4090, 210.3646,
+    216.1196, 221.6668, 227.0003, 232.1152, 237.0078,
+    241.6754,
+];
+
+/// Relative spectral power distribution of the CIE illuminant F2.  The
+/// values are weighted averages (with 1:2:1 weights) of the 5 nm data so
+/// that the narrow emission lines aren’t lost.
+#[rustfmt::skip]
+const F2: [f32; LEN] = [
+    1.2550, 1.8275, 6.1800, 6.7825, 4.1850,
+    12.4300, 16.2175, 6.6150, 7.1775, 7.5250,
+    7.6350, 7.5775, 7.2900, 7.0725, 7.2075,
+    8.1075, 13.4450, 18.1875, 16.1175, 19.0675,
+    21.5850, 18.5850, 16.5050, 13.7925, 10.9775,
+    8.4425, 6.3425, 4.7025, 3.4700, 2.5625,
+    1.9025, 1.4925, 1.1150, 0.8775, 0.6825,
+    0.5675, 0.5075, 0.4600, 0.4550, 0.4000,
+    0.2850,
+];
+
+/// Relative spectral power distribution of the CIE illuminant F11.  The
+/// values are weighted averages (with 1:2:1 weights) of the 5 nm data so
+/// that the narrow emission lines aren’t lost.
+#[rustfmt::skip]
+const F11: [f32; LEN] = [
+    0.8400, 0.4800, 3.9075, 4.4125, 2.5100,
+    11.5625, 16.2875, 7.1125, 6.6725, 5.4600,
+    7.6000, 13.2950, 5.1850, 1.5925, 0.9275,
+    2.0225, 39.2300, 36.3950, 3.7850, 2.4325,
+    10.4375, 12.4900, 8.5300, 40.7100, 20.5250,
+    10.6975, 2.8975, 3.1275, 2.5275, 1.6375,
+    1.5475, 1.7850, 2.0000, 4.4425, 0.9800,
+    0.2350, 0.2325, 0.2200, 0.2850, 0.1750,
+    0.0975,
+];
+
+/// Light source under which a [`Spectrum`] is observed.
+///
+/// Each illuminant is described by its relative spectral power distribution.
+/// Bundled distributions are tabulated at 10 nm intervals from 380 to 780 nm.
+///
+/// The illuminant can also be converted into [`WhitePoint`] whose XYZ
+/// coordinates are those of a perfect reflector under the illuminant.  This
+/// allows [`Xyz`](crate::xyz::Xyz) colours measured under given light source
+/// to be converted into L\*a\*b\* consistently with [`Spectrum`].
+///
+/// ## Example
+///
+/// ```
+/// use empfindung::spectral::Illuminant;
+/// use empfindung::xyz::{WhitePoint, Xyz};
+///
+/// let white = WhitePoint::from(Illuminant::A);
+/// let (x, y, z) = white.xyz();
+/// assert!((x - 1.0985).abs() < 0.005);
+/// assert!((y - 1.0).abs() < 0.001);
+/// assert!((z - 0.3558).abs() < 0.005);
+///
+/// let lab = empfindung::ToLab::to_lab(&Xyz::with_white(x, y, z, white));
+/// assert!((lab.0 - 100.0).abs() < 0.001);
+/// ```
+#[derive(Clone, Copy, PartialEq, Debug, Default)]
+pub enum Illuminant {
+    /// CIE standard illuminant D65 (noon daylight).
+    #[default]
+    D65,
+    /// CIE standard illuminant D50 (horizon light), commonly used in print.
+    D50,
+    /// CIE standard illuminant A (incandescent tungsten light).
+    A,
+    /// CIE illuminant F2 (cool white fluorescent lamp).
+    F2,
+    /// CIE illuminant F11 (narrow band white fluorescent lamp, TL84).
+    F11,
+    /// Custom relative spectral power distribution sampled at 10 nm
+    /// intervals from 380 to 780 nm, i.e. with exactly 41 values.
+    Custom(&'static [f32]),
+}
+
+impl Illuminant {
+    /// Returns relative spectral power distribution of the illuminant
+    /// sampled at 10 nm intervals from 380 to 780 nm.
+    ///
+    /// # Panics
+    ///
+    /// Panics if custom distribution doesn’t have exactly 41 values.
+    pub fn spd(&self) -> &'static [f32; LEN] {
+        match *self {
+            Self::D65 => &D65,
+            Self::D50 => &D50,
+            Self::A => &A,
+            Self::F2 => &F2,
+            Self::F11 => &F11,
+            Self::Custom(spd) => {
+                <&[f32; LEN]>::try_from(spd).unwrap_or_else(|_| {
+                    panic!(
+                        "expected {} spectral power values but got {}",
+                        LEN,
+                        spd.len()
+                    )
+                })
+            }
+        }
+    }
+
+    /// Returns XYZ coordinates of a perfect reflector under the illuminant,
+    /// normalised such that Y equals one.
+    ///
+    /// # Panics
+    ///
+    /// Panics if custom distribution doesn’t have exactly 41 values.
+    pub fn white(&self) -> (f32, f32, f32) { integrate(|_| 1.0, self.spd()) }
+}
+
+impl From<Illuminant> for WhitePoint {
+    /// Returns white point of a perfect reflector under the illuminant.
+    ///
+    /// # Panics
+    ///
+    /// Panics if custom distribution doesn’t have exactly 41 values.
+    fn from(illuminant: Illuminant) -> Self {
+        let (x, y, z) = illuminant.white();
+        Self::Custom { x, y, z }
+    }
+}
+
+/// Surface colour given by its spectral reflectance.
+///
+/// `samples` are reflectance factors (with `1.0` meaning 100% reflectance)
+/// at wavelengths `start_nm`, `start_nm + step_nm`, `start_nm + 2·step_nm`
+/// and so on.  Reflectance between samples is interpolated linearly and
+/// outside of the sampled range is assumed to be equal to the nearest sample.
+/// Spectrum with no samples is black.
+///
+/// When converting to L\*a\*b\* the spectrum is integrated against the CIE
+/// 1931 standard observer under the `illuminant` (D65 by default).  The
+/// reference white is that of a perfect reflector under the same conditions,
+/// i.e. a flat 100% reflectance spectrum maps to L\* = 100.
+///
+/// ## Example
+///
+/// ```
+/// use empfindung::cie00;
+/// use empfindung::spectral::{Illuminant, Spectrum};
+///
+/// // 31 samples from 400 to 700 nm.
+/// let grey = Spectrum::new(vec![0.5; 31], 400.0, 10.0);
+/// let reddish = Spectrum::new(
+///     (0..31).map(|i| 0.3 + i as f32 * 0.01).collect(), 400.0, 10.0);
+/// let delta_e = cie00::diff(&grey, &reddish);
+/// assert!(delta_e > 5.0);
+///
+/// // Colours change under different light source.
+/// let grey = grey.with_illuminant(Illuminant::A);
+/// let reddish = reddish.with_illuminant(Illuminant::A);
+/// assert_ne!(delta_e, cie00::diff(&grey, &reddish));
+/// ```
+#[derive(Clone, PartialEq, Debug)]
+pub struct Spectrum {
+    /// Reflectance factors at consecutive wavelengths.
+    pub samples: Vec<f32>,
+    /// Wavelength of the first sample in nanometres.
+    pub start_nm: f32,
+    /// Interval between samples in nanometres.
+    pub step_nm: f32,
+    /// Light source under which the colour is observed.
+    pub illuminant: Illuminant,
+}
+
+impl Spectrum {
+    /// Constructs a new spectrum from reflectance factors sampled at regular
+    /// intervals observed under D65 illuminant.
+    pub fn new(samples: Vec<f32>, start_nm: f32, step_nm: f32) -> Self {
+        Self {
+            samples,
+            start_nm,
+            step_nm,
+            illuminant: Illuminant::D65,
+        }
+    }
+
+    /// Returns the same spectrum observed under given illuminant.
+    pub fn with_illuminant(self, illuminant: Illuminant) -> Self {
+        Self { illuminant, ..self }
+    }
+
+    /// Returns reflectance at given wavelength.
+    ///
+    /// The value is linearly interpolated between samples and clamped to the
+    /// first or last sample outside of the sampled range.  Returns zero if
+    /// there are no samples.
+    pub fn reflectance(&self, nm: f32) -> f32 {
+        let last = match self.samples.len().checked_sub(1) {
+            Some(last) => last,
+            None => return 0.0,
+        };
+        let pos = ((nm - self.start_nm) / self.step_nm).max(0.0);
+        if pos >= last as f32 {
+            return self.samples[last];
+        }
+        let idx = pos as usize;
+        let frac = pos - idx as f32;
+        let (lo, hi) = (self.samples[idx], self.samples[idx + 1]);
+        lo + (hi - lo) * frac
+    }
+
+    /// Returns XYZ tristimulus values of the colour under its illuminant.
+    ///
+    /// The values are normalised such that Y of a perfect reflector equals
+    /// one.
+    pub fn to_xyz(&self) -> (f32, f32, f32) {
+        integrate(|nm| self.reflectance(nm), self.illuminant.spd())
+    }
+}
+
+impl ToLab for Spectrum {
+    fn to_lab(&self) -> (f32, f32, f32) {
+        crate::xyz::to_lab(self.to_xyz(), self.illuminant.white())
+    }
+}
+
+/// Integrates reflectance spectrum against the standard observer under
+/// illuminant with given spectral power distribution.  Returns XYZ
+/// normalised such that Y of a perfect reflector equals one.
+fn integrate(
+    reflectance: impl Fn(f32) -> f32,
+    illuminant: &[f32; LEN],
+) -> (f32, f32, f32) {
+    let mut xyz = (0.0, 0.0, 0.0);
+    let mut norm = 0.0;
+    for (i, (cmf, &power)) in OBSERVER.iter().zip(illuminant).enumerate() {
+        let nm = START_NM + i as f32 * STEP_NM;
+        let stimulus = reflectance(nm) * power;
+        xyz.0 += stimulus * cmf[0];
+        xyz.1 += stimulus * cmf[1];
+        xyz.2 += stimulus * cmf[2];
+        norm += power * cmf[1];
+    }
+    (xyz.0 / norm, xyz.1 / norm, xyz.2 / norm)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_perfect_reflector() {
+        let white = Spectrum::new(vec![1.0; 31], 400.0, 10.0);
+        let lab = white.to_lab();
+        approx::assert_abs_diff_eq!(100.0, lab.0, epsilon = 0.001);
+        approx::assert_abs_diff_eq!(0.0, lab.1, epsilon = 0.001);
+        approx::assert_abs_diff_eq!(0.0, lab.2, epsilon = 0.001);
+
+        // The white point is close to the one used for sRGB.
+        let xyz = white.to_xyz();
+        let want = crate::xyz::D65;
+        approx::assert_abs_diff_eq!(want.0, xyz.0, epsilon = 0.001);
+        approx::assert_abs_diff_eq!(want.1, xyz.1, epsilon = 0.001);
+        approx::assert_abs_diff_eq!(want.2, xyz.2, epsilon = 0.001);
+    }
+
+    #[test]
+    fn test_flat_grey() {
+        // 50% reflectance is L* ≈ 76 like black and white mixed in equal
+        // proportions.
+        let lab = Spectrum::new(vec![0.5; 41], 380.0, 10.0).to_lab();
+        approx::assert_abs_diff_eq!(76.0693, lab.0, epsilon = 0.001);
+        approx::assert_abs_diff_eq!(0.0, lab.1, epsilon = 0.001);
+        approx::assert_abs_diff_eq!(0.0, lab.2, epsilon = 0.001);
+
+        let black = Spectrum::new(Vec::new(), 0.0, 1.0);
+        assert_eq!((0.0, 0.0, 0.0), black.to_lab());
+    }
+
+    #[test]
+    fn test_illuminants() {
+        // White points of the illuminants as tabulated by CIE.  Differences
+        // come from 10 nm sampling of the distributions.
+        for &(illuminant, want) in &[
+            (Illuminant::D65, (0.9504, 1.0, 1.0888)),
+            (Illuminant::D50, (0.9642, 1.0, 0.8251)),
+            (Illuminant::A, (1.0985, 1.0, 0.3558)),
+            (Illuminant::F2, (0.9919, 1.0, 0.6739)),
+            (Illuminant::F11, (1.0096, 1.0, 0.6435)),
+            (Illuminant::Custom(&D65), (0.9504, 1.0, 1.0888)),
+        ] {
+            let got = illuminant.white();
+            assert!((want.0 - got.0).abs() < 0.006, "{:?} {:?}", want, got);
+            assert!((want.1 - got.1).abs() < 0.001, "{:?} {:?}", want, got);
+            assert!((want.2 - got.2).abs() < 0.006, "{:?} {:?}", want, got);
+            assert_eq!(
+                WhitePoint::Custom {
+                    x: got.0,
+                    y: got.1,
+                    z: got.2
+                },
+                illuminant.into()
+            );
+
+            let white = Spectrum::new(vec![1.0; 2], 380.0, 400.0)
+                .with_illuminant(illuminant)
+                .to_lab();
+            assert!((white.0 - 100.0).abs() < 0.001, "{:?}", white);
+            assert!(white.1.abs() < 0.001, "{:?}", white);
+            assert!(white.2.abs() < 0.001, "{:?}", white);
+        }
+        assert_eq!(Illuminant::D65, Illuminant::default());
+    }
+
+    #[test]
+    fn test_illuminant_changes_colour() {
+        let spectrum = |samples: Vec<f32>| Spectrum::new(samples, 400.0, 10.0);
+        let reddish =
+            spectrum((0..31).map(|i| 0.2 + i as f32 * 0.02).collect());
+        let bluish = spectrum((0..31).map(|i| 0.8 - i as f32 * 0.02).collect());
+
+        let d65 = (reddish.to_lab(), bluish.to_lab());
+        let reddish = reddish.with_illuminant(Illuminant::A);
+        let bluish = bluish.with_illuminant(Illuminant::A);
+        let a = (reddish.to_lab(), bluish.to_lab());
+        assert!(crate::cie76::diff(d65.0, a.0) > 1.0, "{:?} {:?}", d65, a);
+        assert!(crate::cie76::diff(d65.1, a.1) > 1.0, "{:?} {:?}", d65, a);
+
+        let delta_d65 = crate::cie00::diff(d65.0, d65.1);
+        let delta_a = crate::cie00::diff(a.0, a.1);
+        assert!(
+            (delta_d65 - delta_a).abs() > 1.0,
+            "{} {}",
+            delta_d65,
+            delta_a
+        );
+    }
+
+    #[test]
+    fn test_xyz_with_illuminant_white() {
+        let spectrum = Spectrum::new(
+            (0..31).map(|i| 0.2 + i as f32 * 0.02).collect(),
+            400.0,
+            10.0,
+        )
+        .with_illuminant(Illuminant::F11);
+        let (x, y, z) = spectrum.to_xyz();
+        let xyz = crate::xyz::Xyz::with_white(x, y, z, Illuminant::F11.into());
+        let (want, got) = (spectrum.to_lab(), xyz.to_lab());
+        assert!(
+            crate::cie76::diff(want, got) < 0.001,
+            "{:?} {:?}",
+            want,
+            got
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "expected 41 spectral power values but got 3")]
+    fn test_custom_illuminant_length() {
+        Illuminant::Custom(&[1.0, 2.0, 3.0]).white();
+    }
+
+    #[test]
+    fn test_reflectance() {
+        let spectrum = Spectrum::new(vec![0.2, 0.4, 0.8], 400.0, 20.0);
+        assert_eq!(0.2, spectrum.reflectance(380.0));
+        assert_eq!(0.2, spectrum.reflectance(400.0));
+        approx::assert_abs_diff_eq!(0.3, spectrum.reflectance(410.0));
+        assert_eq!(0.4, spectrum.reflectance(420.0));
+        approx::assert_abs_diff_eq!(0.7, spectrum.reflectance(435.0));
+        assert_eq!(0.8, spectrum.reflectance(440.0));
+        assert_eq!(0.8, spectrum.reflectance(700.0));
+
+        // Resampling the spectrum doesn’t change the colour.
+        let coarse = Spectrum::new(vec![0.2, 0.6], 400.0, 300.0);
+        let fine = Spectrum::new(
+            (0..=30).map(|i| 0.2 + i as f32 * 0.4 / 30.0).collect(),
+            400.0,
+            10.0,
+        );
+        let (coarse, fine) = (coarse.to_lab(), fine.to_lab());
+        approx::assert_abs_diff_eq!(coarse.0, fine.0, epsilon = 0.001);
+        approx::assert_abs_diff_eq!(coarse.1, fine.1, epsilon = 0.001);
+        approx::assert_abs_diff_eq!(coarse.2, fine.2, epsilon = 0.001);
+    }
+}
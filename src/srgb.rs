@@ -0,0 +1,819 @@
+// sRGB colour space handling.
+// Copyright (c) 2026 Michał Nazarewicz <mina86@mina86.com>
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in
+// all copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+
+use super::ToLab;
+use crate::float::FloatExt;
+
+/// Linear sRGB to XYZ conversion matrix.
+///
+/// The values are the same as ones used by the `lab` crate so that the
+/// conversions done by this crate agree with it.
+#[rustfmt::skip]
+pub(crate) const TO_XYZ: [[f32; 3]; 3] = [
+    [0.4124108464885388,   0.3575845678529519,  0.18045380393360833],
+    [0.21264934272065283,  0.7151691357059038,  0.07218152157344333],
+    [0.019331758429150258, 0.11919485595098397, 0.9503900340503373],
+];
+
+/// XYZ to linear sRGB conversion matrix; the inverse of [`TO_XYZ`].
+#[cfg(feature = "rgb")]
+#[rustfmt::skip]
+const FROM_XYZ: [[f32; 3]; 3] = [
+    [ 3.240812398895283,    -1.5373084456298138,  -0.4985865229069666],
+    [-0.9692430170086408,    1.8759663029085742,   0.04155503085668564],
+    [ 0.05563839843611281,  -0.20400746093241362,  1.0571295702861432],
+];
+
+/// Decodes gamma-encoded 8-bit sRGB component into linear light in the `0..=1`
+/// range.
+pub(crate) fn decode(c: u8) -> f32 {
+    // S₀ threshold scaled for encoded values in the 0..255 range.
+    const E_0_255: f32 = 3294.6 * 0.003130668442500564;
+    let c = c as f32;
+    if c > E_0_255 {
+        const A: f32 = 0.055 * 255.0;
+        const D: f32 = 1.055 * 255.0;
+        FloatExt::powf((c + A) / D, 2.4)
+    } else {
+        const D: f32 = 12.92 * 255.0;
+        c / D
+    }
+}
+
+/// Decodes gamma-encoded sRGB component normalised to the `0..=1` range into
+/// linear light in the `0..=1` range.
+#[cfg(feature = "rgb")]
+pub(crate) fn decode_normalised(c: f32) -> f32 {
+    const E_0: f32 = 12.92 * 0.003130668442500564;
+    if c > E_0 {
+        FloatExt::powf((c + 0.055) / 1.055, 2.4)
+    } else {
+        c / 12.92
+    }
+}
+
+/// Blends gamma-encoded 8-bit sRGB components in linear light.
+///
+/// Returns linear light value of the `fg` component with `alpha` opacity
+/// composited over the `bg` component.
+pub(crate) fn blend(fg: u8, bg: u8, alpha: u8) -> f32 {
+    let (fg, bg) = (decode(fg), decode(bg));
+    bg + (fg - bg) * (f32::from(alpha) / 255.0)
+}
+
+/// Multiplies 3×3 matrix by a column vector.
+pub(crate) fn mul(
+    matrix: &[[f32; 3]; 3],
+    v: (f32, f32, f32),
+) -> (f32, f32, f32) {
+    let row = |r: &[f32; 3]| r[0] * v.0 + r[1] * v.1 + r[2] * v.2;
+    (row(&matrix[0]), row(&matrix[1]), row(&matrix[2]))
+}
+
+/// An sRGB colour whose primaries are described by a custom matrix.
+///
+/// When converting to L\*a\*b\* the components are first decoded from sRGB
+/// gamma into linear light, then multiplied by the `matrix` and finally the
+/// result is converted from XYZ to L\*a\*b\* using D65 reference white.
+///
+/// The matrix is therefore expected to map linear RGB (each component in the
+/// `0..=1` range) to CIE XYZ with Y normalised so that white has Y equal one.
+/// Rows of the matrix correspond to X, Y and Z coordinates respectively.
+/// This allows comparing colours defined with non-sRGB primaries (e.g. of
+/// a camera or a display) without a full colour-management system.
+///
+/// ## Example
+///
+/// ```
+/// use empfindung::cie00;
+/// use empfindung::Matrixed;
+///
+/// // sRGB matrix gives the same result as plain RGB conversion.
+/// let matrix = Matrixed::SRGB;
+/// let colour_1 = Matrixed { rgb: rgb::RGB::new(234, 76, 76), matrix };
+/// let colour_2 = Matrixed { rgb: rgb::RGB::new(76, 187, 234), matrix };
+/// let delta_e = cie00::diff(colour_1, colour_2);
+/// approx::assert_abs_diff_eq!(58.90164, delta_e, epsilon = 0.001);
+/// ```
+#[cfg(feature = "rgb")]
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub struct Matrixed {
+    /// Gamma-encoded colour.
+    pub rgb: rgb::RGB<u8>,
+    /// Linear RGB to XYZ conversion matrix.
+    pub matrix: [[f32; 3]; 3],
+}
+
+#[cfg(feature = "rgb")]
+impl Matrixed {
+    /// Linear sRGB to XYZ conversion matrix.
+    pub const SRGB: [[f32; 3]; 3] = TO_XYZ;
+}
+
+#[cfg(feature = "rgb")]
+impl ToLab for Matrixed {
+    fn to_lab(&self) -> (f32, f32, f32) {
+        let rgb = (decode(self.rgb.r), decode(self.rgb.g), decode(self.rgb.b));
+        crate::xyz::to_lab(mul(&self.matrix, rgb), crate::xyz::D65)
+    }
+}
+
+/// An sRGB colour with alpha channel composited over an opaque background.
+///
+/// When converting to L\*a\*b\* the colour is first blended with the
+/// `background` and only then converted.  Blending happens in linear light
+/// (i.e. after decoding sRGB gamma) which matches how semi-transparent
+/// surfaces mix physically.  Blending gamma-encoded values instead would make
+/// semi-transparent colours come out too dark.
+///
+/// `rgb::RGBA<u8>` on its own implements [`ToLab`] by compositing the colour
+/// over white background.
+///
+/// ## Example
+///
+/// ```
+/// use empfindung::{cie00, CompositedRgba};
+///
+/// let colour = rgb::RGBA::new(234, 76, 76, 128);
+/// let over_white = CompositedRgba {
+///     colour,
+///     background: rgb::RGB::new(255, 255, 255),
+/// };
+/// let over_black = CompositedRgba {
+///     colour,
+///     background: rgb::RGB::new(0, 0, 0),
+/// };
+/// assert_eq!(0.0, cie00::diff(over_white, colour));
+/// assert!(cie00::diff(over_white, over_black) > 30.0);
+/// ```
+#[cfg(feature = "rgb")]
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub struct CompositedRgba {
+    /// Gamma-encoded colour with straight (not premultiplied) alpha.
+    pub colour: rgb::RGBA<u8>,
+    /// Gamma-encoded opaque background.
+    pub background: rgb::RGB<u8>,
+}
+
+#[cfg(feature = "rgb")]
+impl ToLab for CompositedRgba {
+    fn to_lab(&self) -> (f32, f32, f32) {
+        let (fg, bg) = (self.colour, self.background);
+        let blend = |fg: u8, bg: u8| blend(fg, bg, self.colour.a);
+        let rgb = (blend(fg.r, bg.r), blend(fg.g, bg.g), blend(fg.b, bg.b));
+        crate::xyz::to_lab(mul(&TO_XYZ, rgb), crate::xyz::D65)
+    }
+}
+
+/// An sRGB colour converted into L\*a\*b\* relative to a given reference
+/// white.
+///
+/// Conversion of sRGB colours into L\*a\*b\* normally uses D65 reference
+/// white which is the white point of the sRGB colour space.  Workflows which
+/// use a different reference white (e.g. D50 used in print) need to adapt the
+/// colour to that white before converting it.  This type performs Bradford
+/// chromatic adaptation from D65 to the `white` reference white and then
+/// converts the adapted XYZ coordinates into L\*a\*b\* relative to `white`.
+///
+/// [`ToLab`] is implemented for `RgbWith<rgb::RGB<u8>>` and
+/// `RgbWith<LinearRgb>`.
+///
+/// ## Example
+///
+/// ```
+/// use empfindung::{cie00, RgbWith, ToLab};
+/// use empfindung::xyz::WhitePoint;
+///
+/// let white = RgbWith {
+///     colour: rgb::RGB::new(255, 255, 255),
+///     white: WhitePoint::D50,
+/// };
+/// let (l, a, b) = white.to_lab();
+/// approx::assert_abs_diff_eq!(100.0, l, epsilon = 0.001);
+/// approx::assert_abs_diff_eq!(0.0, a, epsilon = 0.001);
+/// approx::assert_abs_diff_eq!(0.0, b, epsilon = 0.001);
+///
+/// let colour_1 = RgbWith {
+///     colour: rgb::RGB::new(234, 76, 76),
+///     white: WhitePoint::D50,
+/// };
+/// let colour_2 = RgbWith {
+///     colour: rgb::RGB::new(76, 187, 234),
+///     white: WhitePoint::D50,
+/// };
+/// let delta_e = cie00::diff(colour_1, colour_2);
+/// approx::assert_abs_diff_eq!(63.12, delta_e, epsilon = 0.01);
+/// ```
+#[cfg(feature = "rgb")]
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub struct RgbWith<C> {
+    /// The colour.
+    pub colour: C,
+    /// Reference white used when converting the colour into L\*a\*b\*.
+    pub white: crate::xyz::WhitePoint,
+}
+
+#[cfg(feature = "rgb")]
+impl<C> RgbWith<C> {
+    /// Converts linear sRGB colour into L\*a\*b\* relative to `self.white`.
+    fn lab_from_linear(&self, rgb: (f32, f32, f32)) -> (f32, f32, f32) {
+        let white = self.white.xyz();
+        let xyz = crate::xyz::adapt(mul(&TO_XYZ, rgb), crate::xyz::D65, white);
+        crate::xyz::to_lab(xyz, white)
+    }
+}
+
+#[cfg(feature = "rgb")]
+impl ToLab for RgbWith<rgb::RGB<u8>> {
+    fn to_lab(&self) -> (f32, f32, f32) {
+        let rgb = self.colour;
+        self.lab_from_linear((decode(rgb.r), decode(rgb.g), decode(rgb.b)))
+    }
+}
+
+#[cfg(feature = "rgb")]
+impl ToLab for RgbWith<LinearRgb> {
+    fn to_lab(&self) -> (f32, f32, f32) {
+        let LinearRgb(r, g, b) = self.colour;
+        self.lab_from_linear((r.max(0.0), g.max(0.0), b.max(0.0)))
+    }
+}
+
+/// Suggests per-channel gains which correct measured colour of a neutral
+/// patch to match its target.
+///
+/// Returns multipliers for red, green and blue channels which, when applied
+/// to *linear* light values (e.g. raw camera data or [`LinearRgb`]), map the
+/// `measured` colour onto the `target` colour and thus minimise colour
+/// difference between them.  The gains are simple ratios of linearised
+/// target and measured components.  If a measured component is zero, the
+/// corresponding gain is one since no gain can correct it.
+///
+/// This is intended for white-balance and exposure correction based on
+/// a neutral (grey) patch.  It’s not a substitute for a full colour
+/// correction matrix: gains computed from a single patch correct that patch
+/// exactly but other colours only approximately.  Furthermore, gains apply to
+/// linear light so they should not be used to scale gamma-encoded values.
+///
+/// ## Example
+///
+/// ```
+/// let measured = rgb::RGB::new(110, 118, 140);
+/// let target = rgb::RGB::new(118, 118, 118);
+/// let gains = empfindung::suggest_gains(measured, target);
+/// assert_eq!(1.0, gains.1);
+/// assert!(gains.0 > 1.0 && gains.2 < 1.0);
+/// ```
+#[cfg(feature = "rgb")]
+pub fn suggest_gains(
+    measured: rgb::RGB<u8>,
+    target: rgb::RGB<u8>,
+) -> (f32, f32, f32) {
+    let gain = |measured: u8, target: u8| {
+        if measured == 0 {
+            1.0
+        } else {
+            decode(target) / decode(measured)
+        }
+    };
+    (
+        gain(measured.r, target.r),
+        gain(measured.g, target.g),
+        gain(measured.b, target.b),
+    )
+}
+
+/// Converts an array of red, green and blue components into `rgb::RGB8`.
+///
+/// This helps migrating from deprecated `diff_rgb` functions which take
+/// `&[u8; 3]` arguments to `diff` functions which take any type implementing
+/// [`ToLab`].  `rgb::RGB8` also implements `From<[u8; 3]>` so the conversion
+/// can be done with `.into()` as well.  Note that since `[u8; 3]` implements
+/// [`ToLab`] (treating it as an sRGB colour), the array can also be passed to
+/// `diff` functions directly.
+///
+/// ## Example
+///
+/// ```
+/// use empfindung::{cie00, rgb8};
+///
+/// let colour_1 = [234, 76, 76];
+/// let colour_2 = [76, 187, 234];
+/// # #[cfg(feature = "lab")] {
+/// let delta_e = cie00::diff(rgb8(colour_1), rgb8(colour_2));
+/// approx::assert_abs_diff_eq!(58.90164, delta_e, epsilon = 0.001);
+/// # }
+/// assert_eq!(rgb8(colour_1), colour_1.into());
+/// ```
+#[cfg(feature = "rgb")]
+pub const fn rgb8([r, g, b]: [u8; 3]) -> rgb::RGB8 { rgb::RGB8 { r, g, b } }
+
+/// Converts L\*a\*b\* colour into sRGB.
+///
+/// This is an inverse of converting an `rgb::RGB<u8>` colour into L\*a\*b\*.
+/// The colour is converted to XYZ using D65 reference white, then into
+/// linear sRGB, gamma-encoded and finally rounded to the nearest 8-bit value.
+/// Colours outside of the sRGB gamut are clamped per-channel; to find out
+/// whether that happened use [`lab_to_srgb_checked`].
+///
+/// ## Example
+///
+/// ```
+/// use empfindung::lab_to_srgb;
+///
+/// let colour = lab_to_srgb(53.2408, 80.0925, 67.2032);
+/// assert_eq!(rgb::RGB::new(255, 0, 0), colour);
+/// ```
+#[cfg(feature = "rgb")]
+pub fn lab_to_srgb(l: f32, a: f32, b: f32) -> rgb::RGB<u8> {
+    lab_to_srgb_checked(l, a, b).0
+}
+
+/// Converts L\*a\*b\* colour into sRGB and reports whether the colour had
+/// to be clamped.
+///
+/// This works like [`lab_to_srgb`] but in addition returns a flag which is
+/// `true` if the colour is outside of the sRGB gamut and any of its channels
+/// had to be clamped to the `0..=255` range.
+///
+/// ## Example
+///
+/// ```
+/// use empfindung::lab_to_srgb_checked;
+///
+/// let (colour, clamped) = lab_to_srgb_checked(50.0, 0.0, 0.0);
+/// assert_eq!(rgb::RGB::new(119, 119, 119), colour);
+/// assert!(!clamped);
+///
+/// let (colour, clamped) = lab_to_srgb_checked(50.0, -100.0, 100.0);
+/// assert_eq!(0, colour.r);
+/// assert!(clamped);
+/// ```
+#[cfg(feature = "rgb")]
+pub fn lab_to_srgb_checked(l: f32, a: f32, b: f32) -> (rgb::RGB<u8>, bool) {
+    fn encode(c: f32) -> (u8, bool) {
+        let c = if c > 0.003130668442500564 {
+            1.055 * FloatExt::powf(c, 1.0 / 2.4) - 0.055
+        } else {
+            12.92 * c
+        };
+        let c = FloatExt::round(c * 255.0);
+        (c.clamp(0.0, 255.0) as u8, !(0.0..=255.0).contains(&c))
+    }
+
+    let xyz = crate::xyz::from_lab((l, a, b), crate::xyz::D65);
+    let (r, g, b) = mul(&FROM_XYZ, xyz);
+    let ((r, r_clamped), (g, g_clamped), (b, b_clamped)) =
+        (encode(r), encode(g), encode(b));
+    (rgb::RGB::new(r, g, b), r_clamped || g_clamped || b_clamped)
+}
+
+/// A colour in linear sRGB colour space.
+///
+/// The components are red, green and blue linear light intensities with the
+/// nominal range of `0..=1`.  Unlike `rgb::RGB<u8>`, the values are not
+/// gamma-encoded so when converting to L\*a\*b\* the sRGB transfer function
+/// is skipped and the colour is converted directly into XYZ and then into
+/// L\*a\*b\* using D65 reference white.  This is useful for renderers which
+/// work in linear light throughout and would otherwise have to encode colours
+/// only for them to be decoded again.
+///
+/// Linear colours held in `rgb::RGB<f32>` can be converted into this type
+/// using the `From` trait.  Note that `rgb::RGB<f32>` on its own is assumed
+/// to be gamma-encoded when converting it into L\*a\*b\*.
+///
+/// Negative components are clamped to zero.  Components greater than one
+/// (e.g. in HDR content) are accepted and the conversion is extrapolated
+/// which results in lightness L\* above 100.  Note that the L\*a\*b\* colour
+/// space and the colour difference formulæ were not designed for such values
+/// so results for them should be treated with caution.
+///
+/// ## Example
+///
+/// ```
+/// use empfindung::{cie00, LinearRgb};
+///
+/// let white = LinearRgb(1.0, 1.0, 1.0);
+/// let grey = LinearRgb(0.18, 0.18, 0.18);
+/// let delta_e = cie00::diff(white, grey);
+/// approx::assert_abs_diff_eq!(36.99, delta_e, epsilon = 0.01);
+/// ```
+#[derive(Clone, Copy, PartialEq, Debug, Default)]
+pub struct LinearRgb(pub f32, pub f32, pub f32);
+
+impl ToLab for LinearRgb {
+    fn to_lab(&self) -> (f32, f32, f32) {
+        let rgb = (self.0.max(0.0), self.1.max(0.0), self.2.max(0.0));
+        crate::xyz::to_lab(mul(&TO_XYZ, rgb), crate::xyz::D65)
+    }
+}
+
+#[cfg(feature = "rgb")]
+impl From<rgb::RGB<f32>> for LinearRgb {
+    /// Interprets components of the colour as linear light intensities.
+    fn from(rgb: rgb::RGB<f32>) -> Self { Self(rgb.r, rgb.g, rgb.b) }
+}
+
+/// Lookup table for converting 8-bit sRGB colours into L\*a\*b\*.
+///
+/// Decoding sRGB gamma requires raising each component to a fractional power
+/// which is a significant cost when converting whole images.  This type holds
+/// precomputed linear light values for all 256 possible component values so
+/// that conversion is just three table lookups followed by a matrix
+/// multiplication and the XYZ to L\*a\*b\* conversion.  The table takes 1 KiB
+/// so it’s cheap to build but should be reused for all pixels.
+///
+/// Results are within 0.001 ΔE of converting `rgb::RGB8` colours.
+///
+/// ## Example
+///
+/// ```
+/// use empfindung::{cie00, Srgb8Lut};
+///
+/// let lut = Srgb8Lut::new();
+/// let pixels = [[234, 76, 76], [76, 187, 234]];
+/// let labs = pixels.iter().map(|&rgb| lut.to_lab(rgb)).collect::<Vec<_>>();
+/// let delta_e = cie00::diff(labs[0], labs[1]);
+/// approx::assert_abs_diff_eq!(58.90164, delta_e, epsilon = 0.001);
+/// ```
+#[derive(Clone, Debug)]
+pub struct Srgb8Lut {
+    linear: [f32; 256],
+}
+
+impl Srgb8Lut {
+    /// Builds the lookup table.
+    pub fn new() -> Self {
+        let mut linear = [0.0; 256];
+        for (c, value) in linear.iter_mut().enumerate() {
+            *value = decode(c as u8);
+        }
+        Self { linear }
+    }
+
+    /// Converts gamma-encoded 8-bit sRGB colour into L\*a\*b\* using D65
+    /// reference white.
+    pub fn to_lab(&self, [r, g, b]: [u8; 3]) -> (f32, f32, f32) {
+        let linear = |c: u8| self.linear[usize::from(c)];
+        let xyz = mul(&TO_XYZ, (linear(r), linear(g), linear(b)));
+        crate::xyz::to_lab(xyz, crate::xyz::D65)
+    }
+}
+
+impl Default for Srgb8Lut {
+    fn default() -> Self { Self::new() }
+}
+
+/// Strategy for handling out-of-gamut colours; see [`GamutMapped`].
+#[cfg(feature = "rgb")]
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum GamutMapping {
+    /// Clamps each component to the `0..=1` range independently.  This is
+    /// what converting `rgb::RGB<f32>` directly does.  It is cheap but may
+    /// noticeably shift hue of saturated colours.
+    Clamp,
+    /// Reduces chroma of the colour, keeping its lightness and hue, until it
+    /// fits in the sRGB gamut.  Lightness is clamped to the `0..=100` range.
+    Compress,
+}
+
+/// An sRGB colour whose out-of-range components are mapped into the sRGB
+/// gamut according to given strategy before conversion to L\*a\*b\*.
+///
+/// Colours produced by image processing pipelines (e.g. after sharpening or
+/// colour grading) may have components outside of the `0..=1` range.  When
+/// converting `rgb::RGB<f32>` into L\*a\*b\* such components are clamped
+/// which may change hue of the colour.  Wrapping the colour in this type with
+/// [`GamutMapping::Compress`] strategy instead desaturates the colour towards
+/// the achromatic axis preserving its hue.
+///
+/// ## Example
+///
+/// ```
+/// use empfindung::{GamutMapped, GamutMapping, ToLab};
+///
+/// let colour = rgb::RGB::new(1.2, -0.1, -0.1);
+/// let clamped = GamutMapped { colour, mapping: GamutMapping::Clamp };
+/// let compressed = GamutMapped { colour, mapping: GamutMapping::Compress };
+/// assert_eq!(colour.to_lab(), clamped.to_lab());
+/// assert_ne!(clamped.to_lab(), compressed.to_lab());
+/// ```
+#[cfg(feature = "rgb")]
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub struct GamutMapped<C> {
+    /// The colour.
+    pub colour: C,
+    /// Strategy used to map the colour into the sRGB gamut.
+    pub mapping: GamutMapping,
+}
+
+#[cfg(feature = "rgb")]
+impl ToLab for GamutMapped<rgb::RGB<f32>> {
+    fn to_lab(&self) -> (f32, f32, f32) {
+        if self.mapping == GamutMapping::Clamp {
+            return self.colour.to_lab();
+        }
+
+        // Extend the transfer function symmetrically for negative values so
+        // that hue of the colour is retained.
+        let decode = |c: f32| {
+            let d = decode_normalised(c.abs());
+            if c < 0.0 {
+                -d
+            } else {
+                d
+            }
+        };
+        let rgb = self.colour;
+        let rgb = (decode(rgb.r), decode(rgb.g), decode(rgb.b));
+        let (l, a, b) = crate::xyz::to_lab(mul(&TO_XYZ, rgb), crate::xyz::D65);
+        let in_range = |lo: f32, hi: f32, (r, g, b): (f32, f32, f32)| {
+            lo <= r && r <= hi && lo <= g && g <= hi && lo <= b && b <= hi
+        };
+        if in_range(0.0, 1.0, rgb) {
+            return (l, a, b);
+        }
+
+        let l = l.clamp(0.0, 100.0);
+        let in_gamut = |scale: f32| {
+            let lab = (l, a * scale, b * scale);
+            let xyz = crate::xyz::from_lab(lab, crate::xyz::D65);
+            in_range(-1e-5, 1.0 + 1e-5, mul(&FROM_XYZ, xyz))
+        };
+        let (mut lo, mut hi) = (0.0, 1.0);
+        if in_gamut(hi) {
+            lo = hi;
+        }
+        while hi - lo > 1e-5 {
+            let mid = (lo + hi) / 2.0;
+            if in_gamut(mid) {
+                lo = mid;
+            } else {
+                hi = mid;
+            }
+        }
+        (l, a * lo, b * lo)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testutil::EPSILON;
+
+    #[test]
+    #[cfg(all(feature = "lab", feature = "rgb"))]
+    fn test_matrixed_srgb() {
+        let mut errors = Vec::new();
+        for c in (0..=255).step_by(15) {
+            let colours = [(c, 0, 0), (0, c, 0), (0, 0, c), (c, c, 255 - c)];
+            for (r, g, b) in colours {
+                let rgb = rgb::RGB::new(r, g, b);
+                let want = lab::Lab::from_rgb(&[r, g, b]);
+                let want = (want.l, want.a, want.b);
+                let got = Matrixed { rgb, matrix: Matrixed::SRGB }.to_lab();
+                let diff = crate::cie76::diff(want, got);
+                if diff > EPSILON {
+                    errors.push((rgb, want, got));
+                }
+            }
+        }
+        assert!(errors.is_empty(), "{:?}", errors);
+    }
+
+    #[test]
+    #[cfg(feature = "rgb")]
+    fn test_composited_rgba() {
+        let white = rgb::RGB::new(255, 255, 255);
+        let composite = |colour, background| {
+            CompositedRgba { colour, background }.to_lab()
+        };
+
+        // Half-transparent black over white is blended in linear light.
+        let got = composite(rgb::RGBA::new(0, 0, 0, 128), white);
+        let want = LinearRgb(127.0 / 255.0, 127.0 / 255.0, 127.0 / 255.0);
+        let want = want.to_lab();
+        approx::assert_abs_diff_eq!(want.0, got.0, epsilon = 0.001);
+        approx::assert_abs_diff_eq!(0.0, got.1, epsilon = 0.001);
+        approx::assert_abs_diff_eq!(0.0, got.2, epsilon = 0.001);
+        assert_eq!(got, rgb::RGBA::<u8>::new(0, 0, 0, 128).to_lab());
+
+        // Opaque and fully transparent colours.
+        let (r, g, b) = (234, 76, 76);
+        let want = LinearRgb(decode(r), decode(g), decode(b)).to_lab();
+        let got = composite(rgb::RGBA::new(r, g, b, 255), white);
+        approx::assert_abs_diff_eq!(want.0, got.0, epsilon = 0.001);
+        approx::assert_abs_diff_eq!(want.1, got.1, epsilon = 0.001);
+        approx::assert_abs_diff_eq!(want.2, got.2, epsilon = 0.001);
+        let got = composite(rgb::RGBA::new(r, g, b, 0), white);
+        approx::assert_abs_diff_eq!(100.0, got.0, epsilon = 0.001);
+    }
+
+    #[test]
+    #[cfg(feature = "rgb")]
+    fn test_lab_to_srgb() {
+        let mut errors = Vec::new();
+        let values = [0u8, 1, 10, 11, 23, 24, 76, 128, 187, 234, 254, 255];
+        for r in values {
+            for g in values {
+                for b in values {
+                    let want = rgb::RGB::new(r, g, b);
+                    let lab = LinearRgb(decode(r), decode(g), decode(b));
+                    let lab = lab.to_lab();
+                    let (got, clamped) =
+                        lab_to_srgb_checked(lab.0, lab.1, lab.2);
+                    let close = |want: u8, got: u8| want.abs_diff(got) <= 1;
+                    if clamped ||
+                        !close(want.r, got.r) ||
+                        !close(want.g, got.g) ||
+                        !close(want.b, got.b)
+                    {
+                        errors.push((want, lab, got, clamped));
+                    }
+                }
+            }
+        }
+        assert!(errors.is_empty(), "{:?}", errors);
+
+        let (got, clamped) = lab_to_srgb_checked(100.0, 0.0, 0.0);
+        assert_eq!((rgb::RGB::new(255, 255, 255), false), (got, clamped));
+        let (got, clamped) = lab_to_srgb_checked(0.0, 0.0, 0.0);
+        assert_eq!((rgb::RGB::new(0, 0, 0), false), (got, clamped));
+        let (got, clamped) = lab_to_srgb_checked(110.0, 0.0, 0.0);
+        assert_eq!((rgb::RGB::new(255, 255, 255), true), (got, clamped));
+        let (got, clamped) = lab_to_srgb_checked(50.0, 0.0, -120.0);
+        assert!(clamped);
+        assert_eq!(got, lab_to_srgb(50.0, 0.0, -120.0));
+    }
+
+    #[test]
+    #[cfg(feature = "rgb")]
+    fn test_rgb_with() {
+        use crate::xyz::WhitePoint;
+
+        for white in [
+            WhitePoint::D65,
+            WhitePoint::D50,
+            WhitePoint::Custom { x: 1.0, y: 1.0, z: 1.0 },
+        ] {
+            let colour = rgb::RGB::new(255, 255, 255);
+            let got = RgbWith { colour, white }.to_lab();
+            approx::assert_abs_diff_eq!(100.0, got.0, epsilon = 0.001);
+            approx::assert_abs_diff_eq!(0.0, got.1, epsilon = 0.001);
+            approx::assert_abs_diff_eq!(0.0, got.2, epsilon = 0.001);
+        }
+
+        // D65 gives the same result as regular conversion.
+        for c in (0..=255).step_by(15) {
+            let (r, g, b) = (c, 255 - c, 128);
+            let want = LinearRgb(decode(r), decode(g), decode(b));
+            let white = WhitePoint::D65;
+            let got = RgbWith { colour: rgb::RGB::new(r, g, b), white };
+            let diff = crate::cie76::diff(want.to_lab(), got);
+            approx::assert_abs_diff_eq!(0.0, diff, epsilon = EPSILON);
+            let got = RgbWith { colour: want, white };
+            let diff = crate::cie76::diff(want.to_lab(), got);
+            approx::assert_abs_diff_eq!(0.0, diff, epsilon = EPSILON);
+        }
+    }
+
+    #[test]
+    #[cfg(feature = "rgb")]
+    fn test_matrixed_swapped_primaries() {
+        // Matrix with red and blue columns swapped is the same as swapping
+        // the channels of the colour.
+        let matrix = [
+            [TO_XYZ[0][2], TO_XYZ[0][1], TO_XYZ[0][0]],
+            [TO_XYZ[1][2], TO_XYZ[1][1], TO_XYZ[1][0]],
+            [TO_XYZ[2][2], TO_XYZ[2][1], TO_XYZ[2][0]],
+        ];
+        let got = Matrixed { rgb: rgb::RGB::new(234, 76, 10), matrix }.to_lab();
+        let want = rgb::RGB::<u8>::new(10, 76, 234);
+        let want = Matrixed { rgb: want, matrix: Matrixed::SRGB }.to_lab();
+        let diff = crate::cie76::diff(want, got);
+        approx::assert_abs_diff_eq!(0.0, diff, epsilon = EPSILON);
+    }
+
+    #[test]
+    #[cfg(all(feature = "lab", feature = "rgb"))]
+    fn test_srgb8_lut() {
+        let lut = Srgb8Lut::new();
+        for (c, &value) in lut.linear.iter().enumerate() {
+            assert_eq!(decode(c as u8), value);
+        }
+        let mut errors = Vec::new();
+        for r in (0..=255).step_by(15) {
+            for g in (0..=255).step_by(15) {
+                for b in (0..=255).step_by(15) {
+                    let want = rgb::RGB8::new(r, g, b).to_lab();
+                    let got = lut.to_lab([r, g, b]);
+                    if crate::cie76::diff(want, got) > EPSILON {
+                        errors.push(((r, g, b), want, got));
+                    }
+                }
+            }
+        }
+        assert!(errors.is_empty(), "{:?}", errors);
+    }
+
+    #[test]
+    #[cfg(feature = "rgb")]
+    fn test_linear_rgb() {
+        for c in (0..=255).step_by(15) {
+            let linear = LinearRgb(decode(c), decode(0), decode(255 - c));
+            let rgb = rgb::RGB::new(c, 0, 255 - c);
+            let want = Matrixed { rgb, matrix: Matrixed::SRGB }.to_lab();
+            assert_eq!(want, linear.to_lab());
+        }
+    }
+
+    #[test]
+    fn test_linear_rgb_range() {
+        let black = LinearRgb(0.0, 0.0, 0.0).to_lab();
+        assert_eq!(black, LinearRgb(-0.5, -1.0, -0.1).to_lab());
+        let white = LinearRgb(1.0, 1.0, 1.0).to_lab();
+        approx::assert_abs_diff_eq!(100.0, white.0, epsilon = EPSILON);
+        let hdr = LinearRgb(2.0, 2.0, 2.0).to_lab();
+        assert!(hdr.0 > 100.0, "{:?}", hdr);
+    }
+
+    #[test]
+    #[cfg(feature = "rgb")]
+    fn test_gamut_mapped() {
+        let map = |colour, mapping| GamutMapped { colour, mapping }.to_lab();
+
+        // In-gamut colours are unaffected by either strategy.
+        let colour = rgb::RGB::new(0.8, 0.3, 0.1);
+        let want = colour.to_lab();
+        assert_eq!(want, map(colour, GamutMapping::Clamp));
+        let got = map(colour, GamutMapping::Compress);
+        approx::assert_abs_diff_eq!(want.0, got.0, epsilon = EPSILON);
+        approx::assert_abs_diff_eq!(want.1, got.1, epsilon = EPSILON);
+        approx::assert_abs_diff_eq!(want.2, got.2, epsilon = EPSILON);
+
+        // Over-saturated red.  Clamping shifts its hue while compressing
+        // retains it.
+        let colour = rgb::RGB::new(1.2, -0.3, -0.2);
+        let linear = (
+            decode_normalised(1.2),
+            -decode_normalised(0.3),
+            -decode_normalised(0.2),
+        );
+        let unmapped =
+            crate::xyz::to_lab(mul(&TO_XYZ, linear), crate::xyz::D65);
+        let hue = |lab: (f32, f32, f32)| lab.2.atan2(lab.1);
+
+        let got = map(colour, GamutMapping::Clamp);
+        assert_eq!(colour.to_lab(), got);
+        assert!((hue(unmapped) - hue(got)).abs() > 0.01);
+
+        let got = map(colour, GamutMapping::Compress);
+        approx::assert_abs_diff_eq!(hue(unmapped), hue(got), epsilon = EPSILON);
+        let rgb = mul(&FROM_XYZ, crate::xyz::from_lab(got, crate::xyz::D65));
+        for c in [rgb.0, rgb.1, rgb.2] {
+            assert!((-0.001..=1.001).contains(&c), "{:?}", rgb);
+        }
+        let chroma = got.1.hypot(got.2);
+        assert!(chroma < unmapped.1.hypot(unmapped.2));
+        assert!(chroma > 50.0, "{:?}", got);
+    }
+
+    #[test]
+    #[cfg(feature = "rgb")]
+    fn test_suggest_gains() {
+        let target = rgb::RGB::new(118, 118, 118);
+        let want = Matrixed { rgb: target, matrix: Matrixed::SRGB }.to_lab();
+        for measured in [(110, 118, 140), (90, 100, 95), (200, 180, 190)] {
+            let measured = rgb::RGB::new(measured.0, measured.1, measured.2);
+            let gains = suggest_gains(measured, target);
+            let corrected = LinearRgb(
+                decode(measured.r) * gains.0,
+                decode(measured.g) * gains.1,
+                decode(measured.b) * gains.2,
+            );
+            let delta_e = crate::cie00::diff(want, corrected);
+            approx::assert_abs_diff_eq!(0.0, delta_e, epsilon = EPSILON);
+        }
+
+        let gains = suggest_gains(rgb::RGB::new(0, 10, 10), target);
+        assert_eq!(1.0, gains.0);
+    }
+}
@@ -1,5 +1,8 @@
 type Tripple = (f32, f32, f32);
 
+/// Test vector: expected difference and the two colours.
+pub type TestCase = (f32, Tripple, Tripple);
+
 // Floating point calculations in Miri seem broken.  For example, for normal
 // floating point numbers x and y, x+y should equal y+x, but that does not
 // always happen in Miri.
@@ -12,7 +15,7 @@ type Tripple = (f32, f32, f32);
 // test functions.
 pub const EPSILON: f32 = if cfg!(miri) { 0.01 } else { 0.001 };
 
-fn generate_colours(count: usize) -> Vec<Tripple> {
+pub fn generate_colours(count: usize) -> Vec<Tripple> {
     use rand::Rng;
     use rand::SeedableRng;
 
@@ -54,7 +57,7 @@ pub fn do_test_symmetric(diff: impl Fn(Tripple, Tripple) -> f32) {
 }
 
 pub fn do_test_difference(
-    tests: &[(f32, Tripple, Tripple)],
+    tests: &[TestCase],
     diff: impl Fn(Tripple, Tripple) -> f32,
 ) {
     for (want, colour_1, colour_2) in tests {
@@ -69,3 +72,107 @@ pub fn do_test_difference(
         }
     }
 }
+
+/// Tests that `within` agrees with comparing result of `diff` against
+/// a threshold.  Thresholds are chosen around the expected differences but
+/// not within rounding error of them.
+pub fn do_test_within(
+    tests: &[TestCase],
+    diff: impl Fn(Tripple, Tripple) -> f32,
+    within: impl Fn(Tripple, Tripple, f32) -> bool,
+) {
+    for (want, colour_1, colour_2) in tests {
+        for threshold in [-1.0, 0.0, want - 0.01, want + 0.01, want * 2.0] {
+            let got = within(*colour_1, *colour_2, threshold);
+            assert_eq!(
+                diff(*colour_1, *colour_2) <= threshold,
+                got,
+                "threshold: {}; colours: {:?}, {:?}",
+                threshold,
+                colour_1,
+                colour_2
+            );
+        }
+    }
+}
+
+pub fn do_test_squared(
+    tests: &[TestCase],
+    diff: impl Fn(Tripple, Tripple) -> f32,
+    diff_squared: impl Fn(Tripple, Tripple) -> f32,
+) {
+    let colours = generate_colours(if cfg!(miri) { 10 } else { 1000 });
+    let pairs = colours.windows(2).map(|pair| (pair[0], pair[1]));
+    let pairs = tests.iter().map(|(_, a, b)| (*a, *b)).chain(pairs);
+    for (colour_1, colour_2) in pairs {
+        let got = diff_squared(colour_1, colour_2);
+        assert!(
+            got >= 0.0,
+            "{}; colours: {:?}, {:?}",
+            got,
+            colour_1,
+            colour_2
+        );
+        approx::assert_abs_diff_eq!(
+            diff(colour_1, colour_2),
+            got.sqrt(),
+            epsilon = EPSILON
+        );
+    }
+}
+
+/// Like [`do_test_difference`] but for functions operating on `f64` numbers.
+///
+/// Since the expected values are given to four decimal places, the results
+/// are checked to within half a unit in the last place.
+pub fn do_test_difference_f64(
+    tests: &[TestCase],
+    diff: impl Fn((f64, f64, f64), (f64, f64, f64)) -> f64,
+) {
+    // Convert through decimal representation so that the f64 colours are
+    // exactly the published values rather than their f32 approximations.
+    fn to_f64(value: f32) -> f64 { value.to_string().parse().unwrap() }
+    let lab = |c: Tripple| (to_f64(c.0), to_f64(c.1), to_f64(c.2));
+    for (want, colour_1, colour_2) in tests {
+        let got = diff(lab(*colour_1), lab(*colour_2));
+        let want = to_f64(*want);
+        if !approx::abs_diff_eq!(want, got, epsilon = 0.00005) {
+            panic!(
+                "{} ≠ {}; colours: {:?}, {:?}",
+                want, got, colour_1, colour_2
+            );
+        }
+    }
+}
+
+pub fn do_test_triangle(diff: impl Fn(Tripple, Tripple) -> f32) {
+    const COUNT: usize = if cfg!(miri) { 10 } else { 1000 };
+    for triple in generate_colours(COUNT).windows(3) {
+        let (a, b, c) = (triple[0], triple[1], triple[2]);
+        let (ab, bc, ac) = (diff(a, b), diff(b, c), diff(a, c));
+        if ac > ab + bc + EPSILON {
+            panic!(
+                "{} > {} + {}; colours: {:?}, {:?}, {:?}",
+                ac, ab, bc, a, b, c
+            );
+        }
+        // Also check triples of nearby colours by using midpoint of a and
+        // c nudged slightly towards b.
+        let nudge = |a: f32, b: f32, c: f32| {
+            let mid = (a + c) * 0.5;
+            mid + (b - mid) * 0.05
+        };
+        let m = (
+            nudge(a.0, b.0, c.0),
+            nudge(a.1, b.1, c.1),
+            nudge(a.2, b.2, c.2),
+        );
+        let (am, mc) = (diff(a, m), diff(m, c));
+        if ac > am + mc + EPSILON {
+            panic!(
+                "{} > {} + {}; colours: {:?}, {:?}, {:?}",
+                ac, am, mc, a, m, c
+            );
+        }
+    }
+}
@@ -1,4 +1,6 @@
 use super::ToLab;
+#[cfg(feature = "rgb")]
+use crate::float::FloatExt;
 
 impl ToLab for (f32, f32, f32) {
     #[inline]
@@ -10,6 +12,25 @@ impl ToLab for [f32; 3] {
     fn to_lab(&self) -> (f32, f32, f32) { (self[0], self[1], self[2]) }
 }
 
+/// Reads L\*, a\* and b\* coordinates from the first three elements of the
+/// slice.  Any further elements are ignored.
+///
+/// # Panics
+///
+/// Panics if the slice has fewer than three elements.
+impl ToLab for [f32] {
+    #[inline]
+    fn to_lab(&self) -> (f32, f32, f32) {
+        match *self {
+            [l, a, b, ..] => (l, a, b),
+            _ => panic!(
+                "expected at least three L*a*b* coordinates but got {}",
+                self.len()
+            ),
+        }
+    }
+}
+
 #[cfg(feature = "lab")]
 impl ToLab for lab::Lab {
     #[inline]
@@ -25,6 +46,13 @@ impl ToLab for rgb::RGB<u8> {
     }
 }
 
+#[cfg(all(feature = "lab", feature = "rgb"))]
+impl ToLab for [u8; 3] {
+    /// Assumes an sRGB colour and converts it into L\*a\*\b\*.
+    #[inline]
+    fn to_lab(&self) -> (f32, f32, f32) { lab::Lab::from_rgb(self).to_lab() }
+}
+
 #[cfg(all(feature = "lab", feature = "rgb"))]
 impl ToLab for rgb::alt::BGR<u8> {
     /// Assumes an sRGB colour and converts it into L\*a\*\b\*.
@@ -34,6 +62,74 @@ impl ToLab for rgb::alt::BGR<u8> {
     }
 }
 
+#[cfg(feature = "rgb")]
+impl ToLab for rgb::RGB<u16> {
+    /// Assumes a 16-bit sRGB colour and converts it into L\*a\*\b\*.
+    ///
+    /// The components are decoded with full precision rather than being
+    /// truncated to eight bits first.
+    fn to_lab(&self) -> (f32, f32, f32) {
+        use crate::srgb::decode_normalised;
+        let decode = |c: u16| decode_normalised(f32::from(c) / 65535.0);
+        let rgb = (decode(self.r), decode(self.g), decode(self.b));
+        let xyz = crate::srgb::mul(&crate::srgb::TO_XYZ, rgb);
+        crate::xyz::to_lab(xyz, crate::xyz::D65)
+    }
+}
+
+#[cfg(feature = "rgb")]
+impl ToLab for rgb::RGB<f32> {
+    /// Assumes an sRGB colour with components in the `0..=1` range and
+    /// converts it into L\*a\*\b\*.
+    ///
+    /// The components are gamma-encoded; for linear colours use
+    /// [`LinearRgb`](crate::LinearRgb) instead.  Values outside of the
+    /// `0..=1` range are clamped.
+    fn to_lab(&self) -> (f32, f32, f32) {
+        use crate::srgb::decode_normalised;
+        let decode = |c: f32| decode_normalised(c.clamp(0.0, 1.0));
+        let rgb = (decode(self.r), decode(self.g), decode(self.b));
+        let xyz = crate::srgb::mul(&crate::srgb::TO_XYZ, rgb);
+        crate::xyz::to_lab(xyz, crate::xyz::D65)
+    }
+}
+
+#[cfg(feature = "rgb")]
+impl ToLab for rgb::RGBA<u8> {
+    /// Assumes an sRGB colour, composites it over white background and
+    /// converts the result into L\*a\*\b\*.
+    ///
+    /// To use a different background, see
+    /// [`CompositedRgba`](crate::CompositedRgba).
+    fn to_lab(&self) -> (f32, f32, f32) {
+        crate::CompositedRgba {
+            colour: *self,
+            background: rgb::RGB::new(255, 255, 255),
+        }
+        .to_lab()
+    }
+}
+
+/// Implements `ToLab` for colours with alpha channel by converting them to
+/// `rgb::RGBA<u8>` which composites them over white background.
+macro_rules! impl_alpha_layout {
+    ($($t:ident),*) => {$(
+        #[cfg(feature = "rgb")]
+        impl ToLab for rgb::alt::$t<u8> {
+            /// Assumes an sRGB colour, composites it over white background
+            /// and converts the result into L\*a\*\b\*.
+            ///
+            /// This is the same as converting `rgb::RGBA<u8>` colour with
+            /// the same components.
+            fn to_lab(&self) -> (f32, f32, f32) {
+                rgb::RGBA::new(self.r, self.g, self.b, self.a).to_lab()
+            }
+        }
+    )*};
+}
+
+impl_alpha_layout!(BGRA, ARGB, ABGR);
+
 #[cfg(feature = "rgb")]
 impl ToLab for rgb::alt::Gray<u8> {
     /// Assumes a grey colour in sRGB colour and converts it into L\*a\*\b\*.
@@ -45,6 +141,57 @@ impl ToLab for rgb::alt::Gray<u8> {
     fn to_lab(&self) -> (f32, f32, f32) { lab_from_grey(**self) }
 }
 
+#[cfg(feature = "rgb")]
+impl ToLab for rgb::alt::Gray<u16> {
+    /// Assumes a 16-bit grey colour in sRGB colour and converts it into
+    /// L\*a\*\b\*.
+    ///
+    /// Like with `Gray<u8>`, this is faster than converting from a `(r, g, b)`
+    /// colour.
+    #[inline]
+    fn to_lab(&self) -> (f32, f32, f32) { lab_from_grey_u16(**self) }
+}
+
+#[cfg(feature = "image")]
+impl ToLab for image::Rgb<u8> {
+    /// Assumes an sRGB colour and converts it into L\*a\*\b\*.
+    fn to_lab(&self) -> (f32, f32, f32) {
+        use crate::srgb::decode;
+        let [r, g, b] = self.0;
+        let rgb = (decode(r), decode(g), decode(b));
+        let xyz = crate::srgb::mul(&crate::srgb::TO_XYZ, rgb);
+        crate::xyz::to_lab(xyz, crate::xyz::D65)
+    }
+}
+
+#[cfg(feature = "palette")]
+impl ToLab for palette::Lab<palette::white_point::D65, f32> {
+    /// Returns the coordinates as is.  Note that colours using different
+    /// reference white need to be adapted to D65 first, e.g. with
+    /// `palette::chromatic_adaptation::AdaptInto`.
+    #[inline]
+    fn to_lab(&self) -> (f32, f32, f32) { (self.l, self.a, self.b) }
+}
+
+#[cfg(feature = "palette")]
+impl ToLab for palette::Srgb<u8> {
+    /// Converts the colour into L\*a\*b\* using `palette` crate.
+    fn to_lab(&self) -> (f32, f32, f32) {
+        self.into_format::<f32>().to_lab()
+    }
+}
+
+#[cfg(feature = "palette")]
+impl ToLab for palette::Srgb<f32> {
+    /// Converts the colour into L\*a\*b\* with D65 reference white using
+    /// `palette` crate.
+    fn to_lab(&self) -> (f32, f32, f32) {
+        use palette::IntoColor;
+        let lab: palette::Lab<palette::white_point::D65, f32> =
+            (*self).into_color();
+        lab.to_lab()
+    }
+}
 
 /// Calculates L\*a\*b\* for a grey colour with given sRGB component.
 ///
@@ -81,20 +228,52 @@ fn lab_from_grey(grey: u8) -> (f32, f32, f32) {
              *        = κ * y */
             /*     κ  = (29/3)^3 = 24389 / 27 */
             const KAPPA: f32 = 24389.0 / 27.0;
-            KAPPA * ys.powf(2.4)
+            KAPPA * FloatExt::powf(ys, 2.4)
         } else {
             /* Exponential part of gamma and c > ε part of lab mapping. */
             /*     y = ((grey / 255 + 0.055) / 1.055)^2.4
              *     y’ = y^(1/3)
              *        = ((grey / 255 + 0.055) / 1.055)^(2.4 / 3)
              *     l  = 116 * y’ - 16 */
-            116.0 * ys.powf(24.0 / 30.0) - 16.0
+            116.0 * FloatExt::powf(ys, 24.0 / 30.0) - 16.0
         }
     };
 
     (l, 0.0, 0.0)
 }
 
+/// Calculates L\*a\*b\* for a grey colour with given 16-bit sRGB component.
+///
+/// This follows the same derivation as [`lab_from_grey`] except that the
+/// encoded values are in the `0..=65535` range.
+#[cfg(feature = "rgb")]
+fn lab_from_grey_u16(grey: u16) -> (f32, f32, f32) {
+    let l = if grey <= 2650 {
+        /* Linear part of gamma and c < ε part of lab mapping. */
+        /*     l = grey * (κ / (12.92 * 65535)) */
+        /* κ / (12.92 * 65535) = 24389 / 27 / 12.92 / 65535
+         *                     = 24389 / 22861229.4
+         *                     = 243890 / 228612294 */
+        const KAPPA_OVER_D: f32 = 243890.0 / 228612294.0;
+        grey as f32 * KAPPA_OVER_D
+    } else {
+        const A: f32 = 0.055 * 65535.0;
+        const D: f32 = 1.055 * 65535.0;
+        let ys = (grey as f32 + A) / D;
+        if grey <= 6043 {
+            /* Exponential part of gamma and c < ε part of lab mapping. */
+            /*     l = κ * ((grey / 65535 + 0.055) / 1.055)^2.4 */
+            const KAPPA: f32 = 24389.0 / 27.0;
+            KAPPA * FloatExt::powf(ys, 2.4)
+        } else {
+            /* Exponential part of gamma and c > ε part of lab mapping. */
+            /*     l = 116 * ((grey / 65535 + 0.055) / 1.055)^(2.4 / 3) - 16 */
+            116.0 * FloatExt::powf(ys, 24.0 / 30.0) - 16.0
+        }
+    };
+
+    (l, 0.0, 0.0)
+}
 
 /// Tests that `lab_from_grey` gives results close to what `lab` crate gives.
 #[cfg(feature = "rgb")]
@@ -114,3 +293,237 @@ fn test_lab_from_grey() {
         .collect::<Vec<_>>();
     assert!(errors.is_empty(), "{:?}", errors);
 }
+
+/// Tests that `lab_from_grey_u16` gives results close to what `lab` crate
+/// gives for 8-bit values scaled to 16 bits.
+#[cfg(feature = "rgb")]
+#[test]
+fn test_lab_from_grey_u16() {
+    // Scaled values go through different rounding so allow slightly larger
+    // error than in `test_lab_from_grey`.
+    const EPSILON: f32 = crate::testutil::EPSILON / 50.0;
+    let errors = (0..=255)
+        .filter_map(|grey| {
+            let want = lab::Lab::from_rgb(&[grey, grey, grey]).l;
+            let got = lab_from_grey_u16(u16::from(grey) * 257).0;
+            if approx::abs_diff_eq!(want, got, epsilon = EPSILON) {
+                None
+            } else {
+                Some((grey, want, got))
+            }
+        })
+        .collect::<Vec<_>>();
+    assert!(errors.is_empty(), "{:?}", errors);
+
+    // The function should be continuous and monotonic over the whole range.
+    let mut prev = 0.0;
+    for grey in 1..=65535 {
+        let got = lab_from_grey_u16(grey).0;
+        assert!(prev < got && got - prev < 0.01, "{}: {} {}", grey, prev, got);
+        prev = got;
+    }
+    approx::assert_abs_diff_eq!(100.0, prev, epsilon = 0.0001);
+}
+
+/// Tests conversion of 16-bit RGB colours.
+#[cfg(feature = "rgb")]
+#[test]
+fn test_lab_from_rgb16() {
+    let white = rgb::RGB::<u16>::new(65535, 65535, 65535).to_lab();
+    approx::assert_abs_diff_eq!(100.0, white.0, epsilon = 0.001);
+    approx::assert_abs_diff_eq!(0.0, white.1, epsilon = 0.001);
+    approx::assert_abs_diff_eq!(0.0, white.2, epsilon = 0.001);
+    assert_eq!((0.0, 0.0, 0.0), rgb::RGB::<u16>::new(0, 0, 0).to_lab());
+
+    // 8-bit values scaled by 257 map onto the same normalised values so the
+    // results should agree with the 8-bit conversion.
+    let mut errors = Vec::new();
+    for c in (0..=255u8).step_by(15) {
+        for (r, g, b) in [(c, c, c), (c, 255 - c, 128), (255, c, 0)] {
+            let want = crate::srgb::LinearRgb(
+                crate::srgb::decode(r),
+                crate::srgb::decode(g),
+                crate::srgb::decode(b),
+            )
+            .to_lab();
+            let (r16, g16, b16) =
+                (u16::from(r) * 257, u16::from(g) * 257, u16::from(b) * 257);
+            let got = rgb::RGB::new(r16, g16, b16).to_lab();
+            if crate::cie76::diff(want, got) > 0.001 {
+                errors.push(((r, g, b), want, got));
+            }
+        }
+    }
+    assert!(errors.is_empty(), "{:?}", errors);
+
+    // Values between 8-bit steps are not truncated.
+    let lo = rgb::RGB::<u16>::new(128 * 257, 0, 0).to_lab();
+    let mid = rgb::RGB::<u16>::new(128 * 257 + 128, 0, 0).to_lab();
+    assert!(lo.0 < mid.0);
+}
+
+/// Tests conversion of floating point RGB colours.
+#[cfg(feature = "rgb")]
+#[test]
+fn test_lab_from_rgb_f32() {
+    // Gamma-encoded values agree with 8-bit conversion.
+    for c in (0..=255u8).step_by(15) {
+        let want = rgb::RGB::<u16>::new(257 * u16::from(c), 0, 0).to_lab();
+        let got = rgb::RGB::new(f32::from(c) / 255.0, 0.0, 0.0).to_lab();
+        let diff = crate::cie76::diff(want, got);
+        assert!(diff < 0.001, "{}: {:?} {:?}", c, want, got);
+    }
+
+    // Values outside of the range are clamped.
+    let got = rgb::RGB::new(-0.5, 0.5, 1.5).to_lab();
+    assert_eq!(rgb::RGB::new(0.0, 0.5, 1.0).to_lab(), got);
+
+    // Encoded and linear mid-grey differ considerably.
+    let grey = rgb::RGB::new(0.5, 0.5, 0.5);
+    let encoded = grey.to_lab();
+    let linear = crate::LinearRgb::from(grey).to_lab();
+    approx::assert_abs_diff_eq!(53.39, encoded.0, epsilon = 0.01);
+    approx::assert_abs_diff_eq!(76.07, linear.0, epsilon = 0.01);
+}
+
+/// Checks that conversion of `rgb::RGB<u8>` colours gives results close to
+/// what `lab` crate gives.  Samples the RGB cube with given step in each
+/// dimension (always including 255).
+#[cfg(all(test, feature = "lab", feature = "rgb"))]
+fn check_rgb_cube(step: usize) {
+    let values = (0..=255u8)
+        .step_by(step)
+        .chain(std::iter::once(255))
+        .collect::<Vec<_>>();
+    let mut errors = Vec::new();
+    for &r in values.iter() {
+        for &g in values.iter() {
+            for &b in values.iter() {
+                let want = lab::Lab::from_rgb(&[r, g, b]);
+                let want = (want.l, want.a, want.b);
+                let got = rgb::RGB::new(r, g, b).to_lab();
+                let bgr = rgb::alt::BGR { b, g, r }.to_lab();
+                let array = [r, g, b].to_lab();
+                let diff = crate::cie76::diff(want, got);
+                if diff > crate::testutil::EPSILON || got != bgr || got != array
+                {
+                    errors.push(((r, g, b), want, got, bgr));
+                }
+                #[cfg(feature = "image")]
+                {
+                    let img = image::Rgb([r, g, b]).to_lab();
+                    if crate::cie76::diff(want, img) > crate::testutil::EPSILON
+                    {
+                        errors.push(((r, g, b), want, img, img));
+                    }
+                }
+            }
+        }
+    }
+    assert!(errors.is_empty(), "{:?}", errors);
+}
+
+#[cfg(all(feature = "lab", feature = "rgb"))]
+#[test]
+fn test_lab_from_u8_array() {
+    let colours = [[234, 76, 76], [76, 187, 234], [0, 0, 0], [255, 255, 255]];
+    for pair in colours.windows(2) {
+        let (a, b) = (pair[0], pair[1]);
+        let (rgb_a, rgb_b) = (crate::rgb8(a), crate::rgb8(b));
+        assert_eq!(rgb_a.to_lab(), a.to_lab());
+        assert_eq!(crate::cie00::diff(rgb_a, rgb_b), crate::cie00::diff(a, b));
+        assert_eq!(crate::cie76::diff(rgb_a, rgb_b), crate::cie76::diff(a, b));
+    }
+
+    // Arrays of floats are still treated as L*a*b* coordinates.
+    let lab = (54.528, 42.416, 54.497);
+    assert_eq!(lab, [54.528, 42.416, 54.497].to_lab());
+    assert_eq!(
+        crate::cie00::diff(crate::rgb8([234, 76, 76]), lab),
+        crate::cie00::diff([234, 76, 76], [54.528, 42.416, 54.497])
+    );
+}
+
+#[cfg(all(feature = "palette", feature = "lab", feature = "rgb"))]
+#[test]
+fn test_lab_from_palette() {
+    use palette::white_point::D65;
+
+    let lab = palette::Lab::<D65, f32>::new(38.972, 58.991, 37.138);
+    assert_eq!((38.972, 58.991, 37.138), lab.to_lab());
+
+    let mut errors = Vec::new();
+    for r in (0..=255).step_by(15) {
+        for g in (0..=255).step_by(15) {
+            for b in (0..=255).step_by(15) {
+                let want = rgb::RGB::<u8>::new(r, g, b).to_lab();
+                let got = palette::Srgb::<u8>::new(r, g, b).to_lab();
+                // palette uses slightly different conversion matrices.
+                if crate::cie76::diff(want, got) > 0.01 {
+                    errors.push(((r, g, b), want, got));
+                }
+                let float = palette::Srgb::<u8>::new(r, g, b)
+                    .into_format::<f32>()
+                    .to_lab();
+                assert_eq!(got, float);
+            }
+        }
+    }
+    assert!(errors.is_empty(), "{:?}", errors);
+}
+
+#[cfg(feature = "rgb")]
+#[test]
+fn test_lab_from_alpha_layouts() {
+    for &(r, g, b, a) in &[
+        (0, 0, 0, 255),
+        (234, 76, 76, 255),
+        (76, 187, 234, 128),
+        (10, 20, 30, 0),
+        (255, 255, 255, 1),
+    ] {
+        let want = rgb::RGBA::new(r, g, b, a).to_lab();
+        assert_eq!(want, rgb::alt::BGRA { b, g, r, a }.to_lab());
+        assert_eq!(want, rgb::alt::ARGB { a, r, g, b }.to_lab());
+        assert_eq!(want, rgb::alt::ABGR { a, b, g, r }.to_lab());
+        if a == 255 {
+            let opaque = crate::LinearRgb(
+                crate::srgb::decode(r),
+                crate::srgb::decode(g),
+                crate::srgb::decode(b),
+            );
+            let got = opaque.to_lab();
+            approx::assert_abs_diff_eq!(want.0, got.0, epsilon = 0.001);
+            approx::assert_abs_diff_eq!(want.1, got.1, epsilon = 0.001);
+            approx::assert_abs_diff_eq!(want.2, got.2, epsilon = 0.001);
+        }
+    }
+}
+
+#[test]
+fn test_lab_from_slice() {
+    let buffer: [f32; 6] = [50.0, 10.0, -20.0, 60.0, 5.0, 5.0];
+    let array = [50.0, 10.0, -20.0];
+    assert_eq!(array.to_lab(), buffer[..3].to_lab());
+    assert_eq!(array.to_lab(), buffer[..].to_lab());
+    let want = crate::cie00::diff(array, [60.0, 5.0, 5.0]);
+    assert_eq!(want, crate::cie00::diff(&buffer[..3], &buffer[3..]));
+}
+
+#[test]
+#[should_panic(expected = "expected at least three L*a*b* coordinates")]
+fn test_lab_from_short_slice() { [50.0f32, 0.0][..].to_lab(); }
+
+/// Tests that conversion of RGB colours agrees with the `lab` crate on
+/// a sample of the RGB cube.
+#[cfg(all(feature = "lab", feature = "rgb"))]
+#[test]
+fn test_lab_from_rgb() { check_rgb_cube(15) }
+
+/// Tests that conversion of RGB colours agrees with the `lab` crate on the
+/// whole RGB cube.  This takes a while so it’s ignored by default; run with
+/// `cargo test -- --ignored` to include it.
+#[cfg(all(feature = "lab", feature = "rgb"))]
+#[test]
+#[ignore]
+fn test_lab_from_rgb_full() { check_rgb_cube(1) }
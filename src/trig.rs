@@ -0,0 +1,118 @@
+// Table-based trigonometric functions.
+// Copyright (c) 2026 Michał Nazarewicz <mina86@mina86.com>
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in
+// all copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+
+//! Approximations of trigonometric functions using a lookup table.
+//!
+//! Used by the CIEDE2000 implementation when `fast-trig` feature is enabled.
+
+use core::f32::consts::{FRAC_PI_2, TAU};
+
+use crate::float::FloatExt;
+
+/// Number of table entries per full turn.  Must be a power of two.
+const TABLE_SIZE: usize = 256;
+
+/// Returns an approximation of cosine of `x` (given in radians).
+///
+/// The value is linearly interpolated between entries of a table of cosines
+/// of evenly spaced angles.  The absolute error is below 7.6·10⁻⁵.
+#[inline]
+pub(crate) fn cos(x: f32) -> f32 {
+    let pos = x * (TABLE_SIZE as f32 / TAU);
+    let floor = FloatExt::floor(pos);
+    // Casting negative i32 to usize wraps around which, since TABLE_SIZE is
+    // a power of two, gives the correct index after masking.
+    let idx = (floor as i32 as usize) & (TABLE_SIZE - 1);
+    let (lo, hi) = (COS_TABLE[idx], COS_TABLE[idx + 1]);
+    lo + (hi - lo) * (pos - floor)
+}
+
+/// Returns an approximation of sine of `x` (given in radians).
+///
+/// See [`cos`] for description of the approximation.
+#[inline]
+pub(crate) fn sin(x: f32) -> f32 { cos(x - FRAC_PI_2) }
+
+/// Cosines of angles `2π · i / TABLE_SIZE` for `i` in `0..=TABLE_SIZE`.  The
+/// last entry duplicates the first one so that interpolation doesn’t need to
+/// wrap around.
+#[rustfmt::skip]
+static COS_TABLE: [f32; TABLE_SIZE + 1] = [
+    1.0, 0.9996988, 0.99879545, 0.99729043, 0.9951847, 0.99247956, 0.9891765,
+    0.98527765, 0.98078525, 0.9757021, 0.97003126, 0.96377605, 0.95694035,
+    0.94952816, 0.94154406, 0.9329928, 0.9238795, 0.9142098, 0.9039893,
+    0.8932243, 0.8819213, 0.87008697, 0.8577286, 0.8448536, 0.8314696,
+    0.8175848, 0.8032075, 0.7883464, 0.77301043, 0.7572088, 0.7409511,
+    0.7242471, 0.70710677, 0.68954057, 0.671559, 0.65317285, 0.6343933,
+    0.6152316, 0.5956993, 0.57580817, 0.55557024, 0.53499764, 0.51410276,
+    0.4928982, 0.47139674, 0.44961134, 0.42755508, 0.4052413, 0.38268343,
+    0.35989505, 0.33688986, 0.31368175, 0.29028466, 0.26671275, 0.24298018,
+    0.21910124, 0.19509032, 0.17096189, 0.14673047, 0.12241068, 0.09801714,
+    0.07356457, 0.049067676, 0.024541229, 6.123234e-17, -0.024541229,
+    -0.049067676, -0.07356457, -0.09801714, -0.12241068, -0.14673047,
+    -0.17096189, -0.19509032, -0.21910124, -0.24298018, -0.26671275,
+    -0.29028466, -0.31368175, -0.33688986, -0.35989505, -0.38268343, -0.4052413,
+    -0.42755508, -0.44961134, -0.47139674, -0.4928982, -0.51410276, -0.53499764,
+    -0.55557024, -0.57580817, -0.5956993, -0.6152316, -0.6343933, -0.65317285,
+    -0.671559, -0.68954057, -0.70710677, -0.7242471, -0.7409511, -0.7572088,
+    -0.77301043, -0.7883464, -0.8032075, -0.8175848, -0.8314696, -0.8448536,
+    -0.8577286, -0.87008697, -0.8819213, -0.8932243, -0.9039893, -0.9142098,
+    -0.9238795, -0.9329928, -0.94154406, -0.94952816, -0.95694035, -0.96377605,
+    -0.97003126, -0.9757021, -0.98078525, -0.98527765, -0.9891765, -0.99247956,
+    -0.9951847, -0.99729043, -0.99879545, -0.9996988, -1.0, -0.9996988,
+    -0.99879545, -0.99729043, -0.9951847, -0.99247956, -0.9891765, -0.98527765,
+    -0.98078525, -0.9757021, -0.97003126, -0.96377605, -0.95694035, -0.94952816,
+    -0.94154406, -0.9329928, -0.9238795, -0.9142098, -0.9039893, -0.8932243,
+    -0.8819213, -0.87008697, -0.8577286, -0.8448536, -0.8314696, -0.8175848,
+    -0.8032075, -0.7883464, -0.77301043, -0.7572088, -0.7409511, -0.7242471,
+    -0.70710677, -0.68954057, -0.671559, -0.65317285, -0.6343933, -0.6152316,
+    -0.5956993, -0.57580817, -0.55557024, -0.53499764, -0.51410276, -0.4928982,
+    -0.47139674, -0.44961134, -0.42755508, -0.4052413, -0.38268343, -0.35989505,
+    -0.33688986, -0.31368175, -0.29028466, -0.26671275, -0.24298018,
+    -0.21910124, -0.19509032, -0.17096189, -0.14673047, -0.12241068,
+    -0.09801714, -0.07356457, -0.049067676, -0.024541229, -1.8369701e-16,
+    0.024541229, 0.049067676, 0.07356457, 0.09801714, 0.12241068, 0.14673047,
+    0.17096189, 0.19509032, 0.21910124, 0.24298018, 0.26671275, 0.29028466,
+    0.31368175, 0.33688986, 0.35989505, 0.38268343, 0.4052413, 0.42755508,
+    0.44961134, 0.47139674, 0.4928982, 0.51410276, 0.53499764, 0.55557024,
+    0.57580817, 0.5956993, 0.6152316, 0.6343933, 0.65317285, 0.671559,
+    0.68954057, 0.70710677, 0.7242471, 0.7409511, 0.7572088, 0.77301043,
+    0.7883464, 0.8032075, 0.8175848, 0.8314696, 0.8448536, 0.8577286,
+    0.87008697, 0.8819213, 0.8932243, 0.9039893, 0.9142098, 0.9238795,
+    0.9329928, 0.94154406, 0.94952816, 0.95694035, 0.96377605, 0.97003126,
+    0.9757021, 0.98078525, 0.98527765, 0.9891765, 0.99247956, 0.9951847,
+    0.99729043, 0.99879545, 0.9996988, 1.0,
+];
+
+#[cfg(test)]
+mod tests {
+    #[test]
+    fn test_trig() {
+        let mut x = -20.0f32;
+        while x < 30.0 {
+            let (want_cos, want_sin) = (x.cos(), x.sin());
+            let (got_cos, got_sin) = (super::cos(x), super::sin(x));
+            approx::assert_abs_diff_eq!(want_cos, got_cos, epsilon = 0.000076);
+            approx::assert_abs_diff_eq!(want_sin, got_sin, epsilon = 0.000076);
+            x += 0.001;
+        }
+    }
+}
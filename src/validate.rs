@@ -0,0 +1,140 @@
+// Validation of L*a*b* colours.
+// Copyright (c) 2026 Michał Nazarewicz <mina86@mina86.com>
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in
+// all copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+
+use crate::float::Float;
+use crate::ToLab;
+
+/// Error returned by [`validate_lab`] when a colour is out of range.
+///
+/// The variants carry the offending value.
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum LabRangeError {
+    /// Lightness L\* is outside of the `0..=100` range or is NaN.
+    Lightness(f32),
+    /// Chroma C\*ab is greater than [`LabRangeError::MAX_CHROMA`] or is NaN.
+    Chroma(f32),
+}
+
+impl LabRangeError {
+    /// Largest chroma accepted by [`validate_lab`].
+    ///
+    /// Chroma of sRGB colours doesn’t exceed 134 and that of real surface
+    /// colours is lower still so greater values most likely indicate
+    /// measurement or conversion errors.
+    pub const MAX_CHROMA: f32 = 200.0;
+}
+
+impl core::fmt::Display for LabRangeError {
+    fn fmt(&self, fmtr: &mut core::fmt::Formatter) -> core::fmt::Result {
+        match *self {
+            Self::Lightness(l) => {
+                write!(fmtr, "lightness {} outside of the 0–100 range", l)
+            }
+            Self::Chroma(c) => write!(
+                fmtr,
+                "chroma {} greater than {}",
+                c,
+                Self::MAX_CHROMA
+            ),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for LabRangeError {}
+
+/// Checks whether L\*a\*b\* coordinates of a colour are within sensible
+/// range.
+///
+/// Colour difference functions accept any coordinates but produce meaningless
+/// results for physically impossible colours.  For example, CIEDE2000
+/// compensates chroma of colours with the `C⁷/(C⁷ + 25⁷)` term which
+/// saturates for large chroma.  This function can be used to reject such
+/// inputs (e.g. erroneous measurements) before calculating the difference.
+///
+/// Lightness L\* must be within the `0..=100` range and chroma C\*ab must not
+/// exceed [`LabRangeError::MAX_CHROMA`].  Lightness is checked first.
+///
+/// ## Example
+///
+/// ```
+/// use empfindung::{validate_lab, LabRangeError};
+///
+/// assert_eq!(Ok(()), validate_lab((54.528, 42.416, 54.497)));
+/// assert_eq!(Err(LabRangeError::Lightness(150.0)),
+///            validate_lab((150.0, 0.0, 0.0)));
+/// assert_eq!(Err(LabRangeError::Chroma(500.0)),
+///            validate_lab((50.0, 300.0, 400.0)));
+/// ```
+pub fn validate_lab(colour: impl ToLab) -> Result<(), LabRangeError> {
+    let (l, a, b) = colour.to_lab();
+    if !(0.0..=100.0).contains(&l) {
+        return Err(LabRangeError::Lightness(l));
+    }
+    let c = Float::hypot(a, b);
+    if !(0.0..=LabRangeError::MAX_CHROMA).contains(&c) {
+        return Err(LabRangeError::Chroma(c));
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_validate_lab() {
+        for colour in crate::testutil::generate_colours(1000) {
+            assert_eq!(Ok(()), validate_lab(colour));
+        }
+        for (colour, want) in [
+            ((150.0, 0.0, 0.0), LabRangeError::Lightness(150.0)),
+            ((-1.0, 0.0, 0.0), LabRangeError::Lightness(-1.0)),
+            ((150.0, 0.0, -250.0), LabRangeError::Lightness(150.0)),
+            ((50.0, 0.0, -250.0), LabRangeError::Chroma(250.0)),
+            ((100.0, 300.0, 400.0), LabRangeError::Chroma(500.0)),
+        ] {
+            assert_eq!(Err(want), validate_lab(colour));
+        }
+        assert_eq!(Ok(()), validate_lab((100.0, 120.0, 160.0)));
+        assert!(matches!(
+            validate_lab((f32::NAN, 0.0, 0.0)),
+            Err(LabRangeError::Lightness(_))
+        ));
+        assert!(matches!(
+            validate_lab((50.0, f32::NAN, 0.0)),
+            Err(LabRangeError::Chroma(_))
+        ));
+    }
+
+    #[test]
+    #[cfg(all(feature = "lab", feature = "rgb"))]
+    fn test_validate_srgb() {
+        for r in (0..=255).step_by(17) {
+            for g in (0..=255).step_by(17) {
+                for b in (0..=255).step_by(17) {
+                    let colour = rgb::RGB8::new(r, g, b);
+                    assert_eq!(Ok(()), validate_lab(colour), "{:?}", colour);
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,378 @@
+// CIE XYZ colour space handling.
+// Copyright (c) 2026 Michał Nazarewicz <mina86@mina86.com>
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in
+// all copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+
+//! Handling of the CIE XYZ colour space.
+//!
+//! The module provides [`WhitePoint`] type describing reference white used when
+//! converting between XYZ and L\*a\*b\* colour spaces as well as colorimetric
+//! quantities which are defined in terms of XYZ tristimulus values (such as
+//! [`metamerism_index`]).  [`Xyz`] type allows colours given by their
+//! tristimulus values to be used with colour difference functions.
+//!
+//! Throughout the crate, XYZ coordinates are normalised such that the Y
+//! coordinate of the reference white equals one (rather than 100).
+
+use crate::float::FloatExt;
+
+// κ and ε parameters used in conversion between XYZ and L*a*b*.  See
+// http://www.brucelindbloom.com/LContinuity.html for explanation as to why
+// those are different values than those provided by CIE standard.
+const KAPPA: f32 = 24389.0 / 27.0;
+const EPSILON: f32 = 216.0 / 24389.0;
+
+/// XYZ coordinates of the D65 reference white (with Y normalised to one).
+///
+/// Those are the values matching the sRGB to XYZ matrix used by the crate
+/// (and the `lab` crate) rather than the ones tabulated by CIE.
+pub(crate) const D65: (f32, f32, f32) =
+    (0.9504492182750991, 1.0, 1.0889166484304715);
+
+/// Reference white used when converting XYZ coordinates to L\*a\*b\*.
+///
+/// The default is D65 which is the white point of the sRGB colour space.
+#[derive(Clone, Copy, PartialEq, Debug, Default)]
+pub enum WhitePoint {
+    /// CIE standard illuminant D65 (noon daylight).
+    #[default]
+    D65,
+    /// CIE standard illuminant D50 (horizon light), commonly used in print.
+    D50,
+    /// Custom white point given by its XYZ coordinates.
+    Custom {
+        /// The X tristimulus value on the same scale as `y`.
+        x: f32,
+        /// The Y tristimulus value (luminance) of the white point.  Colours
+        /// are normalised to Y of one so this should be 1.0 rather than 100.
+        y: f32,
+        /// The Z tristimulus value on the same scale as `y`.
+        z: f32,
+    },
+}
+
+impl WhitePoint {
+    /// Returns XYZ coordinates of the white point.
+    pub fn xyz(&self) -> (f32, f32, f32) {
+        match *self {
+            Self::D65 => D65,
+            Self::D50 => (0.9642956764295677, 1.0, 0.8251046025104602),
+            Self::Custom { x, y, z } => (x, y, z),
+        }
+    }
+}
+
+/// Colour given by its CIE XYZ tristimulus values.
+///
+/// The type implements [`ToLab`](crate::ToLab) trait which lets colours
+/// obtained e.g. from a spectrophotometer be passed directly to functions
+/// calculating colour difference.  The conversion to L\*a\*b\* is done
+/// relative to the `white` reference white which is D65 by default.  The
+/// Y coordinate should be in the `0..=1` range.
+///
+/// ## Example
+///
+/// ```
+/// use empfindung::cie00;
+/// use empfindung::xyz::{WhitePoint, Xyz};
+///
+/// let colour_1 = Xyz::new(0.2, 0.3, 0.4);
+/// let colour_2 = Xyz::new(0.25, 0.3, 0.3);
+/// let delta_e = cie00::diff(colour_1, colour_2);
+/// approx::assert_abs_diff_eq!(13.94, delta_e, epsilon = 0.01);
+///
+/// let colour_1 = Xyz::with_white(0.2, 0.3, 0.4, WhitePoint::D50);
+/// let colour_2 = Xyz::with_white(0.25, 0.3, 0.3, WhitePoint::D50);
+/// let delta_e = cie00::diff(colour_1, colour_2);
+/// approx::assert_abs_diff_eq!(11.26, delta_e, epsilon = 0.01);
+/// ```
+#[derive(Clone, Copy, PartialEq, Debug, Default)]
+pub struct Xyz {
+    /// The X tristimulus value on the same scale as `y`.
+    pub x: f32,
+    /// The Y tristimulus value (luminance) normalised such that Y of the
+    /// `white` reference white equals one.
+    pub y: f32,
+    /// The Z tristimulus value on the same scale as `y`.
+    pub z: f32,
+    /// Reference white used when converting the colour to L\*a\*b\*.
+    pub white: WhitePoint,
+}
+
+impl Xyz {
+    /// Constructs a new colour with D65 reference white.
+    pub fn new(x: f32, y: f32, z: f32) -> Self {
+        Self::with_white(x, y, z, WhitePoint::D65)
+    }
+
+    /// Constructs a new colour with given reference white.
+    pub fn with_white(x: f32, y: f32, z: f32, white: WhitePoint) -> Self {
+        Self { x, y, z, white }
+    }
+}
+
+impl crate::ToLab for Xyz {
+    fn to_lab(&self) -> (f32, f32, f32) {
+        to_lab((self.x, self.y, self.z), self.white.xyz())
+    }
+}
+
+/// Returns the CIE metamerism index of two samples.
+///
+/// Two colours are metameric if they match under one illuminant (the reference
+/// one) but differ under another (the test one).  Each sample is described by
+/// a pair of XYZ tristimulus values: one measured under the `reference`
+/// illuminant and the other under the `test` illuminant.  The Y coordinate
+/// should be in the `0..=1` range.
+///
+/// Since in practice the samples rarely match exactly under the reference
+/// illuminant, the function performs an additive correction in L\*a\*b\*
+/// colour space: the mismatch of the samples under the reference illuminant
+/// is subtracted from the second sample under the test illuminant.  The
+/// result is the CIE76 colour difference (ΔE\*ab) between the corrected
+/// samples under the test illuminant.
+///
+/// ## Example
+///
+/// ```
+/// use empfindung::xyz;
+///
+/// // Identical samples are never metameric.
+/// let sample = ((0.2, 0.3, 0.4), (0.25, 0.3, 0.3));
+/// let index = xyz::metamerism_index(
+///     sample, sample, xyz::WhitePoint::D65, xyz::WhitePoint::D50);
+/// assert_eq!(0.0, index);
+///
+/// // Samples which match under D65 but not under D50.
+/// let sample_1 = ((0.2, 0.3, 0.4), (0.25, 0.3, 0.3));
+/// let sample_2 = ((0.2, 0.3, 0.4), (0.22, 0.3, 0.33));
+/// let index = xyz::metamerism_index(
+///     sample_1, sample_2, xyz::WhitePoint::D65, xyz::WhitePoint::D50);
+/// assert!(index > 5.0);
+/// ```
+pub fn metamerism_index(
+    sample_1: ((f32, f32, f32), (f32, f32, f32)),
+    sample_2: ((f32, f32, f32), (f32, f32, f32)),
+    reference: WhitePoint,
+    test: WhitePoint,
+) -> f32 {
+    let (reference, test) = (reference.xyz(), test.xyz());
+    let ref_1 = to_lab(sample_1.0, reference);
+    let ref_2 = to_lab(sample_2.0, reference);
+    let test_1 = to_lab(sample_1.1, test);
+    let test_2 = to_lab(sample_2.1, test);
+    let test_2 = (
+        test_2.0 - (ref_2.0 - ref_1.0),
+        test_2.1 - (ref_2.1 - ref_1.1),
+        test_2.2 - (ref_2.2 - ref_1.2),
+    );
+    crate::cie76::diff(test_1, test_2)
+}
+
+/// Converts XYZ coordinates into L\*a\*b\* relative to given reference white.
+///
+/// Y coordinate of the colour is expected to be in the `0..=1` range (though
+/// larger values are extrapolated).
+pub(crate) fn to_lab(
+    xyz: (f32, f32, f32),
+    white: (f32, f32, f32),
+) -> (f32, f32, f32) {
+    fn map(c: f32) -> f32 {
+        if c > EPSILON {
+            FloatExt::powf(c, 1.0 / 3.0)
+        } else {
+            (KAPPA * c + 16.0) / 116.0
+        }
+    }
+
+    let x = map(xyz.0 / white.0);
+    let y = map(xyz.1 / white.1);
+    let z = map(xyz.2 / white.2);
+    (116.0 * y - 16.0, 500.0 * (x - y), 200.0 * (y - z))
+}
+
+/// Adapts XYZ coordinates of a colour seen under the `from` reference white
+/// into coordinates of the corresponding colour seen under the `to` reference
+/// white.
+///
+/// Uses the Bradford chromatic adaptation transform.
+#[cfg(feature = "rgb")]
+pub(crate) fn adapt(
+    xyz: (f32, f32, f32),
+    from: (f32, f32, f32),
+    to: (f32, f32, f32),
+) -> (f32, f32, f32) {
+    #[rustfmt::skip]
+    const BRADFORD: [[f32; 3]; 3] = [
+        [ 0.8951,  0.2664, -0.1614],
+        [-0.7502,  1.7135,  0.0367],
+        [ 0.0389, -0.0685,  1.0296],
+    ];
+    #[rustfmt::skip]
+    const BRADFORD_INV: [[f32; 3]; 3] = [
+        [ 0.9869929054667121,   -0.14705425642099013, 0.15996265166373122],
+        [ 0.4323052697233945,    0.5183602715367776,  0.0492912282128556],
+        [-0.008528664575177326,  0.04004282165408486, 0.9684866957875499],
+    ];
+
+    use crate::srgb::mul;
+    let (from, to) = (mul(&BRADFORD, from), mul(&BRADFORD, to));
+    let cone = mul(&BRADFORD, xyz);
+    let cone = (
+        cone.0 * to.0 / from.0,
+        cone.1 * to.1 / from.1,
+        cone.2 * to.2 / from.2,
+    );
+    mul(&BRADFORD_INV, cone)
+}
+
+/// Converts L\*a\*b\* coordinates relative to given reference white into XYZ.
+///
+/// This is an inverse of [`to_lab`].
+pub(crate) fn from_lab(
+    lab: (f32, f32, f32),
+    white: (f32, f32, f32),
+) -> (f32, f32, f32) {
+    fn map(f: f32) -> f32 {
+        let cube = f * f * f;
+        if cube > EPSILON {
+            cube
+        } else {
+            (116.0 * f - 16.0) / KAPPA
+        }
+    }
+
+    let fy = (lab.0 + 16.0) / 116.0;
+    let fx = fy + lab.1 / 500.0;
+    let fz = fy - lab.2 / 200.0;
+    let y = if lab.0 > KAPPA * EPSILON {
+        fy * fy * fy
+    } else {
+        lab.0 / KAPPA
+    };
+    (map(fx) * white.0, y * white.1, map(fz) * white.2)
+}
+
+/// Applies the SMPTE ST 2084 perceptual quantizer (i.e. the inverse of the PQ
+/// EOTF) to luminance given in cd/m² using given `m_2` exponent.
+///
+/// Negative values (which may appear for colours outside of the spectral
+/// locus) are clamped to zero since otherwise raising them to a fractional
+/// power would produce NaN.
+pub(crate) fn pq(luminance: f32, m_2: f32) -> f32 {
+    const C_1: f32 = 3424.0 / 4096.0;
+    const C_2: f32 = 2413.0 / 128.0;
+    const C_3: f32 = 2392.0 / 128.0;
+    const N: f32 = 2610.0 / 16384.0;
+
+    let y = FloatExt::powf(luminance.max(0.0) / 10000.0, N);
+    FloatExt::powf((C_1 + C_2 * y) / (1.0 + C_3 * y), m_2)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_white_to_lab() {
+        for white in [WhitePoint::D65, WhitePoint::D50] {
+            let got = to_lab(white.xyz(), white.xyz());
+            assert_eq!((100.0, 0.0, 0.0), got);
+        }
+    }
+
+    #[test]
+    fn test_xyz_to_lab() {
+        use crate::ToLab;
+
+        assert_eq!((0.0, 0.0, 0.0), Xyz::new(0.0, 0.0, 0.0).to_lab());
+        for white in [WhitePoint::D65, WhitePoint::D50] {
+            let (x, y, z) = white.xyz();
+            let got = Xyz::with_white(x, y, z, white).to_lab();
+            assert_eq!((100.0, 0.0, 0.0), got);
+        }
+        let got = Xyz::new(0.2, 0.3, 0.4).to_lab();
+        let want = to_lab((0.2, 0.3, 0.4), D65);
+        assert_eq!(want, got);
+    }
+
+    #[test]
+    #[cfg(feature = "rgb")]
+    fn test_adapt() {
+        let (d65, d50) = (WhitePoint::D65.xyz(), WhitePoint::D50.xyz());
+        let check = |want: (f32, f32, f32), got: (f32, f32, f32)| {
+            approx::assert_abs_diff_eq!(want.0, got.0, epsilon = 0.00001);
+            approx::assert_abs_diff_eq!(want.1, got.1, epsilon = 0.00001);
+            approx::assert_abs_diff_eq!(want.2, got.2, epsilon = 0.00001);
+        };
+        check(d50, adapt(d65, d65, d50));
+        check(d65, adapt(d50, d50, d65));
+        check((0.2, 0.3, 0.4), adapt((0.2, 0.3, 0.4), d65, d65));
+        let adapted = adapt((0.2, 0.3, 0.4), d65, d50);
+        check((0.2, 0.3, 0.4), adapt(adapted, d50, d65));
+        check((0.0, 0.0, 0.0), adapt((0.0, 0.0, 0.0), d65, d50));
+    }
+
+    #[test]
+    fn test_from_lab() {
+        let white = WhitePoint::D65.xyz();
+        for xyz in [
+            (0.0, 0.0, 0.0),
+            (0.001, 0.002, 0.003),
+            (0.2, 0.3, 0.4),
+            (0.9, 0.5, 0.1),
+            white,
+        ] {
+            let got = from_lab(to_lab(xyz, white), white);
+            approx::assert_abs_diff_eq!(xyz.0, got.0, epsilon = 0.00001);
+            approx::assert_abs_diff_eq!(xyz.1, got.1, epsilon = 0.00001);
+            approx::assert_abs_diff_eq!(xyz.2, got.2, epsilon = 0.00001);
+        }
+    }
+
+    #[test]
+    fn test_pq() {
+        let m_2 = 2523.0 / 32.0;
+        approx::assert_abs_diff_eq!(1.0, pq(10000.0, m_2), epsilon = 0.00001);
+        assert_eq!(pq(0.0, m_2), pq(-1.0, m_2));
+        assert!(pq(0.0, m_2) < 1e-6);
+    }
+
+    #[test]
+    fn test_metamerism_index_symmetric() {
+        let sample_1 = ((0.2, 0.3, 0.4), (0.25, 0.3, 0.3));
+        let sample_2 = ((0.21, 0.3, 0.39), (0.22, 0.3, 0.33));
+        let (d65, d50) = (WhitePoint::D65, WhitePoint::D50);
+        let lhs = metamerism_index(sample_1, sample_2, d65, d50);
+        let rhs = metamerism_index(sample_2, sample_1, d65, d50);
+        approx::assert_abs_diff_eq!(lhs, rhs, epsilon = 0.001);
+    }
+
+    #[test]
+    fn test_metamerism_index_correction() {
+        // If the samples differ under the test illuminant in exactly the same
+        // way as they do under the reference illuminant, they are not
+        // metameric.
+        let white = WhitePoint::D65;
+        let sample_1 = ((0.2, 0.3, 0.4), (0.2, 0.3, 0.4));
+        let sample_2 = ((0.22, 0.3, 0.33), (0.22, 0.3, 0.33));
+        let index = metamerism_index(sample_1, sample_2, white, white);
+        approx::assert_abs_diff_eq!(0.0, index, epsilon = 0.001);
+    }
+}
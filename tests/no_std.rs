@@ -0,0 +1,27 @@
+//! Smoke test of the library built without `std`.
+//!
+//! Unit tests are always built with `std` which means they never exercise
+//! the `libm` code paths the library uses when `std` feature is disabled.
+//! Integration tests link the library as built for regular use so running
+//! this test with `--no-default-features` checks that the `no_std` build
+//! compiles and produces correct results.
+
+#![no_std]
+
+use empfindung::{cie00, cie76, cie94, cmc};
+
+const REFERENCE: (f32, f32, f32) = (50.0, 2.6772, -79.7751);
+const SAMPLE: (f32, f32, f32) = (50.0, 0.0, -82.7485);
+
+fn assert_close(want: f32, got: f32) {
+    assert!((want - got).abs() < 1e-4, "want: {}, got: {}", want, got);
+}
+
+#[test]
+fn test_diff() {
+    assert_close(4.0011, cie76::diff(REFERENCE, SAMPLE));
+    let params = cie94::Params::graphic();
+    assert_close(1.3950, cie94::diff(REFERENCE, SAMPLE, params));
+    assert_close(1.7387, cmc::diff(REFERENCE, SAMPLE, cmc::LC11));
+    assert_close(2.0425, cie00::diff(REFERENCE, SAMPLE));
+}